@@ -24,20 +24,15 @@ fn init_logger() {
 }
 
 fn main() {
-    use std::io;
-
-    let stdin = io::stdin();
-    let mut input = stdin.lock();
-
     init_logger();
 
-    loop {
-        match Message::try_from_input(&mut input) {
+    for message in Adapter::single_session_mode() {
+        match message {
             Ok(message) => {
                 info!("seq={}", message.seq());
 
-                if let Some(request) = message.message_kind() {
-                    if let Some(init) = request.request_kind() {
+                if let Some(MessageKind::Request(request)) = message.message_kind() {
+                    if let RequestKind::Initialize(init) = request.request_kind() {
                         info!("init={:#?}", init);
                     } else {
                         info!("command={}", request.command());
@@ -47,13 +42,10 @@ fn main() {
                     }
                 } else {
                     info!("type={}", message.message_type());
-                    info!("raw={:#}", message.raw_value);
+                    info!("raw={:#}", message.body());
                 }
             }
-            Err(error) => {
-                error!("error: {}", error);
-                break;
-            }
+            Err(error) => error!("error: {}", error),
         }
     }
 }
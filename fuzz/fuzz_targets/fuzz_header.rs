@@ -0,0 +1,10 @@
+#![no_main]
+
+use headcrab_dap::Header;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mut input = data;
+    // any input is fine, as long as it is rejected with a error rather than a panic
+    let _ = Header::from_input(&mut input);
+});
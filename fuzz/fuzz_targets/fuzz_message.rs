@@ -0,0 +1,11 @@
+#![no_main]
+
+use headcrab_dap::Message;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // `try_from_input` reject a message larger than the default limit before allocating its
+    // content, so the announced length is not filtered here
+    let mut input = data;
+    let _ = Message::try_from_input(&mut input);
+});
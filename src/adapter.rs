@@ -0,0 +1,1825 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io;
+use std::io::{BufRead, Read, Write};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::pending::PendingResponses;
+use crate::util::SeqCounter;
+use crate::{
+    CancelArguments, Capabilities, CapabilitiesEvent, Error, EventKind, Header,
+    InitializeRequestArguments, Message, MessageKind, OutputEvent, OutputGroup, ProgressTracker,
+    ProgressUpdateEvent, Request, RequestKind, Response, RunInTerminalRequestArguments,
+    RunInTerminalResponseBody, StartDebuggingRequestArguments,
+};
+
+mod config;
+mod session;
+
+pub use config::{ErrorStrategy, SessionConfig, SessionConfigBuilder};
+pub use session::{Session, Sessions};
+
+/// The debug adapter side of a dap session.
+///
+/// The adapter read the messages sent by the client on a background thread, and yield them
+/// through its `Iterator` implementation. The iterator end after the first error.
+///
+/// Responses to the requests sent by the adapter are not yielded by the iterator,
+/// they are instead returned to the caller of the method which sent the request.
+///
+/// Cancel requests are answered by the adapter itself, as soon as they are read.
+/// They are still yielded by the iterator, but must not be answered again.
+///
+/// The adapter only start reading once it is first iterated, or once it send its first request.
+pub struct Adapter {
+    receiver: Box<dyn ChannelReceiver<Result<Message, Error>> + Send>,
+    output: Arc<Output>,
+    cancellations: Arc<CancellationRegistry>,
+    pending_requests: Arc<PendingRequests>,
+    in_flight: Arc<InFlightRequests>,
+    error_handler: Arc<Mutex<Option<ErrorHandler>>>,
+    max_message_size: Arc<AtomicUsize>,
+    request_timeout: Arc<Mutex<Option<Duration>>>,
+    client: Arc<Mutex<Option<InitializeRequestArguments>>>,
+    capabilities: Mutex<Capabilities>,
+    listener: Mutex<Option<Box<dyn FnOnce() + Send>>>,
+}
+
+/// The largest message accepted by default, in bytes.
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+
+type ErrorHandler = Box<dyn Fn(&Error) + Send>;
+
+impl Adapter {
+    /// Start a adapter reading the messages of a single session from stdin,
+    /// and writing its own messages to stdout.
+    #[cfg(feature = "stdio-adapter")]
+    pub fn single_session_mode() -> Self {
+        Self::with_reader_writer(io::BufReader::new(io::stdin()), io::stdout())
+    }
+
+    /// Start a adapter accepting its clients on a tcp socket bound to `addr`.
+    ///
+    /// Each connection is served as its own session, with its own sequence numbers.
+    pub fn multi_session_mode(addr: SocketAddr) -> Result<Sessions, Error> {
+        Sessions::bind(addr)
+    }
+
+    /// Start a adapter reading the messages of a single session from `input`,
+    /// and writing its own messages to `output`.
+    ///
+    /// This is mostly useful to test an adapter without touching stdin and stdout.
+    pub fn with_reader_writer<R, W>(input: R, output: W) -> Self
+    where
+        R: BufRead + Send + 'static,
+        W: Write + Send + 'static,
+    {
+        Self::from_config(SessionConfig::default(), input, output)
+    }
+
+    /// Like `with_reader_writer`, with the settings of `config` rather than the default ones.
+    pub fn from_config<R, W>(config: SessionConfig, input: R, output: W) -> Self
+    where
+        R: BufRead + Send + 'static,
+        W: Write + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        Self::with_clock(
+            config,
+            input,
+            output,
+            (sender, receiver),
+            Arc::new(SystemClock),
+        )
+    }
+
+    /// Like `from_config`, forwarding the messages read by the background thread through the
+    /// channel made of `sender` and `receiver`, rather than through a `std::sync::mpsc` channel.
+    ///
+    /// With the `crossbeam` feature, this accept the halves of a `crossbeam_channel` channel.
+    pub fn with_channel<R, W, S, C>(
+        config: SessionConfig,
+        input: R,
+        output: W,
+        sender: S,
+        receiver: C,
+    ) -> Self
+    where
+        R: BufRead + Send + 'static,
+        W: Write + Send + 'static,
+        S: ChannelSender<Result<Message, Error>> + Clone + Send + 'static,
+        C: ChannelReceiver<Result<Message, Error>> + Send + 'static,
+    {
+        Self::with_clock(
+            config,
+            input,
+            output,
+            (sender, receiver),
+            Arc::new(SystemClock),
+        )
+    }
+
+    /// Like `with_channel`, timing the requests with `clock`.
+    fn with_clock<R, W, S, C>(
+        config: SessionConfig,
+        input: R,
+        output: W,
+        (sender, receiver): (S, C),
+        clock: Arc<dyn Clock>,
+    ) -> Self
+    where
+        R: BufRead + Send + 'static,
+        W: Write + Send + 'static,
+        S: ChannelSender<Result<Message, Error>> + Clone + Send + 'static,
+        C: ChannelReceiver<Result<Message, Error>> + Send + 'static,
+    {
+        let output = Arc::new(Output::new(output, config.seq_start));
+        let cancellations = Arc::new(CancellationRegistry::default());
+        let pending_requests = Arc::new(PendingRequests::default());
+        let in_flight = Arc::new(InFlightRequests::new(clock));
+        let error_handler: Option<ErrorHandler> = match config.error_strategy {
+            ErrorStrategy::Fail => None,
+            ErrorStrategy::Recover => Some(Box::new(|_| ())),
+        };
+        let error_handler = Arc::new(Mutex::new(error_handler));
+        let max_message_size = Arc::new(AtomicUsize::new(config.max_message_bytes));
+        let request_timeout = Arc::new(Mutex::new(config.request_timeout));
+        let client = Arc::new(Mutex::new(None));
+
+        let watchdog = Watchdog {
+            sender: sender.clone(),
+            cancellations: cancellations.clone(),
+            in_flight: in_flight.clone(),
+        };
+        let listener = Listener {
+            input,
+            sender,
+            output: output.clone(),
+            cancellations: cancellations.clone(),
+            pending_requests: pending_requests.clone(),
+            in_flight: in_flight.clone(),
+            error_handler: error_handler.clone(),
+            max_message_size: max_message_size.clone(),
+            client: client.clone(),
+        };
+        let timeout = request_timeout.clone();
+        let start_listener = move || {
+            // without a timeout the watchdog is dropped, so it does not keep the channel open
+            if let Some(timeout) = *timeout.lock().unwrap() {
+                thread::spawn(move || watchdog.watch(timeout));
+            }
+            thread::spawn(move || listener.listen());
+        };
+
+        Self {
+            receiver: Box::new(receiver),
+            output,
+            cancellations,
+            pending_requests,
+            in_flight,
+            error_handler,
+            max_message_size,
+            request_timeout,
+            client,
+            capabilities: Mutex::default(),
+            listener: Mutex::new(Some(Box::new(start_listener))),
+        }
+    }
+
+    /// Register a callback for the messages which could not be read.
+    ///
+    /// By default, the adapter stop after the first error. With a handler, invalid messages are
+    /// instead passed to the handler and skipped, and the adapter only stop on i/o errors.
+    pub fn on_error(self, handler: impl Fn(&Error) + Send + 'static) -> Self {
+        self.set_error_handler(handler);
+        self
+    }
+
+    /// Set the largest message accepted from the client, in bytes.
+    ///
+    /// A larger message is skipped without being read into memory, and reported as a
+    /// `Error::MessageTooLarge`. The default is `DEFAULT_MAX_MESSAGE_SIZE`.
+    pub fn with_max_message_size(self, bytes: usize) -> Self {
+        self.max_message_size.store(bytes, Ordering::SeqCst);
+        self
+    }
+
+    /// Cancel the requests of the client which are still unanswered after `duration`.
+    ///
+    /// A request which time out is cancelled as if the client had sent a `cancel` request for it:
+    /// its cancellation token is cancelled, and a synthetic `cancel` request is yielded by the
+    /// iterator. The synthetic request has the sequence number 0, and must not be answered.
+    ///
+    /// The timeout must be set before the adapter is first iterated.
+    pub fn with_request_timeout(self, duration: Duration) -> Self {
+        *self.request_timeout.lock().unwrap() = Some(duration);
+        self
+    }
+
+    fn set_error_handler(&self, handler: impl Fn(&Error) + Send + 'static) {
+        *self.error_handler.lock().unwrap() = Some(Box::new(handler));
+    }
+
+    /// Start reading the session, if it was not started yet.
+    fn start(&self) {
+        if let Some(start_listener) = self.listener.lock().unwrap().take() {
+            start_listener();
+        }
+    }
+
+    /// Return true if the client sent a `cancel` request for the request with the sequence number `seq`.
+    ///
+    /// Since cancel requests are recorded as soon as they are read, this can be polled during a
+    /// long-running operation, before the cancel request is yielded by the iterator.
+    pub fn is_cancelled(&self, seq: usize) -> bool {
+        self.cancellations.is_cancelled(seq)
+    }
+
+    /// Return the token which will be cancelled when the client cancel the request `seq`.
+    ///
+    /// A long-running request handler should periodically check its token,
+    /// and abort its work once the token is cancelled.
+    pub fn cancellation_token(&self, seq: usize) -> CancellationToken {
+        self.cancellations.token(seq)
+    }
+
+    /// Return the sequence number to use for the next message sent by the adapter.
+    pub fn next_seq(&self) -> usize {
+        self.output.next_seq()
+    }
+
+    /// Return the counter of the session, which generate the sequence numbers of the adapter's
+    /// messages. Every message sent in the session should take its sequence number from it.
+    pub fn seq_counter(&self) -> &SeqCounter {
+        &self.output.seq
+    }
+
+    /// Return the number of requests read from the client which were not answered yet.
+    ///
+    /// `cancel` requests are not counted, since the adapter answer them itself.
+    pub fn pending_request_count(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Send a message to the client.
+    ///
+    /// The capabilities sent in the response to the 'initialize' request are recorded, see
+    /// `update_capabilities`.
+    pub fn send(&self, message: &Message) -> Result<(), Error> {
+        self.output.send(message)?;
+        if let Some(MessageKind::Response(response)) = message.message_kind() {
+            self.in_flight.resolve(response.request_seq());
+            // a answered request can no longer be cancelled
+            self.cancellations.remove(response.request_seq());
+
+            if response.command() == "initialize" && response.success() {
+                if let Ok(capabilities) = response.body_as() {
+                    *self.capabilities.lock().unwrap() = capabilities;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace the capabilities of the adapter by `capabilities`, sending a 'capabilities' event
+    /// with those which changed.
+    ///
+    /// Nothing is sent if no capability changed. The capabilities are compared to those of the
+    /// response to the 'initialize' request, and then to those of the last update.
+    pub fn update_capabilities(&self, capabilities: Capabilities) -> Result<(), Error> {
+        let mut current = self.capabilities.lock().unwrap();
+
+        let diff = current.diff(&capabilities);
+        *current = capabilities;
+        if diff == Capabilities::default() {
+            return Ok(());
+        }
+        self.event_emitter()
+            .emit(CapabilitiesEvent { capabilities: diff })
+    }
+
+    /// Return a handle which send events to the client of this adapter.
+    ///
+    /// The handle can be cloned and moved to other threads, for example to forward the output
+    /// of the debuggee while the adapter handle requests.
+    pub fn event_emitter(&self) -> EventEmitter {
+        EventEmitter {
+            output: self.output.clone(),
+        }
+    }
+
+    /// Return the arguments of the client's 'initialize' request, or `None` if it was not read yet.
+    ///
+    /// The arguments are recorded as soon as the request is read, so they are available once the
+    /// request is yielded by the iterator.
+    pub fn initialize_arguments(&self) -> Option<InitializeRequestArguments> {
+        self.client.lock().unwrap().clone()
+    }
+
+    /// Open a group in the output log of the client, see `EventEmitter::output_group`.
+    pub fn output_group(&self, name: &str, collapsed: bool) -> Result<OutputGroupGuard, Error> {
+        self.event_emitter().output_group(name, collapsed)
+    }
+
+    /// Start reporting the progress of a long running operation, sending the 'progressStart' event.
+    ///
+    /// If the client did not declare `supportsProgressReporting` in its 'initialize' request, the
+    /// returned reporter is disabled and send nothing at all.
+    pub fn start_progress(
+        &self,
+        title: &str,
+        cancellable: bool,
+    ) -> Result<ProgressReporter, Error> {
+        let supported = self
+            .initialize_arguments()
+            .and_then(|arguments| arguments.supports_progress_reporting)
+            .unwrap_or(false);
+
+        let mut reporter = ProgressReporter {
+            emitter: self.event_emitter(),
+            tracker: None,
+            percentage: 0.0,
+        };
+        if supported {
+            let (tracker, mut start) = ProgressTracker::start(title);
+            start.cancellable = Some(cancellable);
+            reporter.emitter.emit(start)?;
+            reporter.tracker = Some(tracker);
+        }
+        Ok(reporter)
+    }
+
+    /// Ask the client to run a command in a terminal, and wait for its response.
+    pub fn run_in_terminal(
+        &self,
+        arguments: RunInTerminalRequestArguments,
+    ) -> Result<RunInTerminalResponseBody, Error> {
+        self.send_reverse_request("runInTerminal", arguments)?
+            .body_as()
+    }
+
+    /// Ask the client to start a new debug session, and wait for its response.
+    pub fn start_debugging(&self, arguments: StartDebuggingRequestArguments) -> Result<(), Error> {
+        self.send_reverse_request("startDebugging", arguments)?
+            .check_success()
+    }
+
+    /// Send a request to the client, and wait for its response.
+    ///
+    /// The response is returned even if it report a failure, see `Response::check_success`,
+    /// unless the request was cancelled, which is reported as `Error::Cancelled`.
+    /// `run_in_terminal` and `start_debugging` should be preferred for the requests they cover.
+    pub fn send_reverse_request<T: Serialize>(
+        &self,
+        command: &str,
+        arguments: T,
+    ) -> Result<Response, Error> {
+        let request = Request::new(self.next_seq(), command, arguments)?;
+        let seq = request.seq();
+        let message = Message::try_from(request)?;
+
+        // the request must be registered before being sent,
+        // otherwise the response could be read before the registration.
+        let response = self.pending_requests.register(seq);
+        if let Err(err) = self.send(&message) {
+            self.pending_requests.forget(seq);
+            return Err(err);
+        }
+        self.start();
+
+        response.recv().map_err(|_| {
+            Error::Io(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "the session ended before the request was answered",
+            ))
+        })?
+    }
+}
+
+impl Iterator for Adapter {
+    type Item = Result<Message, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.start();
+        self.receiver.recv()
+    }
+}
+
+/// The writing half of a session, shared by the adapter and its listener.
+struct Output {
+    output: Mutex<Box<dyn Write + Send>>,
+    seq: SeqCounter,
+    groups: OutputGroups,
+}
+
+impl Output {
+    /// Create a output whose first message has the sequence number `seq`.
+    fn new<W: Write + Send + 'static>(output: W, seq: usize) -> Self {
+        Self {
+            output: Mutex::new(Box::new(output)),
+            seq: SeqCounter::new(seq),
+            groups: OutputGroups::default(),
+        }
+    }
+
+    fn next_seq(&self) -> usize {
+        self.seq.next()
+    }
+
+    fn send(&self, message: &Message) -> Result<(), Error> {
+        let mut output = self.output.lock().unwrap();
+        message.write_to(&mut *output)?;
+        output.flush()?;
+        Ok(())
+    }
+}
+
+/// A handle which send events to the client of a adapter, from any thread.
+///
+/// Events take their sequence number from the session, like every other message of the adapter.
+#[derive(Clone)]
+pub struct EventEmitter {
+    output: Arc<Output>,
+}
+
+impl EventEmitter {
+    /// Send `event` to the client.
+    pub fn emit(&self, event: impl Into<EventKind>) -> Result<(), Error> {
+        // the output is locked by `send`, so messages from several threads are never interleaved
+        let message = event.into().into_message(self.output.next_seq())?;
+        self.output.send(&message)
+    }
+
+    /// Open a group in the output log of the client, named `name`.
+    ///
+    /// The output sent through the returned guard is shown indented, as a member of the group.
+    /// The group is closed by `OutputGroupGuard::end`, or when the guard is dropped.
+    pub fn output_group(&self, name: &str, collapsed: bool) -> Result<OutputGroupGuard, Error> {
+        let group = if collapsed {
+            OutputGroup::StartCollapsed
+        } else {
+            OutputGroup::Start
+        };
+
+        let id = self.output.groups.open();
+        if let Err(error) = self.emit(OutputEvent::new(None, name).group(group)) {
+            // the client never saw the group, so there is nothing to close
+            self.output.groups.forget(id);
+            return Err(error);
+        }
+        Ok(OutputGroupGuard {
+            emitter: self.clone(),
+            id,
+            ended: false,
+        })
+    }
+}
+
+/// A group of the client's output log, opened by `EventEmitter::output_group`.
+///
+/// Groups must be closed in the reverse order of their opening, otherwise the indentation of the
+/// client's output log would no longer match the groups. Ending a group out of order is a error,
+/// which hand the guard back so the group can be ended later. Dropping a guard out of order close
+/// the groups nested in it first, and panic in debug builds.
+pub struct OutputGroupGuard {
+    emitter: EventEmitter,
+    id: usize,
+    ended: bool,
+}
+
+impl OutputGroupGuard {
+    /// Send `event` as a member of the group.
+    ///
+    /// The event must not set its own `group`, since that would break the nesting of the groups.
+    pub fn output(&self, event: OutputEvent) -> Result<(), Error> {
+        if event.group.is_some() {
+            return Err(Error::InvalidMessage);
+        }
+        self.emitter.emit(event)
+    }
+
+    /// Open a group nested in this one.
+    pub fn group(&self, name: &str, collapsed: bool) -> Result<OutputGroupGuard, Error> {
+        self.emitter.output_group(name, collapsed)
+    }
+
+    /// Close the group.
+    ///
+    /// If a group opened after this one is still open, nothing is closed on the client and the
+    /// guard is handed back with `Error::InvalidMessage`.
+    pub fn end(mut self) -> Result<(), (OutputGroupGuard, Error)> {
+        match self.emitter.output.groups.close(self.id) {
+            CloseGroup::Closed => {
+                self.ended = true;
+                self.emitter
+                    .emit(OutputEvent::new(None, "").group(OutputGroup::End))
+                    .map_err(|error| (self, error))
+            }
+            CloseGroup::Nested => Err((self, Error::InvalidMessage)),
+            // already closed by the drop of a outer group
+            CloseGroup::Missing => {
+                self.ended = true;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for OutputGroupGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OutputGroupGuard")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+impl Drop for OutputGroupGuard {
+    fn drop(&mut self) {
+        if self.ended {
+            return;
+        }
+
+        let closed = self.emitter.output.groups.close_nested(self.id);
+        for _ in 0..closed {
+            // there is no one left to report a i/o error to
+            let _ = self
+                .emitter
+                .emit(OutputEvent::new(None, "").group(OutputGroup::End));
+        }
+        debug_assert!(closed <= 1, "output group closed out of order");
+    }
+}
+
+/// The output groups open in a session, innermost last.
+#[derive(Default)]
+struct OutputGroups {
+    next_id: AtomicUsize,
+    open: Mutex<Vec<usize>>,
+}
+
+impl OutputGroups {
+    fn open(&self) -> usize {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.open.lock().unwrap().push(id);
+        id
+    }
+
+    /// Forget the group `id` if it is the innermost group.
+    fn close(&self, id: usize) -> CloseGroup {
+        let mut open = self.open.lock().unwrap();
+        match open.iter().rposition(|open| *open == id) {
+            Some(index) if index + 1 == open.len() => {
+                open.pop();
+                CloseGroup::Closed
+            }
+            Some(_) => CloseGroup::Nested,
+            None => CloseGroup::Missing,
+        }
+    }
+
+    /// Forget the group `id` and the groups nested in it, returning how many groups were forgotten.
+    fn close_nested(&self, id: usize) -> usize {
+        let mut open = self.open.lock().unwrap();
+        match open.iter().rposition(|open| *open == id) {
+            Some(index) => {
+                let closed = open.len() - index;
+                open.truncate(index);
+                closed
+            }
+            None => 0,
+        }
+    }
+
+    /// Forget the group `id`, leaving the groups nested in it open.
+    fn forget(&self, id: usize) {
+        let mut open = self.open.lock().unwrap();
+        open.retain(|open| *open != id);
+    }
+}
+
+/// The outcome of `OutputGroups::close`.
+enum CloseGroup {
+    Closed,
+    /// A group nested in it is still open.
+    Nested,
+    /// The group is no longer open.
+    Missing,
+}
+
+/// A handle reporting the progress of a long running operation, created by
+/// `Adapter::start_progress`.
+///
+/// The 'progressEnd' event is sent by `end`, or when the reporter is dropped, so no update can
+/// follow it.
+pub struct ProgressReporter {
+    emitter: EventEmitter,
+    /// `None` once ended, or if the client does not support progress reporting.
+    tracker: Option<ProgressTracker>,
+    percentage: f32,
+}
+
+impl ProgressReporter {
+    /// The id of the progress reporting, which a client can pass to a 'cancel' request.
+    ///
+    /// Return `None` if the reporter is disabled.
+    pub fn progress_id(&self) -> Option<&str> {
+        self.tracker.as_ref().map(ProgressTracker::progress_id)
+    }
+
+    /// Send a 'progressUpdate' event.
+    ///
+    /// `percentage` is clamped to `[0, 100]`, and never goes back below the percentage of a
+    /// previous update.
+    pub fn update(&mut self, message: Option<&str>, percentage: Option<f32>) -> Result<(), Error> {
+        let tracker = match &self.tracker {
+            Some(tracker) => tracker,
+            None => return Ok(()),
+        };
+
+        let mut event = match percentage.filter(|pct| !pct.is_nan()) {
+            Some(pct) => {
+                self.percentage = pct.clamp(self.percentage, 100.0);
+                tracker.update(self.percentage)?
+            }
+            None => ProgressUpdateEvent {
+                progress_id: tracker.progress_id().to_string(),
+                message: None,
+                percentage: None,
+            },
+        };
+        event.message = message.map(str::to_string);
+        self.emitter.emit(event)
+    }
+
+    /// Send the 'progressEnd' event, with a optional final message.
+    pub fn end(mut self, message: Option<&str>) -> Result<(), Error> {
+        self.finish(message)
+    }
+
+    fn finish(&mut self, message: Option<&str>) -> Result<(), Error> {
+        match self.tracker.take() {
+            Some(tracker) => self.emitter.emit(tracker.end(message)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for ProgressReporter {
+    fn drop(&mut self) {
+        // there is no one left to report a error to
+        let _ = self.finish(None);
+    }
+}
+
+/// The source of the current time, which the tests replace to control the request timeouts.
+trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// The requests read from the client which were not answered yet, with the time they were read.
+struct InFlightRequests {
+    requests: Mutex<HashMap<usize, Instant>>,
+    clock: Arc<dyn Clock>,
+    closed: AtomicBool,
+}
+
+impl Default for InFlightRequests {
+    fn default() -> Self {
+        Self::new(Arc::new(SystemClock))
+    }
+}
+
+impl InFlightRequests {
+    fn new(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            requests: Mutex::default(),
+            clock,
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    fn register(&self, seq: usize) {
+        let now = self.clock.now();
+        self.requests.lock().unwrap().insert(seq, now);
+    }
+
+    fn resolve(&self, request_seq: usize) {
+        self.requests.lock().unwrap().remove(&request_seq);
+    }
+
+    fn contains(&self, seq: usize) -> bool {
+        self.requests.lock().unwrap().contains_key(&seq)
+    }
+
+    fn len(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+
+    /// Remove the requests read more than `timeout` ago, returning their sequence numbers.
+    fn expire(&self, timeout: Duration) -> Vec<usize> {
+        let now = self.clock.now();
+        let mut requests = self.requests.lock().unwrap();
+
+        let mut expired: Vec<usize> = requests
+            .iter()
+            .filter(|(_, read_at)| now.saturating_duration_since(**read_at) >= timeout)
+            .map(|(seq, _)| *seq)
+            .collect();
+        expired.sort_unstable();
+
+        for seq in &expired {
+            requests.remove(seq);
+        }
+        expired
+    }
+
+    /// Mark the session as over.
+    fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+}
+
+/// Cancel the requests of the client which were not answered in time.
+struct Watchdog<S> {
+    sender: S,
+    cancellations: Arc<CancellationRegistry>,
+    in_flight: Arc<InFlightRequests>,
+}
+
+impl<S: ChannelSender<Result<Message, Error>>> Watchdog<S> {
+    fn watch(self, timeout: Duration) {
+        let period = (timeout / 10).clamp(Duration::from_millis(1), Duration::from_millis(100));
+
+        while !self.in_flight.is_closed() {
+            thread::sleep(period);
+
+            for seq in self.in_flight.expire(timeout) {
+                self.cancellations.cancel(seq);
+
+                let arguments = CancelArguments {
+                    request_id: Some(seq),
+                    progress_id: None,
+                };
+                let cancel = Request::new(0, "cancel", arguments).and_then(Message::try_from);
+
+                // stop once the adapter was dropped
+                if !self.sender.send(cancel) {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// A flag shared between the adapter and a request handler,
+/// which is raised when the client cancel the request.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release)
+    }
+}
+
+/// The cancellation token of every request which was either cancelled,
+/// or registered by a long-running handler.
+#[derive(Debug, Default)]
+struct CancellationRegistry {
+    tokens: Mutex<HashMap<usize, CancellationToken>>,
+}
+
+impl CancellationRegistry {
+    fn cancel(&self, seq: usize) {
+        self.token(seq).cancel()
+    }
+
+    /// Cancel the request `seq` if `in_flight` return true.
+    ///
+    /// `in_flight` is called with the registry locked, so the request can not be removed by
+    /// `remove` between the check and the cancellation.
+    fn cancel_if(&self, seq: usize, in_flight: impl FnOnce() -> bool) {
+        let mut tokens = self.tokens.lock().unwrap();
+        if in_flight() {
+            tokens.entry(seq).or_default().cancel();
+        }
+    }
+
+    /// Forget the token of the request `seq`, which was answered.
+    fn remove(&self, seq: usize) {
+        self.tokens.lock().unwrap().remove(&seq);
+    }
+
+    fn token(&self, seq: usize) -> CancellationToken {
+        self.tokens.lock().unwrap().entry(seq).or_default().clone()
+    }
+
+    fn is_cancelled(&self, seq: usize) -> bool {
+        match self.tokens.lock().unwrap().get(&seq) {
+            Some(token) => token.is_cancelled(),
+            None => false,
+        }
+    }
+}
+
+/// The requests sent by the adapter which are still waiting for a response.
+type PendingRequests = PendingResponses<mpsc::Sender<Result<Response, Error>>>;
+
+/// The sending half of a channel, through which the listener forward the messages it read.
+pub trait ChannelSender<T> {
+    /// Send `item`, returning false if the receiving half was dropped.
+    fn send(&self, item: T) -> bool;
+}
+
+/// The receiving half of a channel, through which the adapter receive the messages read by its
+/// listener.
+pub trait ChannelReceiver<T> {
+    /// Wait for the next item, returning `None` once every sending half was dropped.
+    fn recv(&self) -> Option<T>;
+}
+
+impl<T> ChannelSender<T> for mpsc::Sender<T> {
+    fn send(&self, item: T) -> bool {
+        mpsc::Sender::send(self, item).is_ok()
+    }
+}
+
+impl<T> ChannelReceiver<T> for mpsc::Receiver<T> {
+    fn recv(&self) -> Option<T> {
+        mpsc::Receiver::recv(self).ok()
+    }
+}
+
+#[cfg(feature = "crossbeam")]
+impl<T> ChannelSender<T> for crossbeam_channel::Sender<T> {
+    fn send(&self, item: T) -> bool {
+        crossbeam_channel::Sender::send(self, item).is_ok()
+    }
+}
+
+#[cfg(feature = "crossbeam")]
+impl<T> ChannelReceiver<T> for crossbeam_channel::Receiver<T> {
+    fn recv(&self) -> Option<T> {
+        crossbeam_channel::Receiver::recv(self).ok()
+    }
+}
+
+/// Read the messages of a session and forward them to the `Adapter`.
+struct Listener<R, S> {
+    input: R,
+    sender: S,
+    output: Arc<Output>,
+    cancellations: Arc<CancellationRegistry>,
+    pending_requests: Arc<PendingRequests>,
+    in_flight: Arc<InFlightRequests>,
+    error_handler: Arc<Mutex<Option<ErrorHandler>>>,
+    max_message_size: Arc<AtomicUsize>,
+    client: Arc<Mutex<Option<InitializeRequestArguments>>>,
+}
+
+impl<R: BufRead, S: ChannelSender<Result<Message, Error>>> Listener<R, S> {
+    /// Read the next message, returning `None` if it was consumed by a pending request.
+    fn next_msg(&mut self) -> Result<Option<Message>, Error> {
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!(
+            "dap_read",
+            seq = tracing::field::Empty,
+            message_type = tracing::field::Empty
+        )
+        .entered();
+
+        let header = Header::from_input(&mut self.input)?;
+
+        if let Err(err) = header.validate(self.max_message_size.load(Ordering::SeqCst)) {
+            // skip the content, so the next message can still be read. Without a error handler
+            // the session end, so the error is reported without waiting for the content.
+            if self.error_handler.lock().unwrap().is_some() {
+                let mut content = (&mut self.input).take(header.content_length as u64);
+                io::copy(&mut content, &mut io::sink())?;
+            }
+            return Err(err);
+        }
+
+        let message = Message::try_from_content(&mut self.input, &header)?;
+
+        #[cfg(feature = "tracing")]
+        {
+            span.record("seq", message.seq());
+            span.record("message_type", message.message_type());
+            tracing::debug!("message read");
+        }
+
+        match message.message_kind() {
+            // cancel request are handled here rather than by the adapter,
+            // since the adapter may be busy with the request being cancelled.
+            Some(MessageKind::Request(request)) => {
+                if let RequestKind::Initialize(initialize) = request.request_kind() {
+                    *self.client.lock().unwrap() = Some(initialize.arguments().clone());
+                }
+
+                if let RequestKind::Cancel(cancel) = request.request_kind() {
+                    if let Some(seq) = cancel.arguments().and_then(|args| args.request_id) {
+                        // a request which was already answered is not recorded, since nothing
+                        // would ever remove it
+                        self.cancellations
+                            .cancel_if(seq, || self.in_flight.contains(seq));
+                    }
+
+                    let response = Response::for_request(self.output.next_seq(), request, ())?;
+                    self.output.send(&Message::try_from(response)?)?;
+                } else {
+                    self.in_flight.register(request.seq());
+                }
+            }
+            // responses to the adapter's own requests are handed to their requester
+            Some(MessageKind::Response(response)) if self.pending_requests.resolve(response) => {
+                return Ok(None)
+            }
+            _ => (),
+        }
+
+        Ok(Some(message))
+    }
+
+    /// Pass a error to the error handler, returning true if the session can go on.
+    fn handle_error(&self, err: &Error) -> bool {
+        // after a i/o error, the input is either closed or in a unknown state
+        if let Error::Io(_) = err {
+            return false;
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::warn!(error = %err, "invalid message read");
+
+        match &*self.error_handler.lock().unwrap() {
+            Some(handler) => {
+                handler(err);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn listen(mut self) {
+        loop {
+            let message = match self.next_msg() {
+                Ok(Some(message)) => Ok(message),
+                Ok(None) => continue,
+                // the client closed the session, the adapter's iterator simply end
+                Err(Error::Eof) => break,
+                Err(err) if self.handle_error(&err) => continue,
+                Err(err) => Err(err),
+            };
+            let is_err = message.is_err();
+
+            // stop if the adapter was dropped or if the session is over
+            if !self.sender.send(message) || is_err {
+                break;
+            }
+        }
+
+        self.pending_requests.close();
+        self.in_flight.close();
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+    use std::io::{Cursor, Read};
+    use std::time::Duration;
+
+    use crate::test_util::SharedOutput;
+
+    fn frame(body: &str) -> String {
+        format!("Content-Length:{}\r\n\r\n{}", body.len(), body)
+    }
+
+    /// A input which block until the test feed it.
+    struct ChannelInput {
+        receiver: mpsc::Receiver<Vec<u8>>,
+        buffer: Cursor<Vec<u8>>,
+    }
+
+    impl Read for ChannelInput {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.buffer.position() as usize == self.buffer.get_ref().len() {
+                match self.receiver.recv() {
+                    Ok(bytes) => self.buffer = Cursor::new(bytes),
+                    // the test is over, act like the client closed the connection.
+                    Err(_) => return Ok(0),
+                }
+            }
+            self.buffer.read(buf)
+        }
+    }
+
+    fn channel_input() -> (io::BufReader<ChannelInput>, mpsc::Sender<Vec<u8>>) {
+        let (sender, receiver) = mpsc::channel();
+        let input = ChannelInput {
+            receiver,
+            buffer: Cursor::new(Vec::new()),
+        };
+        (io::BufReader::new(input), sender)
+    }
+
+    /// A output whose writes all fail.
+    struct FailingOutput;
+
+    impl Write for FailingOutput {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Read back every event written so far, as `(event, body)` pairs.
+    fn events(output: &SharedOutput) -> Vec<(String, serde_json::Value)> {
+        output
+            .events()
+            .into_iter()
+            .map(|event| {
+                let body = event.body().cloned().unwrap_or(serde_json::Value::Null);
+                (event.event().to_string(), body)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn event_emitter_from_threads() {
+        let output = SharedOutput::default();
+        let adapter = Adapter::with_reader_writer(Cursor::new(Vec::new()), output.clone());
+        let emitter = adapter.event_emitter();
+
+        let handles: Vec<_> = (0..4)
+            .map(|thread| {
+                let emitter = emitter.clone();
+                thread::spawn(move || {
+                    for line in 0..25 {
+                        let output = crate::OutputEvent::new(
+                            None,
+                            format!("thread {} line {}\n", thread, line),
+                        );
+                        emitter.emit(output).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let mut seqs = Vec::new();
+        for event in output.events() {
+            assert_eq!(event.event(), "output");
+            seqs.push(event.seq());
+        }
+        seqs.sort_unstable();
+        assert_eq!(seqs, (1..=100).collect::<Vec<_>>());
+    }
+
+    fn initialized_adapter(supports_progress_reporting: bool) -> (Adapter, SharedOutput) {
+        let input = frame(&format!(
+            r#"{{ "seq": 1, "type": "request", "command": "initialize", "arguments": {{ "adapterID": "mock", "supportsProgressReporting": {} }} }}"#,
+            supports_progress_reporting
+        ));
+        let output = SharedOutput::default();
+        let mut adapter = Adapter::with_reader_writer(Cursor::new(input), output.clone());
+        adapter.next().unwrap().unwrap();
+        (adapter, output)
+    }
+
+    #[test]
+    fn progress_reporter_lifecycle() {
+        let (adapter, output) = initialized_adapter(true);
+        assert_eq!(
+            adapter.initialize_arguments().unwrap().adapter_id,
+            "mock".to_string()
+        );
+
+        let mut reporter = adapter.start_progress("indexing", true).unwrap();
+        let progress_id = reporter.progress_id().unwrap().to_string();
+        reporter.update(Some("crate 1/3"), Some(30.0)).unwrap();
+        // out of range and backwards percentages are clamped
+        reporter.update(None, Some(-5.0)).unwrap();
+        reporter.update(None, Some(250.0)).unwrap();
+        reporter.update(Some("almost done"), None).unwrap();
+        reporter.end(Some("indexed")).unwrap();
+
+        let events = events(&output);
+        let names: Vec<_> = events.iter().map(|(event, _)| event.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "progressStart",
+                "progressUpdate",
+                "progressUpdate",
+                "progressUpdate",
+                "progressUpdate",
+                "progressEnd"
+            ]
+        );
+        assert!(events
+            .iter()
+            .all(|(_, body)| body["progressId"] == progress_id.as_str()));
+        assert_eq!(events[0].1["cancellable"], true);
+        assert_eq!(events[1].1["message"], "crate 1/3");
+        assert_eq!(events[1].1["percentage"], 30.0);
+        assert_eq!(events[2].1["percentage"], 30.0);
+        assert_eq!(events[3].1["percentage"], 100.0);
+        assert_eq!(events[4].1.get("percentage"), None);
+        assert_eq!(events[5].1["message"], "indexed");
+    }
+
+    #[test]
+    fn progress_reporter_end_on_drop() {
+        let (adapter, output) = initialized_adapter(true);
+
+        {
+            let mut reporter = adapter.start_progress("loading symbols", false).unwrap();
+            reporter.update(None, Some(50.0)).unwrap();
+        }
+
+        let events = events(&output);
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[2].0, "progressEnd");
+        assert_eq!(events[2].1["progressId"], events[0].1["progressId"]);
+    }
+
+    #[test]
+    fn progress_reporter_unsupported() {
+        let (adapter, output) = initialized_adapter(false);
+
+        let mut reporter = adapter.start_progress("indexing", false).unwrap();
+        assert_eq!(reporter.progress_id(), None);
+        reporter.update(Some("crate 1/3"), Some(30.0)).unwrap();
+        reporter.end(None).unwrap();
+
+        // without a initialize request, the client is not known to support it either
+        let idle = Adapter::with_reader_writer(Cursor::new(Vec::new()), output.clone());
+        drop(idle.start_progress("indexing", false).unwrap());
+
+        assert!(events(&output).is_empty());
+    }
+
+    /// A clock which only move when the test advance it.
+    struct ManualClock(Mutex<Instant>);
+
+    impl Clock for ManualClock {
+        fn now(&self) -> Instant {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    #[test]
+    fn request_timeout() {
+        let (input, sender) = channel_input();
+        let clock = Arc::new(ManualClock(Mutex::new(Instant::now())));
+        let config = SessionConfig::builder()
+            .request_timeout(Duration::from_secs(30))
+            .build();
+        let mut adapter =
+            Adapter::with_clock(config, input, io::sink(), mpsc::channel(), clock.clone());
+
+        for seq in 1..=2 {
+            let request = format!(
+                r#"{{ "seq": {}, "type": "request", "command": "custom" }}"#,
+                seq
+            );
+            sender.send(frame(&request).into_bytes()).unwrap();
+            assert_eq!(adapter.next().unwrap().unwrap().seq(), seq);
+        }
+        assert_eq!(adapter.pending_request_count(), 2);
+
+        // the second request is answered in time
+        let request = Request::new(2, "custom", ()).unwrap();
+        let response = Response::for_request(adapter.next_seq(), &request, ()).unwrap();
+        adapter.send(&Message::try_from(response).unwrap()).unwrap();
+        assert_eq!(adapter.pending_request_count(), 1);
+
+        *clock.0.lock().unwrap() += Duration::from_secs(31);
+
+        let cancel = Request::try_from(adapter.next().unwrap().unwrap()).unwrap();
+        assert_eq!(cancel.seq(), 0);
+        match cancel.request_kind() {
+            RequestKind::Cancel(cancel) => {
+                assert_eq!(cancel.arguments().unwrap().request_id, Some(1))
+            }
+            kind => panic!("unexpected request {:?}", kind),
+        }
+        assert!(adapter.is_cancelled(1));
+        assert!(!adapter.is_cancelled(2));
+        assert_eq!(adapter.pending_request_count(), 0);
+    }
+
+    #[test]
+    fn output_group_nesting() {
+        let output = SharedOutput::default();
+        let adapter = Adapter::with_reader_writer(Cursor::new(Vec::new()), output.clone());
+
+        {
+            let build = adapter.output_group("build", false).unwrap();
+            build
+                .output(crate::OutputEvent::stdout("compiling\n"))
+                .unwrap();
+
+            let warnings = build.group("warnings", true).unwrap();
+            warnings
+                .output(crate::OutputEvent::stderr("unused variable\n"))
+                .unwrap();
+            assert!(warnings
+                .output(crate::OutputEvent::console("").group(OutputGroup::End))
+                .is_err());
+            warnings.end().unwrap();
+            // `build` is closed when dropped
+        }
+
+        let events: Vec<_> = events(&output)
+            .into_iter()
+            .map(|(_, body)| (body["output"].clone(), body["group"].clone()))
+            .collect();
+        let null = serde_json::Value::Null;
+        assert_eq!(
+            events,
+            [
+                ("build".into(), "start".into()),
+                ("compiling\n".into(), null.clone()),
+                ("warnings".into(), "startCollapsed".into()),
+                ("unused variable\n".into(), null),
+                ("".into(), "end".into()),
+                ("".into(), "end".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn output_group_out_of_order() {
+        let output = SharedOutput::default();
+        let adapter = Adapter::with_reader_writer(Cursor::new(Vec::new()), output.clone());
+
+        let outer = adapter.output_group("outer", false).unwrap();
+        let inner = adapter.output_group("inner", false).unwrap();
+        let (outer, error) = match outer.end() {
+            Err(error) => error,
+            Ok(()) => panic!(),
+        };
+        assert!(matches!(error, Error::InvalidMessage));
+        // the outer group can still be ended, once the inner one is
+        inner.end().unwrap();
+        outer.end().unwrap();
+
+        let groups: Vec<_> = events(&output)
+            .into_iter()
+            .map(|(_, body)| body["group"].clone())
+            .collect();
+        assert_eq!(groups, ["start", "start", "end", "end"]);
+    }
+
+    #[test]
+    fn output_group_dropped_out_of_order_close_nested() {
+        let output = SharedOutput::default();
+        let adapter = Adapter::with_reader_writer(Cursor::new(Vec::new()), output.clone());
+
+        let outer = adapter.output_group("outer", false).unwrap();
+        let inner = adapter.output_group("inner", false).unwrap();
+        // only debug builds panic
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| drop(outer)));
+        // the inner group was closed along with the outer one
+        inner.end().unwrap();
+
+        let groups: Vec<_> = events(&output)
+            .into_iter()
+            .map(|(_, body)| body["group"].clone())
+            .collect();
+        assert_eq!(groups, ["start", "start", "end", "end"]);
+    }
+
+    #[test]
+    fn output_group_start_failed() {
+        let adapter = Adapter::with_reader_writer(Cursor::new(Vec::new()), FailingOutput);
+
+        assert!(adapter.output_group("build", false).is_err());
+        assert!(adapter
+            .event_emitter()
+            .output
+            .groups
+            .open
+            .lock()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "output group closed out of order")]
+    fn output_group_dropped_out_of_order() {
+        let adapter = Adapter::with_reader_writer(Cursor::new(Vec::new()), io::sink());
+
+        let outer = adapter.output_group("outer", false).unwrap();
+        let _inner = adapter.output_group("inner", false).unwrap();
+        drop(outer);
+    }
+
+    #[test]
+    fn update_capabilities() {
+        let input = frame(
+            r#"{ "seq": 1, "type": "request", "command": "initialize", "arguments": { "adapterID": "mock" } }"#,
+        );
+        let output = SharedOutput::default();
+        let mut adapter = Adapter::with_reader_writer(Cursor::new(input), output.clone());
+
+        let initialize = Request::try_from(adapter.next().unwrap().unwrap()).unwrap();
+        let capabilities = Capabilities::builder()
+            .supports_configuration_done_request(true)
+            .supports_step_back(false)
+            .build();
+        let response =
+            Response::for_request(adapter.next_seq(), &initialize, capabilities).unwrap();
+        adapter.send(&Message::try_from(response).unwrap()).unwrap();
+        output.clear();
+
+        // nothing changed, so nothing is sent
+        let unchanged = Capabilities::builder()
+            .supports_configuration_done_request(true)
+            .supports_step_back(false)
+            .build();
+        adapter.update_capabilities(unchanged.clone()).unwrap();
+        assert!(events(&output).is_empty());
+
+        adapter
+            .update_capabilities(Capabilities {
+                supports_step_back: Some(true),
+                ..unchanged
+            })
+            .unwrap();
+        assert_eq!(
+            events(&output),
+            [(
+                "capabilities".to_string(),
+                serde_json::json!({ "capabilities": { "supportsStepBack": true } })
+            )]
+        );
+    }
+
+    #[test]
+    fn adapter_records_cancel_request() {
+        let input = [
+            frame(r#"{ "seq": 1, "type": "request", "command": "custom" }"#),
+            frame(r#"{ "seq": 2, "type": "request", "command": "cancel", "arguments": { "requestId": 1 } }"#),
+        ]
+        .concat();
+
+        let output = SharedOutput::default();
+        let mut adapter = Adapter::with_reader_writer(Cursor::new(input), output.clone());
+
+        assert_eq!(adapter.next().unwrap().unwrap().seq(), 1);
+        assert_eq!(adapter.next().unwrap().unwrap().seq(), 2);
+
+        assert!(adapter.is_cancelled(1));
+        assert!(!adapter.is_cancelled(2));
+        assert!(adapter.cancellation_token(1).is_cancelled());
+
+        // the cancel request was answered by the adapter
+        let response = Response::try_from(output.wait_for_message()).unwrap();
+        assert_eq!(response.request_seq(), 2);
+        assert_eq!(response.command(), "cancel");
+        assert!(response.success());
+    }
+
+    #[test]
+    fn cancellation_forgotten_once_answered() {
+        let (input, client) = channel_input();
+        let mut adapter = Adapter::with_reader_writer(input, io::sink());
+
+        client
+            .send(frame(r#"{ "seq": 1, "type": "request", "command": "custom" }"#).into_bytes())
+            .unwrap();
+        let request = Request::try_from(adapter.next().unwrap().unwrap()).unwrap();
+        let token = adapter.cancellation_token(request.seq());
+        let response = Response::for_request(adapter.next_seq(), &request, ()).unwrap();
+        adapter.send(&Message::try_from(response).unwrap()).unwrap();
+        assert!(adapter.cancellations.tokens.lock().unwrap().is_empty());
+
+        // the request was answered, so cancelling it records nothing
+        client
+            .send(frame(r#"{ "seq": 2, "type": "request", "command": "cancel", "arguments": { "requestId": 1 } }"#).into_bytes())
+            .unwrap();
+        assert_eq!(adapter.next().unwrap().unwrap().seq(), 2);
+        assert!(!token.is_cancelled());
+        assert!(adapter.cancellations.tokens.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn cancel_slow_request() {
+        let (input, client) = channel_input();
+        let output = SharedOutput::default();
+        let mut adapter = Adapter::with_reader_writer(input, output.clone());
+
+        client
+            .send(frame(r#"{ "seq": 1, "type": "request", "command": "slow" }"#).into_bytes())
+            .unwrap();
+        let request = adapter.next().unwrap().unwrap();
+        let token = adapter.cancellation_token(request.seq());
+
+        let handler = thread::spawn(move || {
+            let mut iterations = 0;
+            while !token.is_cancelled() {
+                assert!(iterations < 5000, "the request was never cancelled");
+                iterations += 1;
+                thread::sleep(Duration::from_millis(1));
+            }
+        });
+
+        client
+            .send(
+                frame(r#"{ "seq": 2, "type": "request", "command": "cancel", "arguments": { "requestId": 1 } }"#)
+                    .into_bytes(),
+            )
+            .unwrap();
+
+        handler.join().unwrap();
+        assert!(adapter.is_cancelled(1));
+    }
+
+    #[test]
+    fn adapter_stop_after_error() {
+        let mut adapter = Adapter::with_reader_writer(Cursor::new("Content-Length:"), io::sink());
+
+        assert!(adapter.next().unwrap().is_err());
+        assert!(adapter.next().is_none());
+    }
+
+    #[test]
+    fn adapter_end_of_input() {
+        // the input is closed between two messages: the session simply end
+        let message = frame(r#"{ "seq": 1, "type": "request", "command": "custom" }"#);
+        for input in [String::new(), message].iter() {
+            let adapter = Adapter::with_reader_writer(Cursor::new(input.clone()), io::sink());
+            assert!(adapter
+                .map(Result::unwrap)
+                .all(|message| message.seq() == 1));
+        }
+
+        // the input is closed in the middle of a message
+        for input in [
+            "Content-Length:10\r\n",
+            "Content-Length:10\r\n\r\n",
+            "Content-Len",
+        ]
+        .iter()
+        {
+            let mut adapter = Adapter::with_reader_writer(Cursor::new(*input), io::sink());
+            match adapter.next() {
+                Some(Err(Error::Io(err))) => assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof),
+                _ => panic!("{:?} should end with a error", input),
+            }
+            assert!(adapter.next().is_none());
+        }
+    }
+
+    #[test]
+    fn run_in_terminal_valid() {
+        let (input, client) = channel_input();
+        let output = SharedOutput::default();
+        let adapter = Adapter::with_reader_writer(input, output.clone());
+
+        let handle = thread::spawn(move || {
+            let arguments = RunInTerminalRequestArguments::new("/tmp", vec!["./a.out".to_string()]);
+            adapter.run_in_terminal(arguments)
+        });
+
+        let request = Request::try_from(output.wait_for_message()).unwrap();
+        assert_eq!(request.command(), "runInTerminal");
+        assert_eq!(
+            request.arguments(),
+            Some(serde_json::json!({ "cwd": "/tmp", "args": ["./a.out"] }))
+        );
+
+        let response = format!(
+            r#"{{ "seq": 1, "type": "response", "request_seq": {}, "success": true, "command": "runInTerminal", "body": {{ "processId": 1234 }} }}"#,
+            request.seq()
+        );
+        client.send(frame(&response).into_bytes()).unwrap();
+
+        let body = handle.join().unwrap().unwrap();
+        assert_eq!(body.process_id, Some(1234));
+        assert_eq!(body.shell_process_id, None);
+    }
+
+    #[test]
+    fn run_in_terminal_failed() {
+        let (input, client) = channel_input();
+        let output = SharedOutput::default();
+        let adapter = Adapter::with_reader_writer(input, output.clone());
+
+        let handle = thread::spawn(move || {
+            let arguments = RunInTerminalRequestArguments::new("/tmp", vec!["./a.out".to_string()]);
+            adapter.run_in_terminal(arguments)
+        });
+
+        let request = output.wait_for_message();
+        let response = format!(
+            r#"{{ "seq": 1, "type": "response", "request_seq": {}, "success": false, "command": "runInTerminal", "message": "no terminal" }}"#,
+            request.seq()
+        );
+        client.send(frame(&response).into_bytes()).unwrap();
+
+        match handle.join().unwrap() {
+            Err(Error::RequestFailed { message }) => assert_eq!(message, "no terminal"),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn run_in_terminal_cancelled() {
+        let (input, client) = channel_input();
+        let output = SharedOutput::default();
+        let adapter = Adapter::with_reader_writer(input, output.clone());
+
+        let handle = thread::spawn(move || {
+            let arguments = RunInTerminalRequestArguments::new("/tmp", vec!["./a.out".to_string()]);
+            adapter.run_in_terminal(arguments)
+        });
+
+        let request = output.wait_for_message();
+        let response = format!(
+            r#"{{ "seq": 1, "type": "response", "request_seq": {}, "success": false, "command": "runInTerminal", "message": "cancelled" }}"#,
+            request.seq()
+        );
+        client.send(frame(&response).into_bytes()).unwrap();
+
+        match handle.join().unwrap() {
+            Err(Error::Cancelled { request_seq }) => assert_eq!(request_seq, request.seq()),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn run_in_terminal_session_ended() {
+        let adapter = Adapter::with_reader_writer(Cursor::new(String::new()), io::sink());
+
+        let arguments = RunInTerminalRequestArguments::new("/tmp", vec!["./a.out".to_string()]);
+        assert!(adapter.run_in_terminal(arguments).is_err());
+    }
+
+    #[test]
+    fn start_debugging_valid() {
+        let (input, client) = channel_input();
+        let output = SharedOutput::default();
+        let adapter = Adapter::with_reader_writer(input, output.clone());
+
+        let handle = thread::spawn(move || {
+            let mut configuration = serde_json::Map::new();
+            configuration.insert("pid".to_string(), 4242.into());
+
+            adapter.start_debugging(StartDebuggingRequestArguments {
+                configuration,
+                request: crate::StartDebuggingRequestKind::Attach,
+            })
+        });
+
+        let request = Request::try_from(output.wait_for_message()).unwrap();
+        assert_eq!(request.command(), "startDebugging");
+        assert_eq!(
+            request.arguments(),
+            Some(serde_json::json!({ "configuration": { "pid": 4242 }, "request": "attach" }))
+        );
+
+        let response = format!(
+            r#"{{ "seq": 1, "type": "response", "request_seq": {}, "success": true, "command": "startDebugging" }}"#,
+            request.seq()
+        );
+        client.send(frame(&response).into_bytes()).unwrap();
+
+        handle.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn request_response_cycle() {
+        let input = frame(r#"{ "seq": 1, "type": "request", "command": "custom" }"#);
+        let output = SharedOutput::default();
+        let mut adapter = Adapter::with_reader_writer(Cursor::new(input), output.clone());
+
+        let request = Request::try_from(adapter.next().unwrap().unwrap()).unwrap();
+        let response = Response::for_request(adapter.next_seq(), &request, ()).unwrap();
+        adapter.send(&Message::try_from(response).unwrap()).unwrap();
+
+        let response = Response::try_from(output.wait_for_message()).unwrap();
+        assert_eq!(response.seq(), 1);
+        assert_eq!(response.request_seq(), 1);
+        assert_eq!(response.command(), "custom");
+
+        // the input is exhausted, which end the session
+        assert!(adapter.next().is_none());
+    }
+
+    #[test]
+    fn error_handler_skip_invalid_message() {
+        let input = [
+            frame(r#"{ "seq": 1, "type": "request" }"#),
+            frame("not json"),
+            frame(r#"{ "seq": 3, "type": "request", "command": "custom" }"#),
+        ]
+        .concat();
+
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let handled = errors.clone();
+        let mut adapter = Adapter::with_reader_writer(Cursor::new(input), io::sink())
+            .on_error(move |err| handled.lock().unwrap().push(err.to_string()));
+
+        assert_eq!(adapter.next().unwrap().unwrap().seq(), 3);
+        assert_eq!(errors.lock().unwrap().len(), 2);
+
+        // the end of the input end the session, without reaching the handler
+        assert!(adapter.next().is_none());
+        assert_eq!(errors.lock().unwrap().len(), 2);
+    }
+
+    #[cfg(feature = "crossbeam")]
+    #[test]
+    fn adapter_crossbeam_channel() {
+        let input = frame(r#"{ "seq": 1, "type": "request", "command": "custom" }"#);
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let mut adapter = Adapter::with_channel(
+            SessionConfig::default(),
+            Cursor::new(input),
+            io::sink(),
+            sender,
+            receiver,
+        );
+
+        assert_eq!(adapter.next().unwrap().unwrap().seq(), 1);
+        // the end of the input close the channel
+        assert!(adapter.next().is_none());
+    }
+
+    #[test]
+    fn adapter_from_config() {
+        let input = [
+            frame("not json"),
+            format!("Content-Length:{}\r\n\r\n{}", 200, " ".repeat(200)),
+            frame(r#"{ "seq": 3, "type": "request", "command": "custom" }"#),
+        ]
+        .concat();
+        let config = SessionConfig::builder()
+            .max_message_bytes(100)
+            .error_strategy(ErrorStrategy::Recover)
+            .seq_start(100)
+            .build();
+        let mut adapter = Adapter::from_config(config, Cursor::new(input), io::sink());
+
+        // the invalid and the oversized messages are skipped
+        let request = Request::try_from(adapter.next().unwrap().unwrap()).unwrap();
+        assert_eq!(request.seq(), 3);
+        assert_eq!(adapter.next_seq(), 100);
+        assert!(adapter.next().is_none());
+
+        // the default configuration stop on the first invalid message
+        assert_eq!(SessionConfig::builder().build(), SessionConfig::default());
+        let input = Cursor::new(frame("not json"));
+        let mut adapter = Adapter::from_config(SessionConfig::default(), input, io::sink());
+        assert!(adapter.next().unwrap().is_err());
+        assert!(adapter.next().is_none());
+    }
+
+    #[test]
+    fn message_too_large() {
+        let input = [
+            format!("Content-Length:{}\r\n\r\n{}", 1000, " ".repeat(1000)),
+            frame(r#"{ "seq": 2, "type": "request", "command": "custom" }"#),
+        ]
+        .concat();
+
+        let mut adapter = Adapter::with_reader_writer(Cursor::new(input), io::sink())
+            .with_max_message_size(100)
+            .on_error(|_| ());
+
+        // the message is skipped, without stopping the adapter
+        assert_eq!(adapter.next().unwrap().unwrap().seq(), 2);
+
+        let input = "Content-Length:18446744073709551615\r\n\r\n";
+        let mut adapter = Adapter::with_reader_writer(Cursor::new(input), io::sink());
+
+        match adapter.next() {
+            Some(Err(Error::MessageTooLarge {
+                actual: usize::MAX,
+                limit: DEFAULT_MAX_MESSAGE_SIZE,
+            })) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn message_too_large_fail_fast() {
+        let (input, client) = channel_input();
+        let mut adapter = Adapter::with_reader_writer(input, io::sink());
+
+        // the content never arrive, and the input stay open
+        client
+            .send(b"Content-Length: 9999999999\r\n\r\n".to_vec())
+            .unwrap();
+
+        match adapter.next() {
+            Some(Err(Error::MessageTooLarge {
+                actual: 9999999999, ..
+            })) => (),
+            _ => panic!(),
+        }
+        assert!(adapter.next().is_none());
+        drop(client);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tracing_test::traced_test]
+    #[test]
+    fn next_msg_span() {
+        let input = [
+            frame(r#"{ "seq": 7, "type": "request", "command": "custom" }"#),
+            frame("not json"),
+        ]
+        .concat();
+
+        let mut listener = Listener {
+            input: Cursor::new(input),
+            sender: mpsc::channel().0,
+            output: Arc::new(Output::new(io::sink(), 1)),
+            cancellations: Arc::default(),
+            pending_requests: Arc::default(),
+            error_handler: Arc::default(),
+            in_flight: Arc::default(),
+            max_message_size: Arc::new(AtomicUsize::new(DEFAULT_MAX_MESSAGE_SIZE)),
+            client: Arc::default(),
+        };
+
+        listener.next_msg().unwrap();
+        assert!(logs_contain("dap_read{seq=7 message_type=\"request\"}"));
+
+        let err = listener.next_msg().unwrap_err();
+        listener.handle_error(&err);
+        assert!(logs_contain("invalid message read"));
+    }
+
+    #[test]
+    fn send_reverse_request_valid() {
+        let (input, client) = channel_input();
+        let output = SharedOutput::default();
+        let adapter = Adapter::with_reader_writer(input, output.clone());
+
+        let handle = thread::spawn(move || {
+            adapter.send_reverse_request("customReverse", serde_json::json!({ "value": 1 }))
+        });
+
+        let request = Request::try_from(output.wait_for_message()).unwrap();
+        assert_eq!(request.command(), "customReverse");
+
+        let response = format!(
+            r#"{{ "seq": 1, "type": "response", "request_seq": {}, "success": false, "command": "customReverse", "message": "unsupported" }}"#,
+            request.seq()
+        );
+        client.send(frame(&response).into_bytes()).unwrap();
+
+        let response = handle.join().unwrap().unwrap();
+        assert_eq!(response.request_seq(), request.seq());
+        assert!(!response.success());
+        assert_eq!(response.message(), Some("unsupported"));
+    }
+
+    #[test]
+    fn send_reverse_request_write_failed() {
+        let adapter = Adapter::with_reader_writer(Cursor::new(Vec::new()), FailingOutput);
+
+        match adapter.send_reverse_request("customReverse", serde_json::json!({})) {
+            Err(Error::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::BrokenPipe),
+            _ => panic!(),
+        }
+        assert_eq!(adapter.pending_requests.len(), 0);
+    }
+}
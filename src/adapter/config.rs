@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use super::DEFAULT_MAX_MESSAGE_SIZE;
+
+/// The settings of a adapter, gathered in one place, see `Adapter::from_config`.
+///
+/// The default configuration match a adapter created by `Adapter::with_reader_writer`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionConfig {
+    pub(super) max_message_bytes: usize,
+    pub(super) request_timeout: Option<Duration>,
+    pub(super) error_strategy: ErrorStrategy,
+    pub(super) seq_start: usize,
+}
+
+impl SessionConfig {
+    pub fn builder() -> SessionConfigBuilder {
+        SessionConfigBuilder::default()
+    }
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            max_message_bytes: DEFAULT_MAX_MESSAGE_SIZE,
+            request_timeout: None,
+            error_strategy: ErrorStrategy::default(),
+            seq_start: 1,
+        }
+    }
+}
+
+/// What a adapter does after reading a invalid message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorStrategy {
+    /// Yield the error, then stop.
+    #[default]
+    Fail,
+    /// Skip the message and go on with the session. I/O errors still stop the adapter.
+    Recover,
+}
+
+/// Build a `SessionConfig` setting by setting, starting from the default configuration.
+#[derive(Debug, Clone, Default)]
+pub struct SessionConfigBuilder {
+    config: SessionConfig,
+}
+
+impl SessionConfigBuilder {
+    /// Set the largest message accepted from the client, in bytes.
+    ///
+    /// See `Adapter::with_max_message_size`.
+    pub fn max_message_bytes(mut self, bytes: usize) -> Self {
+        self.config.max_message_bytes = bytes;
+        self
+    }
+
+    /// Cancel the requests of the client which are still unanswered after `duration`.
+    ///
+    /// See `Adapter::with_request_timeout`.
+    pub fn request_timeout(mut self, duration: Duration) -> Self {
+        self.config.request_timeout = Some(duration);
+        self
+    }
+
+    pub fn error_strategy(mut self, strategy: ErrorStrategy) -> Self {
+        self.config.error_strategy = strategy;
+        self
+    }
+
+    /// Set the sequence number of the first message sent by the adapter, 1 by default.
+    pub fn seq_start(mut self, seq: usize) -> Self {
+        self.config.seq_start = seq;
+        self
+    }
+
+    pub fn build(self) -> SessionConfig {
+        self.config
+    }
+}
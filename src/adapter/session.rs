@@ -0,0 +1,156 @@
+//! The multi session mode, where the adapter accept its clients on a tcp socket.
+
+use std::io;
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+
+use super::Adapter;
+use crate::{Error, Message};
+
+/// The sessions accepted by a adapter started in multi session mode.
+///
+/// The iterator yield a `Session` for each client which connect to the adapter.
+pub struct Sessions {
+    listener: TcpListener,
+}
+
+impl Sessions {
+    pub(super) fn bind(addr: SocketAddr) -> Result<Self, Error> {
+        let listener = TcpListener::bind(addr)?;
+        Ok(Self { listener })
+    }
+
+    /// Return the address the adapter is listening on.
+    ///
+    /// This is useful to find the port chosen by the system when binding to port 0.
+    pub fn local_addr(&self) -> Result<SocketAddr, Error> {
+        Ok(self.listener.local_addr()?)
+    }
+}
+
+impl Iterator for Sessions {
+    type Item = Result<Session, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(
+            self.listener
+                .accept()
+                .map_err(Error::from)
+                .and_then(|(stream, _)| Session::new(stream)),
+        )
+    }
+}
+
+/// A session with a single client connected over tcp.
+///
+/// The session yield the messages sent by its client through its `Iterator` implementation,
+/// like an `Adapter` does. The connection is closed when the session is dropped.
+pub struct Session {
+    adapter: Adapter,
+    stream: TcpStream,
+}
+
+impl Session {
+    fn new(stream: TcpStream) -> Result<Self, Error> {
+        let input = io::BufReader::new(stream.try_clone()?);
+        let output = stream.try_clone()?;
+
+        Ok(Self {
+            adapter: Adapter::with_reader_writer(input, output),
+            stream,
+        })
+    }
+
+    /// Return the address of the client.
+    pub fn peer_addr(&self) -> Result<SocketAddr, Error> {
+        Ok(self.stream.peer_addr()?)
+    }
+
+    /// Return the sequence number to use for the next message sent in this session.
+    pub fn next_seq(&self) -> usize {
+        self.adapter.next_seq()
+    }
+
+    /// Send a message to the client of this session.
+    pub fn send(&self, message: &Message) -> Result<(), Error> {
+        self.adapter.send(message)
+    }
+
+    /// Register a callback for the messages of this session which could not be read.
+    ///
+    /// See `Adapter::on_error`.
+    pub fn on_error(self, handler: impl Fn(&Error) + Send + 'static) -> Self {
+        self.adapter.set_error_handler(handler);
+        self
+    }
+
+    /// Return the adapter serving this session, to send requests to its client
+    /// or to check for cancellations.
+    pub fn adapter(&self) -> &Adapter {
+        &self.adapter
+    }
+}
+
+impl Iterator for Session {
+    type Item = Result<Message, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.adapter.next()
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        // unblock the listener thread, which is still reading from the connection.
+        // the client may already have closed it, in which case there is nothing to do.
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+    use crate::{Response, ResponseBuilder};
+    use std::convert::TryFrom;
+    use std::io::Write;
+    use std::thread;
+
+    #[test]
+    fn two_sequential_sessions() {
+        let mut sessions = Adapter::multi_session_mode("127.0.0.1:0".parse().unwrap()).unwrap();
+        let addr = sessions.local_addr().unwrap();
+
+        let client = thread::spawn(move || {
+            let mut responses = Vec::new();
+            for seq in 1..=2 {
+                let mut stream = TcpStream::connect(addr).unwrap();
+                let body = format!(
+                    r#"{{ "seq": {}, "type": "request", "command": "custom" }}"#,
+                    seq
+                );
+                write!(stream, "Content-Length:{}\r\n\r\n{}", body.len(), body).unwrap();
+
+                let mut input = io::BufReader::new(stream);
+                let response = Response::try_from(Message::try_from_input(&mut input).unwrap());
+                responses.push(response.unwrap().request_seq());
+            }
+            responses
+        });
+
+        for expected_seq in 1..=2 {
+            let mut session = sessions.next().unwrap().unwrap();
+            let request = session.next().unwrap().unwrap();
+            assert_eq!(request.seq(), expected_seq);
+
+            let response = ResponseBuilder::default()
+                .seq(session.next_seq())
+                .request_seq(request.seq())
+                .command("custom")
+                .build()
+                .unwrap();
+            session.send(&Message::try_from(response).unwrap()).unwrap();
+        }
+
+        assert_eq!(client.join().unwrap(), vec![1, 2]);
+    }
+}
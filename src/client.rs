@@ -0,0 +1,192 @@
+//! The client side of a session, for adapters sending reverse requests, or for tests.
+
+use std::convert::TryFrom;
+use std::io;
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::header::{find_header_end, MAX_HEADER_BYTES};
+use crate::pending::PendingResponses;
+use crate::{
+    Error, Header, Message, MessageKind, Request, RequestArguments, Response, SeqCounter,
+    DEFAULT_MAX_MESSAGE_SIZE,
+};
+
+/// A dap client, sending requests to a adapter and awaiting their responses.
+///
+/// The messages of the adapter are read by a task spawned on the current tokio runtime.
+/// Responses are handed to the request they answer, while events and reverse requests are
+/// returned by `next_message`.
+pub struct DapClient {
+    output: tokio::sync::Mutex<Box<dyn AsyncWrite + Send + Unpin>>,
+    seq: SeqCounter,
+    pending: Arc<PendingRequests>,
+    messages: tokio::sync::Mutex<mpsc::UnboundedReceiver<Message>>,
+}
+
+impl DapClient {
+    /// Start a client reading the messages of the adapter from `input`, and writing its own
+    /// messages to `output`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime.
+    pub fn new<R, W>(input: R, output: W) -> Self
+    where
+        R: AsyncBufRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        Self::with_max_message_size(input, output, DEFAULT_MAX_MESSAGE_SIZE)
+    }
+
+    /// Like `new`, accepting messages of at most `bytes` bytes from the adapter rather than
+    /// `DEFAULT_MAX_MESSAGE_SIZE`.
+    ///
+    /// A larger message end the session, like any invalid message. The limit is given here rather
+    /// than set afterward, since the messages are read as soon as the client is created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime.
+    pub fn with_max_message_size<R, W>(input: R, output: W, bytes: usize) -> Self
+    where
+        R: AsyncBufRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        let pending = Arc::new(PendingRequests::default());
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(listen(input, pending.clone(), sender, bytes));
+
+        Self {
+            output: tokio::sync::Mutex::new(Box::new(output)),
+            seq: SeqCounter::default(),
+            pending,
+            messages: tokio::sync::Mutex::new(receiver),
+        }
+    }
+
+    /// Send a request made of `arguments`, and wait for the body of its response.
+    ///
+    /// A failed response is reported as `Error::RequestFailed`.
+    pub async fn send_request<A, T>(&self, arguments: A) -> Result<T, Error>
+    where
+        A: RequestArguments,
+        T: DeserializeOwned,
+    {
+        self.send_custom_request(A::COMMAND, arguments)
+            .await?
+            .body_as()
+    }
+
+    /// Send a request for `command`, and wait for its response.
+    ///
+    /// The response is returned even if it report a failure, see `Response::check_success`,
+    /// unless the request was cancelled, which is reported as `Error::Cancelled`.
+    /// `send_request` should be preferred for the requests it cover.
+    pub async fn send_custom_request<A: Serialize>(
+        &self,
+        command: &str,
+        arguments: A,
+    ) -> Result<Response, Error> {
+        let request = Request::new(self.seq.next(), command, arguments)?;
+        let seq = request.seq();
+
+        // the request must be registered before being sent,
+        // otherwise the response could be read before the registration.
+        let response = self.pending.register(seq);
+        if let Err(err) = self.send(&Message::try_from(request)?).await {
+            self.pending.forget(seq);
+            return Err(err);
+        }
+
+        response.await.map_err(|_| {
+            Error::Io(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "the session ended before the request was answered",
+            ))
+        })?
+    }
+
+    /// Send `message` to the adapter, such as the response to a reverse request.
+    pub async fn send(&self, message: &Message) -> Result<(), Error> {
+        let bytes = message.to_bytes()?;
+
+        // the output is locked for the whole message, so messages are never interleaved
+        let mut output = self.output.lock().await;
+        output.write_all(&bytes).await?;
+        output.flush().await?;
+        Ok(())
+    }
+
+    /// Return the next event or reverse request sent by the adapter,
+    /// or `None` once the session is over.
+    pub async fn next_message(&self) -> Option<Message> {
+        self.messages.lock().await.recv().await
+    }
+
+    /// Return the sequence number of the next message sent by the client.
+    pub fn next_seq(&self) -> usize {
+        self.seq.next()
+    }
+}
+
+/// The requests sent by the client which are still waiting for a response.
+type PendingRequests = PendingResponses<oneshot::Sender<Result<Response, Error>>>;
+
+/// Read the messages of the adapter until the end of the session.
+async fn listen<R: AsyncBufRead + Unpin>(
+    mut input: R,
+    pending: Arc<PendingRequests>,
+    messages: mpsc::UnboundedSender<Message>,
+    max_message_size: usize,
+) {
+    // a invalid message leave the input in a unknown state, so it end the session like the end
+    // of the input does
+    while let Ok(message) = read_message(&mut input, max_message_size).await {
+        match message.message_kind() {
+            Some(MessageKind::Response(response)) if pending.resolve(response) => (),
+            // nobody may be waiting for the other messages, which is fine
+            _ => {
+                let _ = messages.send(message);
+            }
+        }
+    }
+
+    pending.close();
+}
+
+/// Read a message: a header, followed by the json content it announce, which must be at most
+/// `max_message_size` bytes long.
+async fn read_message<R: AsyncBufRead + Unpin>(
+    input: &mut R,
+    max_message_size: usize,
+) -> Result<Message, Error> {
+    let mut header = Vec::new();
+    let mut line_start = 0;
+    // a empty line signify the end of the header
+    while let Err(next_line) = find_header_end(&header, line_start) {
+        line_start = next_line;
+        let read = input.read_until(b'\n', &mut header).await?;
+        if read == 0 && header.is_empty() {
+            return Err(Error::Eof);
+        } else if read == 0 {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "the input ended in a message header",
+            )));
+        } else if header.len() > MAX_HEADER_BYTES {
+            return Err(Error::Invalid);
+        }
+    }
+
+    let header = Header::from_input(&mut header.as_slice())?;
+    header.validate(max_message_size)?;
+
+    let mut content = vec![0; header.content_length];
+    input.read_exact(&mut content).await?;
+    Message::from_content(content)
+}
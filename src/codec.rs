@@ -0,0 +1,189 @@
+//! A codec framing dap messages, for use with `tokio_util::codec`.
+
+use bytes::{Buf, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::header::{find_header_end, MAX_HEADER_BYTES};
+use crate::{Error, Header, Message, DEFAULT_MAX_MESSAGE_SIZE};
+
+/// Decode and encode dap messages, for example through a `tokio_util::codec::Framed`.
+#[derive(Debug, Clone)]
+pub struct DapCodec {
+    max_message_size: usize,
+    /// The start of the line the search for the end of the header resume from, so the header is
+    /// scanned only once.
+    search_from: usize,
+}
+
+impl DapCodec {
+    pub fn new() -> Self {
+        Self {
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            search_from: 0,
+        }
+    }
+
+    /// Set the largest message accepted by the decoder, in bytes.
+    pub fn with_max_message_size(mut self, bytes: usize) -> Self {
+        self.max_message_size = bytes;
+        self
+    }
+}
+
+impl Default for DapCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decoder for DapCodec {
+    type Item = Message;
+    type Error = Error;
+
+    fn decode(&mut self, buffer: &mut BytesMut) -> Result<Option<Message>, Error> {
+        // the header end with a empty line, which may be a bare line feed like with
+        // `Header::from_input`
+        let header_len = match find_header_end(buffer, self.search_from) {
+            Ok(header_len) => header_len,
+            Err(_) if buffer.len() > MAX_HEADER_BYTES => return Err(Error::Invalid),
+            Err(next_line) => {
+                self.search_from = next_line;
+                return Ok(None);
+            }
+        };
+        // until the content is read, resume from the empty line
+        self.search_from = if buffer[..header_len].ends_with(b"\r\n") {
+            header_len - 2
+        } else {
+            header_len - 1
+        };
+
+        let header = Header::from_input(&mut &buffer[..header_len])?;
+        header.validate(self.max_message_size)?;
+
+        let message_len = header_len + header.content_length;
+        if buffer.len() < message_len {
+            buffer.reserve(message_len - buffer.len());
+            return Ok(None);
+        }
+
+        buffer.advance(header_len);
+        self.search_from = 0;
+        let content = buffer.split_to(header.content_length);
+        Message::from_content(content.to_vec()).map(Some)
+    }
+}
+
+impl Encoder<Message> for DapCodec {
+    type Error = Error;
+
+    fn encode(&mut self, message: Message, buffer: &mut BytesMut) -> Result<(), Error> {
+        buffer.extend_from_slice(&message.to_bytes()?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+    use std::convert::TryFrom;
+
+    use futures::{SinkExt, StreamExt};
+    use tokio::io::AsyncWriteExt;
+    use tokio_util::codec::{Framed, FramedRead};
+
+    #[tokio::test]
+    async fn framed_duplex() {
+        let (client, adapter) = tokio::io::duplex(64);
+        let mut client = Framed::new(client, DapCodec::new());
+        let mut adapter = Framed::new(adapter, DapCodec::new());
+
+        let raw_value = serde_json::json!({ "seq": 1, "type": "event", "event": "initialized" });
+        let message = Message::try_from(raw_value.clone()).unwrap();
+
+        // the duplex buffer is smaller than the message, which is therefore read in several parts
+        let (sent, received) = tokio::join!(client.send(message), adapter.next());
+        sent.unwrap();
+        let received = received.unwrap().unwrap();
+
+        assert_eq!(received.seq(), 1);
+        assert_eq!(*received.body(), raw_value);
+    }
+
+    #[test]
+    fn decode_header_without_end() {
+        let mut codec = DapCodec::new();
+        let mut buffer = BytesMut::new();
+
+        // the header arrive in parts, none of them ending it
+        let header = format!("X-Padding: {}", "x".repeat(MAX_HEADER_BYTES));
+        for part in header.as_bytes()[..MAX_HEADER_BYTES].chunks(1000) {
+            buffer.extend_from_slice(part);
+            assert!(codec.decode(&mut buffer).unwrap().is_none());
+        }
+
+        buffer.extend_from_slice(b"x");
+        assert_eq!(buffer.len(), MAX_HEADER_BYTES + 1);
+        match codec.decode(&mut buffer) {
+            Err(Error::Invalid) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn decode_terminator_across_parts() {
+        let mut codec = DapCodec::new();
+        let mut buffer = BytesMut::new();
+
+        let body = r#"{"seq":1,"type":"event","event":"initialized"}"#;
+        let message = format!("Content-Length: {}\r\n\r\n{}", body.len(), body);
+        let (header, content) = message.split_at(message.find("\n\r\n").unwrap() + 1);
+
+        buffer.extend_from_slice(header.as_bytes());
+        assert!(codec.decode(&mut buffer).unwrap().is_none());
+        buffer.extend_from_slice(content.as_bytes());
+        assert_eq!(codec.decode(&mut buffer).unwrap().unwrap().seq(), 1);
+        assert!(buffer.is_empty());
+
+        // the next message is searched from the start of the buffer
+        buffer.extend_from_slice(message.as_bytes());
+        assert_eq!(codec.decode(&mut buffer).unwrap().unwrap().seq(), 1);
+    }
+
+    #[test]
+    fn decode_bare_line_feed() {
+        let mut codec = DapCodec::new();
+        let mut buffer = BytesMut::new();
+
+        let body = r#"{"seq":1,"type":"event","event":"initialized"}"#;
+        let message = format!("Content-Length: {}\n\n{}", body.len(), body);
+        let (header, content) = message.split_at(message.find('{').unwrap());
+
+        buffer.extend_from_slice(header.as_bytes());
+        buffer.extend_from_slice(&content.as_bytes()[..10]);
+        assert!(codec.decode(&mut buffer).unwrap().is_none());
+        buffer.extend_from_slice(&content.as_bytes()[10..]);
+        assert_eq!(codec.decode(&mut buffer).unwrap().unwrap().seq(), 1);
+        assert!(buffer.is_empty());
+    }
+
+    #[tokio::test]
+    async fn decode_too_large() {
+        let (mut client, adapter) = tokio::io::duplex(64);
+        let mut adapter = FramedRead::new(adapter, DapCodec::new().with_max_message_size(10));
+
+        client
+            .write_all(b"Content-Length: 100\r\n\r\n")
+            .await
+            .unwrap();
+
+        match adapter.next().await {
+            Some(Err(Error::MessageTooLarge {
+                actual: 100,
+                limit: 10,
+            })) => (),
+            _ => panic!(),
+        }
+    }
+}
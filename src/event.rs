@@ -0,0 +1,1430 @@
+//! The events, and their bodies.
+
+use std::cell::Cell;
+use std::convert::TryFrom;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    Breakpoint, Capabilities, Error, InvalidatedAreas, Message, Module, ProtocolMessage, Source,
+    WriteMemoryArguments,
+};
+
+/// A debug adapter initiated event.
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub(crate) event_info: EventInfo,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct EventInfo {
+    pub(crate) seq: usize,
+
+    /**
+     * Type of event.
+     */
+    pub(crate) event: String,
+
+    /**
+     * Event-specific information.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) body: Option<serde_json::Value>,
+}
+
+impl Event {
+    /// Create a event to be sent with the sequence number `seq`.
+    pub fn new(seq: usize, event_kind: EventKind) -> Result<Self, Error> {
+        let event_info = EventInfo {
+            seq,
+            event: event_kind.event().to_string(),
+            body: event_kind.body()?,
+        };
+        Ok(Self { event_info })
+    }
+
+    #[doc(hidden)]
+    pub fn seq(&self) -> usize {
+        self.event_info.seq
+    }
+
+    #[doc(hidden)]
+    pub fn event(&self) -> &str {
+        self.event_info.event.as_str()
+    }
+
+    #[doc(hidden)]
+    pub fn body(&self) -> Option<&serde_json::Value> {
+        self.event_info.body.as_ref()
+    }
+
+    /// Parse the event into its specialized form.
+    ///
+    /// Unknown events are returned as `EventKind::Other`. A body which does not match its event
+    /// is a invalid message.
+    pub fn event_kind(&self) -> Result<EventKind, Error> {
+        EventKind::from_parts(self.event(), self.body().cloned())
+    }
+}
+
+/// A event is serialized as sent on the wire, `type` field included.
+impl Serialize for Event {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.into_json_value()
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+/// The specialized form of a event, chosen from its `event` field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventKind {
+    Breakpoint(BreakpointEvent),
+    Capabilities(CapabilitiesEvent),
+    Continued(ContinuedEvent),
+    Exited(ExitedEvent),
+    Initialized,
+    Invalidated(InvalidatedEvent),
+    LoadedSource(LoadedSourceEvent),
+    Memory(MemoryEvent),
+    Module(ModuleEvent),
+    Output(OutputEvent),
+    ProgressEnd(ProgressEndEvent),
+    ProgressStart(ProgressStartEvent),
+    ProgressUpdate(ProgressUpdateEvent),
+    Stopped(StoppedEvent),
+    Terminated(TerminatedEvent),
+    Thread(ThreadEvent),
+    /// A event which is not supported by this crate. Its body is sent untouched.
+    Other {
+        event: String,
+        body: Option<serde_json::Value>,
+    },
+}
+
+impl EventKind {
+    /// Return the value of the `event` field for this kind of event.
+    pub fn event(&self) -> &str {
+        match self {
+            EventKind::Breakpoint(_) => "breakpoint",
+            EventKind::Capabilities(_) => "capabilities",
+            EventKind::Continued(_) => "continued",
+            EventKind::Exited(_) => "exited",
+            EventKind::Initialized => "initialized",
+            EventKind::Invalidated(_) => "invalidated",
+            EventKind::LoadedSource(_) => "loadedSource",
+            EventKind::Memory(_) => "memory",
+            EventKind::Module(_) => "module",
+            EventKind::Output(_) => "output",
+            EventKind::ProgressEnd(_) => "progressEnd",
+            EventKind::ProgressStart(_) => "progressStart",
+            EventKind::ProgressUpdate(_) => "progressUpdate",
+            EventKind::Stopped(_) => "stopped",
+            EventKind::Terminated(_) => "terminated",
+            EventKind::Thread(_) => "thread",
+            EventKind::Other { event, .. } => event,
+        }
+    }
+
+    /// Return the body of the event, as sent on the wire.
+    pub fn body(&self) -> Result<Option<serde_json::Value>, Error> {
+        let body = match self {
+            EventKind::Breakpoint(body) => serde_json::to_value(body)?,
+            EventKind::Capabilities(body) => serde_json::to_value(body)?,
+            EventKind::Continued(body) => serde_json::to_value(body)?,
+            EventKind::Exited(body) => serde_json::to_value(body)?,
+            EventKind::Initialized => return Ok(None),
+            EventKind::Invalidated(body) => serde_json::to_value(body)?,
+            EventKind::LoadedSource(body) => serde_json::to_value(body)?,
+            EventKind::Memory(body) => serde_json::to_value(body)?,
+            EventKind::Module(body) => serde_json::to_value(body)?,
+            EventKind::Output(body) => serde_json::to_value(body)?,
+            EventKind::ProgressEnd(body) => serde_json::to_value(body)?,
+            EventKind::ProgressStart(body) => serde_json::to_value(body)?,
+            EventKind::ProgressUpdate(body) => serde_json::to_value(body)?,
+            EventKind::Stopped(body) => serde_json::to_value(body)?,
+            EventKind::Terminated(body) => serde_json::to_value(body)?,
+            EventKind::Thread(body) => serde_json::to_value(body)?,
+            EventKind::Other { body, .. } => return Ok(body.clone()),
+        };
+        Ok(Some(body))
+    }
+
+    /// Specialize the event named `event`, whose body is `body`.
+    fn from_parts(event: &str, body: Option<serde_json::Value>) -> Result<Self, Error> {
+        /// Parse the body of a known event. A absent body is read as a empty one, which is
+        /// accepted when every field of the body is optional, as for 'terminated'.
+        fn parse<T: DeserializeOwned>(body: Option<serde_json::Value>) -> Result<T, Error> {
+            let body = body.unwrap_or_else(|| serde_json::json!({}));
+            serde_json::from_value(body).or(Err(Error::InvalidMessage))
+        }
+
+        let kind = match event {
+            "breakpoint" => EventKind::Breakpoint(parse(body)?),
+            "capabilities" => EventKind::Capabilities(parse(body)?),
+            "continued" => EventKind::Continued(parse(body)?),
+            "exited" => EventKind::Exited(parse(body)?),
+            "initialized" => EventKind::Initialized,
+            "invalidated" => EventKind::Invalidated(parse(body)?),
+            "loadedSource" => EventKind::LoadedSource(parse(body)?),
+            "memory" => EventKind::Memory(parse(body)?),
+            "module" => EventKind::Module(parse(body)?),
+            "output" => EventKind::Output(parse(body)?),
+            "progressEnd" => EventKind::ProgressEnd(parse(body)?),
+            "progressStart" => EventKind::ProgressStart(parse(body)?),
+            "progressUpdate" => EventKind::ProgressUpdate(parse(body)?),
+            "stopped" => EventKind::Stopped(parse(body)?),
+            "terminated" => EventKind::Terminated(parse(body)?),
+            "thread" => EventKind::Thread(parse(body)?),
+            _ => EventKind::Other {
+                event: event.to_string(),
+                body,
+            },
+        };
+        Ok(kind)
+    }
+
+    /// Turn the event into a message, to be sent with the sequence number `seq`.
+    pub fn into_message(self, seq: usize) -> Result<Message, Error> {
+        Message::try_from(Event::new(seq, self)?)
+    }
+}
+
+macro_rules! event_kind_from {
+    ($($body:ident => $variant:ident,)*) => {
+        $(
+            impl From<$body> for EventKind {
+                fn from(body: $body) -> Self {
+                    EventKind::$variant(body)
+                }
+            }
+        )*
+    };
+}
+
+event_kind_from! {
+    BreakpointEvent => Breakpoint,
+    CapabilitiesEvent => Capabilities,
+    ContinuedEvent => Continued,
+    ExitedEvent => Exited,
+    InvalidatedEvent => Invalidated,
+    LoadedSourceEvent => LoadedSource,
+    MemoryEvent => Memory,
+    ModuleEvent => Module,
+    OutputEvent => Output,
+    ProgressEndEvent => ProgressEnd,
+    ProgressStartEvent => ProgressStart,
+    ProgressUpdateEvent => ProgressUpdate,
+    StoppedEvent => Stopped,
+    TerminatedEvent => Terminated,
+    ThreadEvent => Thread,
+}
+
+/// A event kind is serialized as the `event` and `body` fields of a event.
+impl Serialize for EventKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Fields<'a> {
+            event: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            body: Option<serde_json::Value>,
+        }
+
+        let body = self.body().map_err(serde::ser::Error::custom)?;
+        Fields {
+            event: self.event(),
+            body,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// A event kind is deserialized from the `event` and `body` fields of a event.
+impl<'de> Deserialize<'de> for EventKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Fields {
+            event: String,
+            #[serde(default)]
+            body: Option<serde_json::Value>,
+        }
+
+        let Fields { event, body } = Fields::deserialize(deserializer)?;
+        EventKind::from_parts(&event, body)
+            .map_err(|_| serde::de::Error::custom(format_args!("invalid body for event {}", event)))
+    }
+}
+
+impl TryFrom<Message> for EventKind {
+    type Error = Error;
+
+    fn try_from(message: Message) -> Result<Self, Error> {
+        Event::try_from(message)?.event_kind()
+    }
+}
+
+/// The event indicates that the execution of the debuggee has stopped due to some condition.
+///
+/// This can be caused by a break point previously set, a stepping request has completed, by executing a debugger statement etc.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoppedEvent {
+    /**
+     * The reason for the event.
+     * For backward compatibility this string is shown in the UI if the
+     * 'description' attribute is missing (but it must not be translated).
+     */
+    pub reason: StoppedEventReason,
+
+    /**
+     * The full reason for the event, e.g. 'Paused on exception'. This string is
+     * shown in the UI as is and must be translated.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /**
+     * The thread which was stopped.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<usize>,
+
+    /**
+     * A value of true hints to the frontend that this event should not change
+     * the focus.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preserve_focus_hint: Option<bool>,
+
+    /**
+     * Additional information. E.g. if reason is 'exception', text contains the
+     * exception name. This string is shown in the UI.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    /**
+     * If 'allThreadsStopped' is true, a debug adapter can announce that all
+     * threads have stopped.
+     * - The client should use this information to enable that all threads can
+     *   be expanded to access their stacktraces.
+     * - If the attribute is missing or false, only the thread with the given
+     *   threadId can be expanded.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all_threads_stopped: Option<bool>,
+
+    /**
+     * Ids of the breakpoints that triggered the event. In most cases there will
+     * be only a single breakpoint but here are some examples for multiple
+     * breakpoints:
+     * - Different types of breakpoints map to the same location.
+     * - Multiple source breakpoints get collapsed to the same instruction by the
+     *   compiler/runtime.
+     * - Multiple function breakpoints with different function names map to the
+     *   same location.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hit_breakpoint_ids: Option<Vec<usize>>,
+}
+
+impl StoppedEvent {
+    pub fn new(reason: StoppedEventReason) -> Self {
+        Self {
+            reason,
+            description: None,
+            thread_id: None,
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }
+    }
+
+    /// The thread `thread_id` stopped after a step.
+    pub fn step(thread_id: usize) -> Self {
+        Self::new(StoppedEventReason::Step).thread_id(thread_id)
+    }
+
+    /// The thread `thread_id` stopped on the breakpoints `hit_breakpoint_ids`.
+    pub fn breakpoint(thread_id: usize, hit_breakpoint_ids: Vec<usize>) -> Self {
+        Self {
+            hit_breakpoint_ids: Some(hit_breakpoint_ids),
+            ..Self::new(StoppedEventReason::Breakpoint).thread_id(thread_id)
+        }
+    }
+
+    /// The thread `thread_id` stopped on a exception, described by `text`.
+    pub fn exception(thread_id: usize, text: impl Into<String>) -> Self {
+        Self::new(StoppedEventReason::Exception)
+            .thread_id(thread_id)
+            .text(text)
+    }
+
+    /// The thread `thread_id` was paused by a 'pause' request.
+    pub fn pause(thread_id: usize) -> Self {
+        Self::new(StoppedEventReason::Pause).thread_id(thread_id)
+    }
+
+    /// The thread `thread_id` stopped on the entry of the program.
+    pub fn entry(thread_id: usize) -> Self {
+        Self::new(StoppedEventReason::Entry).thread_id(thread_id)
+    }
+
+    pub fn thread_id(mut self, thread_id: usize) -> Self {
+        self.thread_id = Some(thread_id);
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    pub fn preserve_focus_hint(mut self, preserve_focus_hint: bool) -> Self {
+        self.preserve_focus_hint = Some(preserve_focus_hint);
+        self
+    }
+
+    pub fn all_threads_stopped(mut self, all_threads_stopped: bool) -> Self {
+        self.all_threads_stopped = Some(all_threads_stopped);
+        self
+    }
+}
+
+/// The reason for a ‘stopped’ event.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StoppedEventReason {
+    Step,
+    Breakpoint,
+    Exception,
+    Pause,
+    Entry,
+    Goto,
+    #[serde(rename = "function breakpoint")]
+    FunctionBreakpoint,
+    #[serde(rename = "data breakpoint")]
+    DataBreakpoint,
+    #[serde(rename = "instruction breakpoint")]
+    InstructionBreakpoint,
+    /// A reason which is not part of the specification.
+    #[serde(untagged)]
+    Other(String),
+}
+
+/// The event indicates that the execution of the debuggee has continued.
+///
+/// Please note: a debug adapter is not expected to send this event in response to a request that implies that execution continues, e.g. ‘launch’ or ‘continue’.
+///
+/// It is only necessary to send a ‘continued’ event if there was no previous request that implied this.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContinuedEvent {
+    /**
+     * The thread which was continued.
+     */
+    pub thread_id: usize,
+
+    /**
+     * If 'allThreadsContinued' is true, a debug adapter can announce that all
+     * threads have continued.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all_threads_continued: Option<bool>,
+}
+
+impl ContinuedEvent {
+    pub fn new(thread_id: usize) -> Self {
+        Self {
+            thread_id,
+            all_threads_continued: None,
+        }
+    }
+
+    pub fn all_threads_continued(mut self, all_threads_continued: bool) -> Self {
+        self.all_threads_continued = Some(all_threads_continued);
+        self
+    }
+}
+
+/// The event indicates that the debuggee has exited and returns its exit code.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExitedEvent {
+    /**
+     * The exit code returned from the debuggee.
+     */
+    pub exit_code: i64,
+}
+
+impl ExitedEvent {
+    pub fn new(exit_code: i64) -> Self {
+        Self { exit_code }
+    }
+}
+
+/// The event indicates that debugging of the debuggee has terminated. This does not mean that the debuggee itself has exited.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminatedEvent {
+    /**
+     * A debug adapter may set 'restart' to true (or to an arbitrary object) to
+     * request that the front end restarts the session.
+     * The value is not interpreted by the client and passed unmodified as an
+     * attribute '__restart' to the 'launch' and 'attach' requests.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restart: Option<serde_json::Value>,
+}
+
+impl TerminatedEvent {
+    /// Ask the client to restart the session, passing `restart` to the next 'launch' or 'attach'
+    /// request.
+    pub fn restart(restart: serde_json::Value) -> Self {
+        Self {
+            restart: Some(restart),
+        }
+    }
+}
+
+/// The event indicates that a thread has started or exited.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadEvent {
+    /**
+     * The reason for the event.
+     */
+    pub reason: ThreadEventReason,
+
+    /**
+     * The identifier of the thread.
+     */
+    pub thread_id: usize,
+}
+
+impl ThreadEvent {
+    pub fn started(thread_id: usize) -> Self {
+        Self {
+            reason: ThreadEventReason::Started,
+            thread_id,
+        }
+    }
+
+    pub fn exited(thread_id: usize) -> Self {
+        Self {
+            reason: ThreadEventReason::Exited,
+            thread_id,
+        }
+    }
+}
+
+/// The reason for a ‘thread’ event.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ThreadEventReason {
+    Started,
+    Exited,
+    /// A reason which is not part of the specification.
+    #[serde(untagged)]
+    Other(String),
+}
+
+/// The event indicates that the target has produced some output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputEvent {
+    /**
+     * The output category. If not specified, 'console' is assumed.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<OutputCategory>,
+
+    /**
+     * The output to report.
+     */
+    pub output: String,
+
+    /**
+     * Support for keeping an output log organized by grouping related messages.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<OutputGroup>,
+
+    /**
+     * If an attribute 'variablesReference' exists and its value is > 0, the
+     * output contains objects which can be retrieved by passing
+     * 'variablesReference' to the 'variables' request. The value should be less
+     * than or equal to 2147483647 (2^31-1).
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables_reference: Option<usize>,
+
+    /**
+     * An optional source location where the output was produced.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<Source>,
+
+    /**
+     * An optional source location line where the output was produced.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+
+    /**
+     * An optional source location column where the output was produced.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+
+    /**
+     * Optional data to report. For the 'telemetry' category the data will be
+     * sent to telemetry, for the other categories the data is shown in JSON
+     * format.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+impl OutputEvent {
+    pub fn new(category: Option<OutputCategory>, output: impl Into<String>) -> Self {
+        Self {
+            category,
+            output: output.into(),
+            group: None,
+            variables_reference: None,
+            source: None,
+            line: None,
+            column: None,
+            data: None,
+        }
+    }
+
+    pub fn console(output: impl Into<String>) -> Self {
+        Self::new(Some(OutputCategory::Console), output)
+    }
+
+    pub fn stdout(output: impl Into<String>) -> Self {
+        Self::new(Some(OutputCategory::Stdout), output)
+    }
+
+    pub fn stderr(output: impl Into<String>) -> Self {
+        Self::new(Some(OutputCategory::Stderr), output)
+    }
+
+    pub fn group(mut self, group: OutputGroup) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    pub fn variables_reference(mut self, variables_reference: usize) -> Self {
+        self.variables_reference = Some(variables_reference);
+        self
+    }
+
+    /// Set the source location where the output was produced.
+    pub fn location(mut self, source: Source, line: usize, column: Option<usize>) -> Self {
+        self.source = Some(source);
+        self.line = Some(line);
+        self.column = column;
+        self
+    }
+
+    pub fn data(mut self, data: serde_json::Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+}
+
+/// The category of a ‘output’ event.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OutputCategory {
+    Console,
+    Important,
+    Stdout,
+    Stderr,
+    Telemetry,
+    /// A category which is not part of the specification.
+    #[serde(untagged)]
+    Other(String),
+}
+
+/// How a ‘output’ event change the grouping of the output log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OutputGroup {
+    /// Start a new group in expanded mode. Subsequent output events are members of the group and should be shown indented.
+    ///
+    /// The ‘output’ attribute becomes the name of the group and is not indented.
+    Start,
+    /// Start a new group in collapsed mode. Subsequent output events are members of the group and should be shown indented (as soon as the group is expanded).
+    ///
+    /// The ‘output’ attribute becomes the name of the group and is not indented.
+    StartCollapsed,
+    /// End the current group and decreases the indentation of subsequent output events.
+    ///
+    /// A non empty ‘output’ attribute is shown as the unindented end of the group.
+    End,
+}
+
+/// The event indicates that some information about a breakpoint has changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BreakpointEvent {
+    /**
+     * The reason for the event.
+     */
+    pub reason: BreakpointEventReason,
+
+    /**
+     * The 'id' attribute is used to find the target breakpoint and the other
+     * attributes are used as the new values.
+     */
+    pub breakpoint: Breakpoint,
+}
+
+impl BreakpointEvent {
+    pub fn new(reason: BreakpointEventReason, breakpoint: Breakpoint) -> Self {
+        Self { reason, breakpoint }
+    }
+
+    pub fn changed(breakpoint: Breakpoint) -> Self {
+        Self::new(BreakpointEventReason::Changed, breakpoint)
+    }
+
+    pub fn removed(breakpoint: Breakpoint) -> Self {
+        Self::new(BreakpointEventReason::Removed, breakpoint)
+    }
+}
+
+/// The reason for a ‘breakpoint’ event.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BreakpointEventReason {
+    Changed,
+    New,
+    Removed,
+    /// A reason which is not part of the specification.
+    #[serde(untagged)]
+    Other(String),
+}
+
+/// The event indicates that one or more capabilities have changed.
+///
+/// Since the capabilities are dependent on the frontend and its UI, it might not be possible to change that at random times (or too late).
+///
+/// Consequently this event has a hint characteristic: a frontend can only be expected to make a ‘best effort’ in honouring individual capabilities but there are no guarantees.
+///
+/// Only changed capabilities need to be included, all other capabilities keep their values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilitiesEvent {
+    /**
+     * The set of updated capabilities.
+     */
+    pub capabilities: Capabilities,
+}
+
+/// This event signals that some state in the debug adapter has changed and requires that the client needs to re-render the data snapshot previously requested.
+///
+/// Debug adapters do not have to emit this event for runtime changes like stopped or thread events because in that case the client refetches the new state anyway. But the event can be used for example to refresh the UI after rendering formatting has changed in the debug adapter.
+///
+/// This event should only be sent if the debug adapter has received a value true for the ‘supportsInvalidatedEvent’ capability of the ‘initialize’ request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvalidatedEvent {
+    /**
+     * Optional set of logical areas that got invalidated. This property has a
+     * hint characteristic: a client can only be expected to make a 'best effort'
+     * in honouring the areas but there are no guarantees. If this property is
+     * missing, empty, or if values are not understand the client should assume a
+     * single value 'all'.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub areas: Option<Vec<InvalidatedAreas>>,
+
+    /**
+     * If specified, the client only needs to refetch data related to this
+     * thread.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<usize>,
+
+    /**
+     * If specified, the client only needs to refetch data related to this stack
+     * frame (and the 'threadId' is ignored).
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stack_frame_id: Option<usize>,
+}
+
+/// This event indicates that some memory range has been updated. It should only be sent if the corresponding capability ‘supportsMemoryEvent’ is true.
+///
+/// Clients typically react to the event by re-issuing a ‘readMemory’ request if they show the memory identified by the ‘memoryReference’ and if the updated memory range overlaps the displayed range. Clients should not make assumptions how individual memory references relate to each other, so they should not assume that they are part of a single continuous address range and might overlap.
+///
+/// Debug adapters can use this event to indicate that the contents of a memory range has changed due to some other request like ‘setVariable’ or ‘setExpression’. Debug adapters are not expected to emit this event for each and every memory change of a running program, because that information is typically not available from debuggers and it would flood clients with too many events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryEvent {
+    /**
+     * Memory reference of a memory range that has been updated.
+     */
+    pub memory_reference: String,
+
+    /**
+     * Starting offset in bytes where memory has been updated. Can be negative.
+     */
+    pub offset: i64,
+
+    /**
+     * Number of bytes updated.
+     */
+    pub count: usize,
+}
+
+impl MemoryEvent {
+    pub fn new(memory_reference: impl Into<String>, offset: i64, count: usize) -> Self {
+        Self {
+            memory_reference: memory_reference.into(),
+            offset,
+            count,
+        }
+    }
+
+    /// Create the event reporting the memory updated by a 'writeMemory' request.
+    ///
+    /// `bytes_written` is the number of bytes actually written, which is less than the bytes
+    /// sent when `allow_partial` let the write stop early.
+    pub fn written(arguments: &WriteMemoryArguments, bytes_written: usize) -> Self {
+        Self::new(
+            arguments.memory_reference.clone(),
+            arguments.offset.unwrap_or(0),
+            bytes_written,
+        )
+    }
+}
+
+/// The event indicates that some information about a module has changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModuleEvent {
+    /**
+     * The reason for the event.
+     */
+    pub reason: ModuleEventReason,
+
+    /**
+     * The new, changed, or removed module. In case of 'removed' only the module
+     * id is used.
+     */
+    pub module: Module,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ModuleEventReason {
+    New,
+    Changed,
+    Removed,
+}
+
+/// The event indicates that some source has been added, changed, or removed from the set of all loaded sources.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoadedSourceEvent {
+    /**
+     * The reason for the event.
+     */
+    pub reason: LoadedSourceEventReason,
+
+    /**
+     * The new, changed, or removed source.
+     */
+    pub source: Source,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LoadedSourceEventReason {
+    New,
+    Changed,
+    Removed,
+}
+
+/// The event signals that a long running operation is about to start and provides additional information for the client to set up a corresponding progress and cancellation UI.
+///
+/// The client is free to delay the showing of the UI in order to reduce flicker.
+///
+/// This event should only be sent if the client has passed the value true for the ‘supportsProgressReporting’ capability of the ‘initialize’ request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgressStartEvent {
+    /**
+     * An ID that must be used in subsequent 'progressUpdate' and 'progressEnd'
+     * events to make them refer to the same progress reporting.
+     * IDs must be unique within a debug session.
+     */
+    pub progress_id: String,
+
+    /**
+     * Mandatory (short) title of the progress reporting. Shown in the UI to
+     * describe the long running operation.
+     */
+    pub title: String,
+
+    /**
+     * The request ID that this progress report is related to. If specified a
+     * debug adapter is expected to emit
+     * progress events for the long running request until the request has been
+     * either completed or cancelled.
+     * If the request ID is omitted, the progress report is assumed to be
+     * related to some general activity of the debug adapter.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<usize>,
+
+    /**
+     * If true, the request that reports progress may be canceled with a
+     * 'cancel' request.
+     * So this property basically controls whether the client should use UX that
+     * supports cancellation.
+     * Clients that don't support cancellation are allowed to ignore the
+     * setting.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cancellable: Option<bool>,
+
+    /**
+     * Optional, more detailed progress message.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
+    /**
+     * Optional progress percentage to display (value range: 0 to 100). If
+     * omitted no percentage will be shown.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percentage: Option<f32>,
+}
+
+/// The event signals that the progress reporting needs to updated with a new message and/or percentage.
+///
+/// The client does not have to update the UI immediately, but the clients needs to keep track of the message and/or percentage values.
+///
+/// This event should only be sent if the client has passed the value true for the ‘supportsProgressReporting’ capability of the ‘initialize’ request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgressUpdateEvent {
+    /**
+     * The ID that was introduced in the initial 'progressStart' event.
+     */
+    pub progress_id: String,
+
+    /**
+     * Optional, more detailed progress message. If omitted, the previous
+     * message (if any) is used.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
+    /**
+     * Optional progress percentage to display (value range: 0 to 100). If
+     * omitted no percentage will be shown.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percentage: Option<f32>,
+}
+
+/// The event signals the end of the progress reporting with an optional final message.
+///
+/// This event should only be sent if the client has passed the value true for the ‘supportsProgressReporting’ capability of the ‘initialize’ request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgressEndEvent {
+    /**
+     * The ID that was introduced in the initial 'ProgressStartEvent'.
+     */
+    pub progress_id: String,
+
+    /**
+     * Optional, more detailed progress message. If omitted, the previous
+     * message (if any) is used.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Keep track of a progress reporting, from its start event to its end event.
+///
+/// Every tracker get a unique `progress_id`. Since `end` consume the tracker, no update can be
+/// produced once the progress reporting has ended.
+#[derive(Debug)]
+pub struct ProgressTracker {
+    progress_id: String,
+    percentage: Cell<f32>,
+}
+
+impl ProgressTracker {
+    /// Start a new progress reporting, returning the tracker and the event to send to the client.
+    pub fn start(title: &str) -> (Self, ProgressStartEvent) {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+        let progress_id = format!("progress-{}", NEXT_ID.fetch_add(1, Ordering::Relaxed));
+
+        let event = ProgressStartEvent {
+            progress_id: progress_id.clone(),
+            title: title.to_string(),
+            request_id: None,
+            cancellable: None,
+            message: None,
+            percentage: None,
+        };
+        let tracker = Self {
+            progress_id,
+            percentage: Cell::new(0.0),
+        };
+        (tracker, event)
+    }
+
+    /// The id shared by every event of this progress reporting.
+    pub fn progress_id(&self) -> &str {
+        &self.progress_id
+    }
+
+    /// Produce a update event.
+    ///
+    /// Return `Error::InvalidMessage` if `pct` is not in `[0, 100]`, or is lower than the
+    /// percentage of a previous update.
+    pub fn update(&self, pct: f32) -> Result<ProgressUpdateEvent, Error> {
+        // a NaN is rejected, since it is not part of the range
+        if !(0.0..=100.0).contains(&pct) || pct < self.percentage.get() {
+            return Err(Error::InvalidMessage);
+        }
+        self.percentage.set(pct);
+
+        Ok(ProgressUpdateEvent {
+            progress_id: self.progress_id.clone(),
+            message: None,
+            percentage: Some(pct),
+        })
+    }
+
+    /// End the progress reporting, with a optional final message.
+    pub fn end(self, msg: Option<&str>) -> ProgressEndEvent {
+        ProgressEndEvent {
+            progress_id: self.progress_id,
+            message: msg.map(str::to_string),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+
+    /// Check that `event_kind` is sent as `expected`, and that its body can be read back.
+    fn check_event<T>(event_kind: EventKind, expected: serde_json::Value, body: T)
+    where
+        T: std::fmt::Debug + PartialEq + serde::de::DeserializeOwned,
+    {
+        let message = event_kind.into_message(7).unwrap();
+        assert_eq!(*message.body(), expected);
+
+        let event = Event::try_from(message).unwrap();
+        assert_eq!(serde_json::to_value(&event).unwrap(), expected);
+        let read_back: T = serde_json::from_value(event.body().unwrap().clone()).unwrap();
+        assert_eq!(read_back, body);
+    }
+
+    #[test]
+    fn read_recorded_events() {
+        let stream = [
+            r#"{"seq":1,"type":"event","event":"initialized"}"#,
+            r#"{"seq":2,"type":"event","event":"stopped","body":{"reason":"breakpoint","threadId":1,"hitBreakpointIds":[2]}}"#,
+            r#"{"seq":3,"type":"event","event":"output","body":{"category":"stdout","output":"hello\n"}}"#,
+            r#"{"seq":4,"type":"event","event":"custom","body":{"answer":42}}"#,
+            r#"{"seq":5,"type":"event","event":"terminated"}"#,
+        ]
+        .iter()
+        .map(|body| format!("Content-Length: {}\r\n\r\n{}", body.len(), body))
+        .collect::<String>();
+
+        let events: Vec<_> = crate::test_util::read_messages(stream.as_bytes())
+            .into_iter()
+            .map(|message| EventKind::try_from(message).unwrap())
+            .collect();
+
+        assert_eq!(
+            events,
+            [
+                EventKind::Initialized,
+                EventKind::Stopped(StoppedEvent::breakpoint(1, vec![2])),
+                EventKind::Output(OutputEvent::stdout("hello\n")),
+                EventKind::Other {
+                    event: "custom".to_string(),
+                    body: Some(serde_json::json!({ "answer": 42 })),
+                },
+                EventKind::Terminated(TerminatedEvent::default()),
+            ]
+        );
+    }
+
+    #[test]
+    fn event_kind_deserialize() {
+        let value = serde_json::json!({ "event": "exited", "body": { "exitCode": 3 } });
+        let kind: EventKind = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(kind, EventKind::Exited(ExitedEvent::new(3)));
+        assert_eq!(serde_json::to_value(&kind).unwrap(), value);
+
+        // the exit code is required
+        let value = serde_json::json!({ "event": "exited" });
+        assert!(serde_json::from_value::<EventKind>(value).is_err());
+    }
+
+    #[test]
+    fn build_events() {
+        let stopped = StoppedEvent::breakpoint(1, vec![4, 5])
+            .preserve_focus_hint(false)
+            .all_threads_stopped(true);
+        assert_eq!(
+            serde_json::to_value(&stopped).unwrap(),
+            serde_json::json!({
+                "reason": "breakpoint",
+                "threadId": 1,
+                "preserveFocusHint": false,
+                "allThreadsStopped": true,
+                "hitBreakpointIds": [4, 5]
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(StoppedEvent::step(2)).unwrap(),
+            serde_json::json!({ "reason": "step", "threadId": 2 })
+        );
+
+        let output = OutputEvent::stdout("hello\n").group(OutputGroup::Start);
+        assert_eq!(
+            serde_json::to_value(&output).unwrap(),
+            serde_json::json!({ "category": "stdout", "output": "hello\n", "group": "start" })
+        );
+
+        assert_eq!(
+            serde_json::to_value(ExitedEvent::new(-1)).unwrap(),
+            serde_json::json!({ "exitCode": -1 })
+        );
+        assert_eq!(
+            serde_json::to_value(ThreadEvent::started(3)).unwrap(),
+            serde_json::json!({ "reason": "started", "threadId": 3 })
+        );
+        assert_eq!(
+            serde_json::to_value(BreakpointEvent::changed(Breakpoint::new(false))).unwrap(),
+            serde_json::json!({ "reason": "changed", "breakpoint": { "verified": false } })
+        );
+    }
+
+    #[test]
+    fn stopped_event_round_trip() {
+        let stopped = StoppedEvent {
+            reason: StoppedEventReason::FunctionBreakpoint,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: Some(true),
+            hit_breakpoint_ids: Some(vec![3]),
+        };
+
+        check_event(
+            EventKind::Stopped(stopped.clone()),
+            serde_json::json!({
+                "seq": 7,
+                "type": "event",
+                "event": "stopped",
+                "body": {
+                    "reason": "function breakpoint",
+                    "threadId": 1,
+                    "allThreadsStopped": true,
+                    "hitBreakpointIds": [3]
+                }
+            }),
+            stopped,
+        );
+    }
+
+    #[test]
+    fn stopped_reasons_match_spec() {
+        let reasons = [
+            (StoppedEventReason::Step, "step"),
+            (StoppedEventReason::Breakpoint, "breakpoint"),
+            (StoppedEventReason::Exception, "exception"),
+            (StoppedEventReason::Pause, "pause"),
+            (StoppedEventReason::Entry, "entry"),
+            (StoppedEventReason::Goto, "goto"),
+            (
+                StoppedEventReason::FunctionBreakpoint,
+                "function breakpoint",
+            ),
+            (StoppedEventReason::DataBreakpoint, "data breakpoint"),
+            (
+                StoppedEventReason::InstructionBreakpoint,
+                "instruction breakpoint",
+            ),
+            (
+                StoppedEventReason::Other("hit count".to_string()),
+                "hit count",
+            ),
+            // the camel case spelling is not the spec one, it must survive unchanged
+            (
+                StoppedEventReason::Other("functionBreakpoint".to_string()),
+                "functionBreakpoint",
+            ),
+        ];
+
+        for (reason, name) in reasons.iter() {
+            assert_eq!(serde_json::to_value(reason).unwrap(), *name);
+            let parsed: StoppedEventReason =
+                serde_json::from_value(serde_json::json!(name)).unwrap();
+            assert_eq!(&parsed, reason);
+        }
+    }
+
+    #[test]
+    fn output_group_names() {
+        let groups = [
+            (OutputGroup::Start, "start"),
+            (OutputGroup::StartCollapsed, "startCollapsed"),
+            (OutputGroup::End, "end"),
+        ];
+
+        for (group, name) in groups.iter() {
+            assert_eq!(serde_json::to_value(group).unwrap(), *name);
+            let parsed: OutputGroup = serde_json::from_value(serde_json::json!(name)).unwrap();
+            assert_eq!(&parsed, group);
+        }
+        assert!(serde_json::from_str::<OutputGroup>(r#""start_collapsed""#).is_err());
+    }
+
+    #[test]
+    fn output_event_round_trip() {
+        let output = OutputEvent {
+            category: Some(OutputCategory::Stdout),
+            output: "hello\n".to_string(),
+            group: Some(OutputGroup::StartCollapsed),
+            variables_reference: None,
+            source: None,
+            line: Some(4),
+            column: None,
+            data: None,
+        };
+
+        check_event(
+            EventKind::Output(output.clone()),
+            serde_json::json!({
+                "seq": 7,
+                "type": "event",
+                "event": "output",
+                "body": {
+                    "category": "stdout",
+                    "output": "hello\n",
+                    "group": "startCollapsed",
+                    "line": 4
+                }
+            }),
+            output,
+        );
+    }
+
+    #[test]
+    fn breakpoint_event_round_trip() {
+        let breakpoint = BreakpointEvent {
+            reason: BreakpointEventReason::Changed,
+            breakpoint: Breakpoint {
+                id: Some(2),
+                line: Some(12),
+                ..Breakpoint::new(true)
+            },
+        };
+
+        check_event(
+            EventKind::Breakpoint(breakpoint.clone()),
+            serde_json::json!({
+                "seq": 7,
+                "type": "event",
+                "event": "breakpoint",
+                "body": {
+                    "reason": "changed",
+                    "breakpoint": { "id": 2, "verified": true, "line": 12 }
+                }
+            }),
+            breakpoint,
+        );
+    }
+
+    #[test]
+    fn custom_event_round_trip() {
+        let body = serde_json::json!({ "nested": { "list": [1, null, "x"] }, "flag": false });
+
+        check_event(
+            EventKind::Other {
+                event: "headcrab/custom".to_string(),
+                body: Some(body.clone()),
+            },
+            serde_json::json!({
+                "seq": 7,
+                "type": "event",
+                "event": "headcrab/custom",
+                "body": body
+            }),
+            body,
+        );
+
+        let message = EventKind::Initialized.into_message(8).unwrap();
+        assert_eq!(
+            *message.body(),
+            serde_json::json!({ "seq": 8, "type": "event", "event": "initialized" })
+        );
+        assert_eq!(
+            serde_json::to_value(EventKind::Initialized).unwrap(),
+            serde_json::json!({ "event": "initialized" })
+        );
+    }
+
+    #[test]
+    fn terminated_event_restart() {
+        let terminated = TerminatedEvent {
+            restart: Some(serde_json::json!({ "port": 4711, "reason": "rebuild" })),
+        };
+
+        check_event(
+            EventKind::Terminated(terminated.clone()),
+            serde_json::json!({
+                "seq": 7,
+                "type": "event",
+                "event": "terminated",
+                "body": { "restart": { "port": 4711, "reason": "rebuild" } }
+            }),
+            terminated,
+        );
+    }
+
+    #[test]
+    fn progress_end_event_message() {
+        let end = ProgressEndEvent {
+            progress_id: "progress-12".to_string(),
+            message: Some("indexed 1024 symbols".to_string()),
+        };
+
+        check_event(
+            EventKind::ProgressEnd(end.clone()),
+            serde_json::json!({
+                "seq": 7,
+                "type": "event",
+                "event": "progressEnd",
+                "body": { "progressId": "progress-12", "message": "indexed 1024 symbols" }
+            }),
+            end,
+        );
+    }
+
+    #[test]
+    fn module_event_valid() {
+        let event = ModuleEvent {
+            reason: ModuleEventReason::New,
+            module: Module::new(1, "a.out"),
+        };
+
+        let value = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(
+            value,
+            serde_json::json!({ "reason": "new", "module": { "id": 1, "name": "a.out" } })
+        );
+        assert_eq!(serde_json::from_value::<ModuleEvent>(value).unwrap(), event);
+    }
+
+    #[test]
+    fn invalidated_event_round_trip() {
+        let text = r#"{"areas":["threads","customArea"],"threadId":4}"#;
+
+        let event: InvalidatedEvent = serde_json::from_str(text).unwrap();
+
+        assert_eq!(
+            event.areas,
+            Some(vec![
+                InvalidatedAreas::Threads,
+                InvalidatedAreas::Other("customArea".to_string())
+            ])
+        );
+        assert_eq!(event.thread_id, Some(4));
+        assert_eq!(event.stack_frame_id, None);
+        assert_eq!(serde_json::to_string(&event).unwrap(), text);
+    }
+
+    #[test]
+    fn memory_event_round_trip() {
+        let arguments = WriteMemoryArguments {
+            offset: Some(-8),
+            ..WriteMemoryArguments::new("0x7ffe1000", "kJCQkA==")
+        };
+        let memory = MemoryEvent::written(&arguments, 4);
+
+        check_event(
+            EventKind::Memory(memory.clone()),
+            serde_json::json!({
+                "seq": 7,
+                "type": "event",
+                "event": "memory",
+                "body": { "memoryReference": "0x7ffe1000", "offset": -8, "count": 4 }
+            }),
+            memory,
+        );
+
+        // a write without offset starts at the reference itself
+        let arguments = WriteMemoryArguments::new("0x7ffe1000", "kA==");
+        assert_eq!(MemoryEvent::written(&arguments, 1).offset, 0);
+    }
+
+    #[test]
+    fn progress_tracker() {
+        let (tracker, start) = ProgressTracker::start("indexing");
+        assert_eq!(start.progress_id, tracker.progress_id());
+        assert_eq!(
+            serde_json::to_value(&start).unwrap(),
+            serde_json::json!({ "progressId": tracker.progress_id(), "title": "indexing" })
+        );
+
+        let update = tracker.update(25.0).unwrap();
+        assert_eq!(update.progress_id, tracker.progress_id());
+        assert_eq!(update.percentage, Some(25.0));
+        assert!(tracker.update(25.0).is_ok());
+
+        assert!(matches!(tracker.update(10.0), Err(Error::InvalidMessage)));
+        assert!(matches!(tracker.update(100.5), Err(Error::InvalidMessage)));
+        assert!(matches!(
+            tracker.update(f32::NAN),
+            Err(Error::InvalidMessage)
+        ));
+        // a rejected update does not change the percentage
+        assert!(tracker.update(50.0).is_ok());
+
+        let progress_id = tracker.progress_id().to_string();
+        let end = tracker.end(Some("done"));
+        assert_eq!(
+            serde_json::to_value(&end).unwrap(),
+            serde_json::json!({ "progressId": progress_id, "message": "done" })
+        );
+    }
+
+    #[test]
+    fn progress_tracker_unique_id() {
+        let (first, _) = ProgressTracker::start("first");
+        let (second, _) = ProgressTracker::start("second");
+
+        assert_ne!(first.progress_id(), second.progress_id());
+    }
+}
@@ -0,0 +1,384 @@
+//! Handling the requests of a session through a trait, rather than by matching on messages.
+
+use std::convert::TryFrom;
+
+use serde::Serialize;
+
+use crate::request::*;
+use crate::response::*;
+use crate::{Adapter, Capabilities, Error, Message, MessageKind};
+
+/// The requests a debug adapter can answer, with a method per command.
+///
+/// Each method receive the request it handle, and return the body of its response. A error is
+/// answered with a error response, described by `Error::to_error_message`. Every method default
+/// to failing with `Error::InvalidMessage`, so a adapter only implement the requests it support.
+///
+/// The requests whose response body has no type yet return it as a json value.
+pub trait RequestHandler {
+    fn on_attach(&mut self, _request: AttachRequest) -> Result<(), Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_breakpoint_locations(
+        &mut self,
+        _request: BreakpointLocationsRequest,
+    ) -> Result<BreakpointLocationsResponseBody, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_completions(
+        &mut self,
+        _request: CompletionsRequest,
+    ) -> Result<CompletionsResponseBody, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_configuration_done(&mut self, _request: ConfigurationDoneRequest) -> Result<(), Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_continue(&mut self, _request: ContinueRequest) -> Result<ContinueResponseBody, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_data_breakpoint_info(
+        &mut self,
+        _request: DataBreakpointInfoRequest,
+    ) -> Result<DataBreakpointInfoResponseBody, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_disassemble(
+        &mut self,
+        _request: DisassembleRequest,
+    ) -> Result<DisassembleResponseBody, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_disconnect(&mut self, _request: DisconnectRequest) -> Result<(), Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_evaluate(&mut self, _request: EvaluateRequest) -> Result<serde_json::Value, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_goto_targets(
+        &mut self,
+        _request: GotoTargetsRequest,
+    ) -> Result<GotoTargetsResponseBody, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_initialize(&mut self, _request: InitializeRequest) -> Result<Capabilities, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_launch(&mut self, _request: LaunchRequest) -> Result<(), Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_loaded_sources(
+        &mut self,
+        _request: LoadedSourcesRequest,
+    ) -> Result<LoadedSourcesResponseBody, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_modules(&mut self, _request: ModulesRequest) -> Result<ModulesResponseBody, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_next(&mut self, _request: NextRequest) -> Result<(), Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_read_memory(
+        &mut self,
+        _request: ReadMemoryRequest,
+    ) -> Result<ReadMemoryResponseBody, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_restart(&mut self, _request: RestartRequest) -> Result<(), Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_reverse_continue(&mut self, _request: ReverseContinueRequest) -> Result<(), Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_set_breakpoints(
+        &mut self,
+        _request: SetBreakpointsRequest,
+    ) -> Result<serde_json::Value, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_set_exception_breakpoints(
+        &mut self,
+        _request: SetExceptionBreakpointsRequest,
+    ) -> Result<SetExceptionBreakpointsResponseBody, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_source(&mut self, _request: SourceRequest) -> Result<SourceResponseBody, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_stack_trace(&mut self, _request: StackTraceRequest) -> Result<serde_json::Value, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_step_back(&mut self, _request: StepBackRequest) -> Result<(), Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_step_in(&mut self, _request: StepInRequest) -> Result<(), Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_step_in_targets(
+        &mut self,
+        _request: StepInTargetsRequest,
+    ) -> Result<StepInTargetsResponseBody, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_step_out(&mut self, _request: StepOutRequest) -> Result<(), Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_terminate(&mut self, _request: TerminateRequest) -> Result<(), Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_terminate_threads(&mut self, _request: TerminateThreadsRequest) -> Result<(), Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_threads(&mut self, _request: ThreadsRequest) -> Result<ThreadsResponseBody, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_variables(&mut self, _request: VariablesRequest) -> Result<serde_json::Value, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    fn on_write_memory(
+        &mut self,
+        _request: WriteMemoryRequest,
+    ) -> Result<serde_json::Value, Error> {
+        Err(Error::InvalidMessage)
+    }
+
+    /// Handle a request whose command is not supported by this crate.
+    fn on_other(
+        &mut self,
+        _command: &str,
+        _arguments: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value, Error> {
+        Err(Error::InvalidMessage)
+    }
+}
+
+/// Read the requests of a session, pass them to a `RequestHandler`, and send back its responses.
+///
+/// Cancel requests are answered by the adapter itself, and so are not passed to the handler.
+/// The requests which are sent by a adapter to its client, like 'runInTerminal', are answered
+/// with a error response.
+pub struct Dispatcher<H> {
+    adapter: Adapter,
+    handler: H,
+}
+
+impl<H: RequestHandler> Dispatcher<H> {
+    pub fn new(adapter: Adapter, handler: H) -> Self {
+        Self { adapter, handler }
+    }
+
+    pub fn adapter(&self) -> &Adapter {
+        &self.adapter
+    }
+
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+
+    pub fn handler_mut(&mut self) -> &mut H {
+        &mut self.handler
+    }
+
+    /// Handle every request of the session, until it end.
+    ///
+    /// Return the error which ended the session, like any error yielded by `Adapter`.
+    pub fn run(&mut self) -> Result<(), Error> {
+        while let Some(message) = self.adapter.next() {
+            if let Some(MessageKind::Request(request)) = message?.message_kind() {
+                if let Some(response) = self.dispatch(request)? {
+                    self.adapter.send(&Message::try_from(response)?)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Pass `request` to the handler, returning the response to send, if any.
+    fn dispatch(&mut self, request: &Request) -> Result<Option<Response>, Error> {
+        let handler = &mut self.handler;
+
+        let body = match request.request_kind().clone() {
+            RequestKind::Cancel(_) => return Ok(None),
+            RequestKind::Attach(attach) => to_value(handler.on_attach(attach)),
+            RequestKind::BreakpointLocations(locations) => {
+                to_value(handler.on_breakpoint_locations(locations))
+            }
+            RequestKind::Completions(completions) => to_value(handler.on_completions(completions)),
+            RequestKind::ConfigurationDone(done) => to_value(handler.on_configuration_done(done)),
+            RequestKind::Continue(resume) => to_value(handler.on_continue(resume)),
+            RequestKind::DataBreakpointInfo(info) => {
+                to_value(handler.on_data_breakpoint_info(info))
+            }
+            RequestKind::Disassemble(disassemble) => to_value(handler.on_disassemble(disassemble)),
+            RequestKind::Disconnect(disconnect) => to_value(handler.on_disconnect(disconnect)),
+            RequestKind::Evaluate(evaluate) => to_value(handler.on_evaluate(evaluate)),
+            RequestKind::GotoTargets(goto) => to_value(handler.on_goto_targets(goto)),
+            RequestKind::Initialize(initialize) => to_value(handler.on_initialize(initialize)),
+            RequestKind::Launch(launch) => to_value(handler.on_launch(launch)),
+            RequestKind::LoadedSources(loaded) => to_value(handler.on_loaded_sources(loaded)),
+            RequestKind::Modules(modules) => to_value(handler.on_modules(modules)),
+            RequestKind::Next(next) => to_value(handler.on_next(next)),
+            RequestKind::ReadMemory(read) => to_value(handler.on_read_memory(read)),
+            RequestKind::Restart(restart) => to_value(handler.on_restart(restart)),
+            RequestKind::ReverseContinue(resume) => to_value(handler.on_reverse_continue(resume)),
+            RequestKind::SetBreakpoints(set) => to_value(handler.on_set_breakpoints(set)),
+            RequestKind::SetExceptionBreakpoints(set) => {
+                to_value(handler.on_set_exception_breakpoints(set))
+            }
+            RequestKind::Source(source) => to_value(handler.on_source(source)),
+            RequestKind::StackTrace(stack_trace) => to_value(handler.on_stack_trace(stack_trace)),
+            RequestKind::StepBack(step) => to_value(handler.on_step_back(step)),
+            RequestKind::StepIn(step) => to_value(handler.on_step_in(step)),
+            RequestKind::StepInTargets(targets) => to_value(handler.on_step_in_targets(targets)),
+            RequestKind::StepOut(step) => to_value(handler.on_step_out(step)),
+            RequestKind::Terminate(terminate) => to_value(handler.on_terminate(terminate)),
+            RequestKind::TerminateThreads(terminate) => {
+                to_value(handler.on_terminate_threads(terminate))
+            }
+            RequestKind::Threads(threads) => to_value(handler.on_threads(threads)),
+            RequestKind::Variables(variables) => to_value(handler.on_variables(variables)),
+            RequestKind::WriteMemory(write) => to_value(handler.on_write_memory(write)),
+            RequestKind::Other { command, arguments } => {
+                to_value(handler.on_other(&command, arguments))
+            }
+            // these requests are sent by the adapter, not by the client
+            RequestKind::RunInTerminal(_) | RequestKind::StartDebugging(_) => {
+                Err(Error::InvalidMessage)
+            }
+        };
+
+        let seq = self.adapter.next_seq();
+        let response = match body {
+            Ok(body) => Response::for_request(seq, request, body)?,
+            Err(err) => Response::error_for_request(seq, request, err.to_error_message())?,
+        };
+        Ok(Some(response))
+    }
+}
+
+/// Serialize the body returned by a handler.
+fn to_value<T: Serialize>(body: Result<T, Error>) -> Result<serde_json::Value, Error> {
+    Ok(serde_json::to_value(body?)?)
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+
+    use crate::test_util::SharedOutput;
+    use crate::Thread;
+
+    fn frame(body: &str) -> String {
+        format!("Content-Length:{}\r\n\r\n{}", body.len(), body)
+    }
+
+    #[derive(Default)]
+    struct Handler {
+        initialized: bool,
+    }
+
+    impl RequestHandler for Handler {
+        fn on_initialize(&mut self, request: InitializeRequest) -> Result<Capabilities, Error> {
+            assert_eq!(request.arguments().adapter_id, "headcrab");
+            self.initialized = true;
+            Ok(Capabilities::builder()
+                .supports_configuration_done_request(true)
+                .build())
+        }
+
+        fn on_configuration_done(
+            &mut self,
+            _request: ConfigurationDoneRequest,
+        ) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn on_threads(&mut self, _request: ThreadsRequest) -> Result<ThreadsResponseBody, Error> {
+            Ok(ThreadsResponseBody {
+                threads: vec![Thread::new(1, "main")],
+            })
+        }
+    }
+
+    #[test]
+    fn dispatch_requests() {
+        let input = [
+            frame(r#"{ "seq": 1, "type": "request", "command": "initialize", "arguments": { "adapterID": "headcrab" } }"#),
+            frame(r#"{ "seq": 2, "type": "request", "command": "configurationDone" }"#),
+            frame(r#"{ "seq": 3, "type": "request", "command": "threads" }"#),
+            frame(r#"{ "seq": 4, "type": "request", "command": "custom" }"#),
+            frame(r#"{ "seq": 5, "type": "request", "command": "cancel", "arguments": { "requestId": 4 } }"#),
+        ]
+        .concat();
+        let output = SharedOutput::default();
+        let adapter = Adapter::with_reader_writer(Cursor::new(input), output.clone());
+
+        let mut dispatcher = Dispatcher::new(adapter, Handler::default());
+        // the session end with the input
+        dispatcher.run().unwrap();
+        assert!(dispatcher.handler().initialized);
+
+        let mut responses: Vec<_> = output
+            .messages()
+            .into_iter()
+            .map(|message| Response::try_from(message).unwrap())
+            .collect();
+
+        // the cancel request is answered by the adapter, so its response may come at any point
+        responses.retain(|response| response.command() != "cancel");
+        assert_eq!(responses.len(), 4);
+
+        assert_eq!(responses[0].request_seq(), 1);
+        assert_eq!(
+            responses[0].body(),
+            Some(&serde_json::json!({ "supportsConfigurationDoneRequest": true }))
+        );
+
+        assert_eq!(responses[1].command(), "configurationDone");
+        assert!(responses[1].success());
+        assert_eq!(responses[1].body(), None);
+
+        assert_eq!(
+            responses[2].body(),
+            Some(&serde_json::json!({ "threads": [{ "id": 1, "name": "main" }] }))
+        );
+
+        // a request the handler does not support is answered with a error
+        assert_eq!(responses[3].command(), "custom");
+        assert!(!responses[3].success());
+        assert_eq!(responses[3].message(), Some("invalid message"));
+    }
+}
@@ -0,0 +1,939 @@
+use std::convert::TryFrom;
+use std::io;
+use std::io::{BufRead, Read, Write};
+
+use crate::Error;
+
+/// How strictly the end of the lines of a header are checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    /// Only accept lines ending with `\r\n`, as required by the specification.
+    Strict,
+    /// Also accept lines ending with a bare `\n`, as sent by some hand-written clients.
+    #[default]
+    Lenient,
+}
+
+impl LineEnding {
+    /// Remove the line ending of `line`, returning `None` if it is not accepted.
+    fn strip<'a>(&self, line: &'a str) -> Option<&'a str> {
+        let line = line.strip_suffix('\n')?;
+        match (line.strip_suffix('\r'), self) {
+            (Some(line), _) => Some(line),
+            (None, LineEnding::Lenient) => Some(line),
+            (None, LineEnding::Strict) => None,
+        }
+    }
+}
+
+/// The most fields a header may have.
+const MAX_FIELDS: usize = 64;
+/// The longest a header may be, in bytes, empty line included.
+pub(crate) const MAX_HEADER_BYTES: usize = 8 * 1024;
+
+#[derive(Debug, Clone)]
+/// A dap message header.
+/// In the current, version of dap, a Header can only contain one field : `Content-Length`.
+/// That being say, the standard was design to make it possible for a future version to add field.
+/// As such, This type support header which contain unknown fields.
+pub struct Header {
+    /// "The length of the content part in bytes"
+    pub content_length: usize,
+    /// The list of the header field, both know and unknown.
+    pub fields: Vec<HeaderField>,
+    /// The lines of the header as they were read, written back as is by `write_to`.
+    read_lines: Option<ReadLines>,
+}
+
+/// The lines of a header which was read, along with the field each line was parsed into.
+#[derive(Debug, Clone)]
+struct ReadLines {
+    fields: Vec<(HeaderField, String)>,
+    /// The empty line ending the header.
+    end: String,
+}
+
+impl Header {
+    /// Create a header containing only the `Content-Length` field.
+    pub fn new(content_length: usize) -> Self {
+        Self {
+            content_length,
+            fields: vec![HeaderField::ContentLength(content_length)],
+            read_lines: None,
+        }
+    }
+
+    /// Create a header with a `Content-Length` field, followed by the other `fields`.
+    ///
+    /// Any `Content-Length` field of `fields` is dropped, so that the header announce `content_length`.
+    pub fn with_fields(content_length: usize, fields: Vec<HeaderField>) -> Self {
+        let mut header = Self::new(content_length);
+        header.fields.extend(
+            fields
+                .into_iter()
+                .filter(|field| field.content_length().is_none()),
+        );
+        header
+    }
+
+    /// Announce a content of `content_length` bytes, for example after the content was modified.
+    ///
+    /// Every `Content-Length` field is updated in place, so the other fields keep their order.
+    pub fn set_content_length(&mut self, content_length: usize) {
+        self.content_length = content_length;
+        for field in self.fields.iter_mut() {
+            match field {
+                HeaderField::ContentLength(length) => *length = content_length,
+                HeaderField::Other { name, value }
+                    if name.eq_ignore_ascii_case("Content-Length") =>
+                {
+                    *value = content_length.to_string()
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Return the header as written by `write_to`, empty line included.
+    pub fn into_string(self) -> String {
+        let mut output = Vec::new();
+        self.write_to(&mut output)
+            .expect("writing to a vector never fail");
+        String::from_utf8(output).expect("the fields of a header are strings")
+    }
+
+    /// Take a list of `HeaderField` and return Header if the list of field contain a `Content-Length`.
+    ///
+    /// Several `Content-Length` fields are accepted only if they all announce the same length,
+    /// otherwise the length of the content would be ambiguous.
+    fn from_raw_fields(fields: Vec<HeaderField>) -> Result<Self, Error> {
+        let mut lengths = fields.iter().filter_map(HeaderField::content_length);
+
+        let content_length = lengths.next().ok_or(Error::Invalid)?;
+        if lengths.any(|length| length != content_length) {
+            return Err(Error::Invalid);
+        }
+
+        Ok(Self {
+            content_length,
+            fields,
+            read_lines: None,
+        })
+    }
+
+    /// Read a header, returning `Error::Eof` if the input ended before the header started.
+    ///
+    /// A header with more than 64 fields, or longer than 8 KiB, is rejected with `Error::Invalid`.
+    pub fn from_input<R: BufRead>(input: &mut R) -> Result<Header, Error> {
+        Header::from_input_with(input, LineEnding::default())
+    }
+
+    /// Like `from_input`, checking the end of the lines according to `line_ending`.
+    pub fn from_input_with<R: BufRead>(
+        input: &mut R,
+        line_ending: LineEnding,
+    ) -> Result<Header, Error> {
+        // a input closed between two messages is not a malformed header
+        if input.fill_buf()?.is_empty() {
+            return Err(Error::Eof);
+        }
+
+        let mut input = input.take(MAX_HEADER_BYTES as u64);
+        let mut lines = Vec::new();
+
+        let end = loop {
+            let line = match read_line(&mut input) {
+                Ok(line) => line,
+                // the line was cut by the size limit, rather than by the end of the input
+                Err(_) if input.limit() == 0 => return Err(Error::Invalid),
+                Err(err) => return Err(err),
+            };
+
+            match HeaderField::from_line(&line, line_ending)? {
+                Some(_) if lines.len() == MAX_FIELDS => return Err(Error::Invalid),
+                Some(field) => lines.push((field, line)),
+                // a empty line signify the end of the header
+                None => break line,
+            }
+        };
+
+        let fields = lines.iter().map(|(field, _)| field.clone()).collect();
+        let mut header = Header::from_raw_fields(fields)?;
+        header.read_lines = Some(ReadLines { fields: lines, end });
+        Ok(header)
+    }
+
+    /// Check that the content announced by the header is at most `max_body_bytes` long.
+    ///
+    /// This should be checked before allocating the buffer of a message read from a untrusted input.
+    pub fn validate(&self, max_body_bytes: usize) -> Result<(), Error> {
+        if self.content_length > max_body_bytes {
+            return Err(Error::MessageTooLarge {
+                actual: self.content_length,
+                limit: max_body_bytes,
+            });
+        }
+        Ok(())
+    }
+
+    /// Write every field of the header, followed by the empty line which separate the header from the content.
+    ///
+    /// The fields of a header which was read are written as they were read, spacing and casing
+    /// included, unless they were modified since, for example by `set_content_length`.
+    pub fn write_to<W: Write>(&self, output: &mut W) -> Result<(), Error> {
+        let read_lines = self.read_lines.as_ref();
+        for (index, field) in self.fields.iter().enumerate() {
+            match read_lines.and_then(|lines| lines.fields.get(index)) {
+                Some((read, line)) if read == field => output.write_all(line.as_bytes())?,
+                _ => field.write_to(output)?,
+            }
+        }
+        match read_lines {
+            Some(lines) => output.write_all(lines.end.as_bytes())?,
+            None => write!(output, "\r\n")?,
+        }
+        Ok(())
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+/// A dap message header field.
+pub enum HeaderField {
+    /// "The length of the content part in bytes"
+    ContentLength(usize),
+    /// The type of the content, reserved by the specification for future use, and sent by some
+    /// clients sharing their transport with the language server protocol.
+    ///
+    /// Only the utf-8 charset is supported. The other parameters are kept as written, such as
+    /// `boundary=x`.
+    ContentType {
+        mime: String,
+        charset: Option<String>,
+        parameters: Vec<String>,
+    },
+    /// a unknown field
+    Other { name: String, value: String },
+}
+
+impl HeaderField {
+    /// Field names are case-insensitive, so a `Content-Length` or `Content-Type` field written with
+    /// a unusual casing (`content-length`, `CONTENT-TYPE`, ...) is kept as a `Other` field,
+    /// preserving its name, once its value has been checked.
+    fn specialize(self) -> Result<Self, Error> {
+        match self {
+            HeaderField::Other { name, value } if name == "Content-Length" => {
+                let length = parse_length(&value).ok_or(Error::Invalid)?;
+                Ok(HeaderField::ContentLength(length))
+            }
+            HeaderField::Other {
+                ref name,
+                ref value,
+            } if name.eq_ignore_ascii_case("Content-Length") => {
+                parse_length(value).ok_or(Error::Invalid)?;
+                Ok(self)
+            }
+            HeaderField::Other { name, value } if name == "Content-Type" => {
+                parse_content_type(&value)
+            }
+            HeaderField::Other {
+                ref name,
+                ref value,
+            } if name.eq_ignore_ascii_case("Content-Type") => {
+                parse_content_type(value)?;
+                Ok(self)
+            }
+            _ => Ok(self),
+        }
+    }
+
+    /// Return the length announced by the field, if it is a `Content-Length` field, whatever its casing.
+    fn content_length(&self) -> Option<usize> {
+        match self {
+            HeaderField::ContentLength(length) => Some(*length),
+            HeaderField::Other { name, value } if name.eq_ignore_ascii_case("Content-Length") => {
+                parse_length(value)
+            }
+            HeaderField::ContentType { .. } | HeaderField::Other { .. } => None,
+        }
+    }
+
+    fn write_to<W: Write>(&self, output: &mut W) -> Result<(), Error> {
+        match self {
+            HeaderField::ContentLength(length) => write!(output, "Content-Length: {}\r\n", length)?,
+            HeaderField::ContentType {
+                mime,
+                charset,
+                parameters,
+            } => {
+                write!(output, "Content-Type: {}", mime)?;
+                if let Some(charset) = charset {
+                    write!(output, "; charset={}", charset)?;
+                }
+                for parameter in parameters {
+                    write!(output, "; {}", parameter)?;
+                }
+                write!(output, "\r\n")?
+            }
+            HeaderField::Other { name, value } => write!(output, "{}: {}\r\n", name, value)?,
+        }
+        Ok(())
+    }
+
+    /// Parse a line of a header, line ending included, returning `None` for the empty line.
+    fn from_line(line: &str, line_ending: LineEnding) -> Result<Option<HeaderField>, Error> {
+        let line = line_ending.strip(line).ok_or(Error::Invalid)?;
+        // a carriage return is only valid as part of the line ending
+        if line.contains('\r') {
+            return Err(Error::Invalid);
+        }
+
+        // if the line is empty: return None
+        if line.is_empty() {
+            return Ok(None);
+        }
+
+        // a header field is compose of a name and a value separated by the first ':',
+        // the value may itself contain ':' (an url, a time, ...)
+        let mut parts = line
+            .splitn(2, ':')
+            .map(|part| part.trim_matches(|c| c == ' ' || c == '\t'));
+        match (parts.next(), parts.next()) {
+            // a empty value is still a value, only the name is mandatory
+            (Some(name), Some(value)) if !name.is_empty() => {
+                let header = HeaderField::Other {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                }
+                .specialize()?;
+                Ok(Some(header))
+            }
+            _ => Err(Error::Invalid),
+        }
+    }
+}
+
+/// Search the empty line ending the header at the start of `bytes`, from the line starting at
+/// `from`, for the readers which can not read the header with `Header::from_input`.
+///
+/// Return the length of the header, empty line included, or the start of the line the search
+/// should resume from once more bytes were read. Like `LineEnding::Lenient`, the empty line may be
+/// a bare `\n`.
+#[cfg(any(feature = "tokio-client", feature = "tokio-codec", test))]
+pub(crate) fn find_header_end(bytes: &[u8], from: usize) -> Result<usize, usize> {
+    let mut start = from;
+    while let Some(end) = bytes[start..].iter().position(|byte| *byte == b'\n') {
+        let end = start + end + 1;
+        if let b"\r\n" | b"\n" = &bytes[start..end] {
+            return Ok(end);
+        }
+        start = end;
+    }
+    Err(start)
+}
+
+/// Read a line of a header, line ending included.
+fn read_line<R: BufRead>(input: &mut R) -> Result<String, Error> {
+    let mut line = String::new();
+    // a line without its line feed was cut by the end of the input
+    if input.read_line(&mut line)? == 0 || !line.ends_with('\n') {
+        // unlike a empty line, the end of the input can not end a header
+        return Err(Error::Io(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "the input ended in a message header",
+        )));
+    }
+    Ok(line)
+}
+
+/// Parse the value of a `Content-Length` field.
+///
+/// The length is parsed as a `u64`, the widest length a client may send, so a length which does not
+/// fit in a `usize` on a 32 bits target is rejected rather than parsed as a other number.
+fn parse_length(value: &str) -> Option<usize> {
+    let length: u64 = value.parse().ok()?;
+    usize::try_from(length).ok()
+}
+
+/// Parse the value of a `Content-Type` field, such as `application/vscode-jsonrpc; charset=utf-8`.
+///
+/// The content is json, so a charset other than utf-8 is rejected with `Error::InvalidMessage`.
+fn parse_content_type(value: &str) -> Result<HeaderField, Error> {
+    let mut parts = value.split(';').map(str::trim);
+    let mime = parts.next().filter(|mime| !mime.is_empty());
+    let mime = mime.ok_or(Error::Invalid)?.to_string();
+
+    let mut charset = None;
+    let mut parameters = Vec::new();
+    for parameter in parts.filter(|parameter| !parameter.is_empty()) {
+        match parameter.split_once('=') {
+            Some((name, value)) if name.trim().eq_ignore_ascii_case("charset") => {
+                let value = value.trim().trim_matches('"');
+                if !value.eq_ignore_ascii_case("utf-8") && !value.eq_ignore_ascii_case("utf8") {
+                    return Err(Error::InvalidMessage);
+                }
+                charset = Some(value.to_string());
+            }
+            _ => parameters.push(parameter.to_string()),
+        }
+    }
+
+    Ok(HeaderField::ContentType {
+        mime,
+        charset,
+        parameters,
+    })
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+    use bstr::B;
+    use proptest::prelude::*;
+
+    #[test]
+    fn parse_header_field_valid_content_length() {
+        let header = HeaderField::from_line("Content-Length:6\r\n", LineEnding::Lenient)
+            .unwrap()
+            .unwrap();
+        match header {
+            HeaderField::ContentLength(6) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn parse_header_field_content_length_any_case() {
+        for name in [
+            "CONTENT-LENGTH",
+            "content-length",
+            "Content-length",
+            "cOnTeNt-LeNgTh",
+        ]
+        .iter()
+        {
+            let input = format!("{}: 6\r\n\r\n", name);
+            let header = Header::from_input(&mut input.as_bytes()).unwrap();
+
+            assert_eq!(header.content_length, 6);
+            // the name is kept as received
+            assert_eq!(
+                header.fields,
+                vec![HeaderField::Other {
+                    name: name.to_string(),
+                    value: "6".to_string()
+                }]
+            );
+            assert_eq!(header.into_string(), input);
+        }
+
+        match HeaderField::from_line("content-length: six\r\n", LineEnding::Lenient) {
+            Err(Error::Invalid) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn parse_header_field_content_length_overflow() {
+        for value in ["18446744073709551616", "-1", "1e3", "0x10"].iter() {
+            let input = format!("Content-Length: {}\r\n", value);
+            match HeaderField::from_line(&input, LineEnding::Lenient) {
+                Err(Error::Invalid) => (),
+                _ => panic!("{:?} should be invalid", value),
+            }
+        }
+
+        assert_eq!(
+            parse_length("18446744073709551615"),
+            usize::try_from(u64::MAX).ok()
+        );
+    }
+
+    #[test]
+    fn parse_header_field_content_type() {
+        let read = |line: &str| HeaderField::from_line(line, LineEnding::Lenient);
+        let content_type = |charset: Option<&str>, parameters: &[&str]| HeaderField::ContentType {
+            mime: "application/vscode-jsonrpc".to_string(),
+            charset: charset.map(str::to_string),
+            parameters: parameters.iter().map(|p| p.to_string()).collect(),
+        };
+
+        let cases = [
+            (
+                "Content-Type: application/vscode-jsonrpc; charset=utf-8\r\n",
+                content_type(Some("utf-8"), &[]),
+            ),
+            (
+                "Content-Type: application/vscode-jsonrpc\r\n",
+                content_type(None, &[]),
+            ),
+            (
+                "Content-Type: application/vscode-jsonrpc;charset=\"UTF8\"; version=2 \r\n",
+                content_type(Some("UTF8"), &["version=2"]),
+            ),
+        ];
+        for (line, expected) in cases.iter() {
+            let field = read(line).unwrap().unwrap();
+            assert_eq!(field, *expected);
+
+            // the field is written back in its canonical form, and read back unchanged
+            let mut output = Vec::new();
+            field.write_to(&mut output).unwrap();
+            assert_eq!(
+                read(std::str::from_utf8(&output).unwrap())
+                    .unwrap()
+                    .unwrap(),
+                field
+            );
+        }
+        let mut output = Vec::new();
+        cases[0].1.write_to(&mut output).unwrap();
+        assert_eq!(output, cases[0].0.as_bytes());
+
+        for line in [
+            "Content-Type: application/vscode-jsonrpc; charset=latin1\r\n",
+            "content-type: application/vscode-jsonrpc; charset=utf-16\r\n",
+        ]
+        .iter()
+        {
+            match read(line) {
+                Err(Error::InvalidMessage) => (),
+                _ => panic!("{:?} should be invalid", line),
+            }
+        }
+        match read("Content-Type: ; charset=utf-8\r\n") {
+            Err(Error::Invalid) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn parse_header_field_valid_unknown_field() {
+        let field = HeaderField::from_line("name:value\r\n", LineEnding::Lenient)
+            .unwrap()
+            .unwrap();
+        match field {
+            HeaderField::Other { name, value } => {
+                assert_eq!(name, "name");
+                assert_eq!(value, "value");
+            }
+            _ => {
+                panic!()
+            }
+        }
+    }
+
+    #[test]
+    fn parse_header_field_empty_line() {
+        let none = HeaderField::from_line("\r\n", LineEnding::Lenient).unwrap();
+        assert_eq!(none, None);
+    }
+
+    #[test]
+    fn parse_header_field_name_only() {
+        let field = HeaderField::from_line("name:\r\n", LineEnding::Lenient).unwrap();
+        assert_eq!(
+            field,
+            Some(HeaderField::Other {
+                name: "name".to_string(),
+                value: String::new()
+            })
+        );
+    }
+
+    #[test]
+    fn parse_header_field_invalid() {
+        for line in [":value\r\n", "  : value\r\n", "name value\r\n"].iter() {
+            match HeaderField::from_line(line, LineEnding::Lenient) {
+                Err(Error::Invalid) => (),
+                _ => panic!("{:?} should be invalid", line),
+            }
+        }
+    }
+
+    #[test]
+    fn parse_header_field_value_with_colons() {
+        let cases = [
+            (
+                "X-Debug-Origin: vscode://ms-vscode.debugger\r\n",
+                "X-Debug-Origin",
+                "vscode://ms-vscode.debugger",
+            ),
+            (
+                "X-Sent-At:2021-03-04T12:34:56Z\r\n",
+                "X-Sent-At",
+                "2021-03-04T12:34:56Z",
+            ),
+            (
+                "  X-Proxy  :   http://localhost:4711/  \r\n",
+                "X-Proxy",
+                "http://localhost:4711/",
+            ),
+            ("X-Separator: :\r\n", "X-Separator", ":"),
+        ];
+        for (line, name, value) in cases.iter() {
+            let field = HeaderField::from_line(line, LineEnding::Lenient)
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                field,
+                HeaderField::Other {
+                    name: name.to_string(),
+                    value: value.to_string()
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn parse_header_empty_input() {
+        match Header::from_input(&mut B("")) {
+            Err(Error::Eof) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn parse_header_end_of_input() {
+        match Header::from_input(&mut B("Content-Length:415\r\n")) {
+            Err(Error::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn validate_header() {
+        let header = Header::new(1024);
+
+        assert!(header.validate(1024).is_ok());
+        match header.validate(1023) {
+            Err(Error::MessageTooLarge {
+                actual: 1024,
+                limit: 1023,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn parse_header_valid_header() {
+        let header = Header::from_input(&mut B("Content-Length:415\r\n\r\n")).unwrap();
+
+        assert_eq!(header.content_length, 415);
+
+        assert_eq!(header.fields[0], HeaderField::ContentLength(415));
+        assert_eq!(header.fields.get(1), None)
+    }
+
+    #[test]
+    fn parse_header_valid_header_with_unknown_field() {
+        let header =
+            Header::from_input(&mut B("Content-Length:360\r\nOther-Field:value\r\n\r\n")).unwrap();
+
+        assert_eq!(header.fields.len(), 2);
+        assert_eq!(header.content_length, 360);
+        assert_eq!(
+            header.fields.first(),
+            Some(&HeaderField::ContentLength(360))
+        );
+        assert_eq!(
+            header.fields.get(1),
+            Some(&HeaderField::Other {
+                name: "Other-Field".to_string(),
+                value: "value".to_string()
+            })
+        );
+        assert_eq!(header.fields.get(2), None);
+    }
+
+    #[test]
+    fn parse_header_line_endings() {
+        let read =
+            |input: &str, line_ending| Header::from_input_with(&mut input.as_bytes(), line_ending);
+
+        // both modes accept the line ending required by the specification
+        for &line_ending in [LineEnding::Strict, LineEnding::Lenient].iter() {
+            let header = read("Content-Length: 2\r\nX-Name:  a b \r\n\r\n", line_ending).unwrap();
+            assert_eq!(header.content_length, 2);
+            assert_eq!(
+                header.fields[1],
+                HeaderField::Other {
+                    name: "X-Name".to_string(),
+                    value: "a b".to_string()
+                }
+            );
+        }
+
+        // a bare line feed, either on a field or on the empty line ending the header
+        for input in [
+            "Content-Length: 2\n\n",
+            "Content-Length: 2\r\n\n",
+            "Content-Length: 2\n\r\n",
+        ]
+        .iter()
+        {
+            assert_eq!(read(input, LineEnding::Lenient).unwrap().content_length, 2);
+            match read(input, LineEnding::Strict) {
+                Err(Error::Invalid) => (),
+                _ => panic!("{:?} should be invalid", input),
+            }
+        }
+
+        // a carriage return alone never end a line
+        for &line_ending in [LineEnding::Strict, LineEnding::Lenient].iter() {
+            for input in [
+                "Content-Length: 2\r\r\n\r\n",
+                "Content-Length: 2\rX: y\r\n\r\n",
+            ]
+            .iter()
+            {
+                match read(input, line_ending) {
+                    Err(Error::Invalid) => (),
+                    _ => panic!("{:?} should be invalid", input),
+                }
+            }
+            match read("Content-Length: 2\r", line_ending) {
+                Err(Error::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof),
+                _ => panic!(),
+            }
+        }
+    }
+
+    #[test]
+    fn find_header_end_line_endings() {
+        for input in [
+            "Content-Length: 2\r\n\r\n",
+            "Content-Length: 2\n\n",
+            "Content-Length: 2\r\n\n",
+            "Content-Length: 2\n\r\n",
+        ]
+        .iter()
+        {
+            let bytes = format!("{}{{}}", input);
+            assert_eq!(find_header_end(bytes.as_bytes(), 0), Ok(input.len()));
+        }
+
+        // the search resume from the start of the unfinished line
+        let bytes = b"Content-Length: 2\r\nX: y\r";
+        assert_eq!(find_header_end(bytes, 0), Err(19));
+        assert_eq!(find_header_end(b"Content-Length: 2\r\n\r", 19), Err(19));
+        assert_eq!(find_header_end(b"Content-Length: 2\r\n\r\n", 19), Ok(21));
+    }
+
+    #[test]
+    fn parse_header_duplicate_content_length() {
+        let header =
+            Header::from_input(&mut B("Content-Length:12\r\ncontent-length: 12\r\n\r\n")).unwrap();
+        assert_eq!(header.content_length, 12);
+        assert_eq!(header.fields.len(), 2);
+
+        for input in [
+            "Content-Length:12\r\nContent-Length:13\r\n\r\n",
+            "Content-Length:12\r\nCONTENT-LENGTH:120\r\n\r\n",
+        ]
+        .iter()
+        {
+            match Header::from_input(&mut input.as_bytes()) {
+                Err(Error::Invalid) => (),
+                _ => panic!("{:?} should be invalid", input),
+            }
+        }
+    }
+
+    #[test]
+    fn parse_header_too_many_fields() {
+        let field = "X-Field: value\r\n";
+        let input = format!("Content-Length:2\r\n{}\r\n", field.repeat(MAX_FIELDS - 1));
+        assert_eq!(
+            Header::from_input(&mut input.as_bytes())
+                .unwrap()
+                .fields
+                .len(),
+            MAX_FIELDS
+        );
+
+        let input = format!("Content-Length:2\r\n{}\r\n", field.repeat(MAX_FIELDS));
+        match Header::from_input(&mut input.as_bytes()) {
+            Err(Error::Invalid) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn parse_header_too_long() {
+        // a single line which never end
+        let input = "X".repeat(MAX_HEADER_BYTES * 2);
+        match Header::from_input(&mut input.as_bytes()) {
+            Err(Error::Invalid) => (),
+            _ => panic!(),
+        }
+
+        let value = "x".repeat(MAX_HEADER_BYTES / 2);
+        let input = format!("Content-Length:2\r\nA:{}\r\nB:{}\r\n\r\n", value, value);
+        match Header::from_input(&mut input.as_bytes()) {
+            Err(Error::Invalid) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn from_raw_fields_valid() {
+        let header = Header::from_raw_fields(vec![HeaderField::ContentLength(1)]).unwrap();
+
+        assert_eq!(header.content_length, 1);
+        assert_eq!(header.fields.first(), Some(&HeaderField::ContentLength(1)));
+        assert_eq!(header.fields.get(1), None);
+    }
+
+    #[test]
+    fn from_raw_fields_valid_with_unknown_field() {
+        let header = Header::from_raw_fields(vec![
+            HeaderField::Other {
+                name: "name".to_string(),
+                value: "value".to_string(),
+            },
+            HeaderField::ContentLength(1),
+        ])
+        .unwrap();
+
+        assert_eq!(header.content_length, 1);
+        assert_eq!(
+            header.fields.first(),
+            Some(&HeaderField::Other {
+                name: "name".to_string(),
+                value: "value".to_string()
+            })
+        );
+        assert_eq!(header.fields.get(1), Some(&HeaderField::ContentLength(1)));
+        assert_eq!(header.fields.get(2), None);
+    }
+
+    #[test]
+    fn write_header_valid() {
+        let mut output = Vec::new();
+        Header::new(12).write_to(&mut output).unwrap();
+
+        assert_eq!(output, b"Content-Length: 12\r\n\r\n");
+    }
+
+    #[test]
+    fn header_into_string() {
+        assert_eq!(Header::new(0).into_string(), "Content-Length: 0\r\n\r\n");
+
+        let header = Header::with_fields(
+            42,
+            vec![
+                HeaderField::Other {
+                    name: "Content-Type".to_string(),
+                    value: "application/vscode-jsonrpc".to_string(),
+                },
+                HeaderField::ContentLength(7),
+            ],
+        );
+        assert_eq!(
+            header.into_string(),
+            "Content-Length: 42\r\nContent-Type: application/vscode-jsonrpc\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn proxy_header_round_trip() {
+        let input = "X-Trace: 4f2a\r\nContent-Length: 42\r\nContent-Type: application/vscode-jsonrpc; charset=utf-8\r\nX-Sent-At: 12:34:56\r\n\r\n";
+
+        // the fields are written back verbatim, in the order they were read
+        let mut header = Header::from_input(&mut input.as_bytes()).unwrap();
+        assert_eq!(header.clone().into_string(), input);
+        let read_back = Header::from_input(&mut header.clone().into_string().as_bytes()).unwrap();
+        assert_eq!(read_back.fields, header.fields);
+
+        // the length is updated in place
+        header.set_content_length(7);
+        assert_eq!(header.content_length, 7);
+        assert_eq!(header.into_string(), input.replace("42", "7"));
+
+        let mut header = Header::from_input(&mut B("content-length: 42\r\nX: y\r\n\r\n")).unwrap();
+        header.set_content_length(1024);
+        assert_eq!(header.content_length, 1024);
+        assert_eq!(header.into_string(), "content-length: 1024\r\nX: y\r\n\r\n");
+    }
+
+    #[test]
+    fn proxy_header_round_trip_non_canonical() {
+        let input = "Content-Length:12\r\nX-Foo:bar\r\n  content-TYPE :application/json;CHARSET=\"UTF-8\" ;  v=1\t\r\nX-Empty:\n\n";
+
+        let mut header = Header::from_input(&mut input.as_bytes()).unwrap();
+        assert_eq!(header.clone().into_string(), input);
+
+        // only the modified field is written in its canonical form
+        header.set_content_length(7);
+        assert_eq!(
+            header.clone().into_string(),
+            input.replace("Content-Length:12", "Content-Length: 7")
+        );
+
+        // a field set by the caller replace the line it was read from
+        header.fields[1] = HeaderField::Other {
+            name: "X-Foo".to_string(),
+            value: "baz".to_string(),
+        };
+        header.fields.push(HeaderField::Other {
+            name: "X-New".to_string(),
+            value: "1".to_string(),
+        });
+        assert_eq!(
+            header.into_string(),
+            "Content-Length: 7\r\nX-Foo: baz\r\n  content-TYPE :application/json;CHARSET=\"UTF-8\" ;  v=1\t\r\nX-Empty:\nX-New: 1\r\n\n"
+        );
+    }
+
+    #[test]
+    fn write_then_read_header() {
+        let checksum = HeaderField::Other {
+            name: "X-Checksum".to_string(),
+            value: "abc123".to_string(),
+        };
+
+        for header in [Header::new(1024), Header::with_fields(7, vec![checksum])].iter() {
+            let mut output = Vec::new();
+            header.write_to(&mut output).unwrap();
+            let read_back = Header::from_input(&mut output.as_slice()).unwrap();
+
+            assert_eq!(read_back.content_length, header.content_length);
+            assert_eq!(read_back.fields, header.fields);
+        }
+    }
+
+    fn other_field() -> impl Strategy<Value = HeaderField> {
+        ("[A-Za-z][A-Za-z0-9-]{0,15}", "[A-Za-z0-9_./=:-]{1,20}")
+            .prop_filter("must not be a Content-Length", |(name, _)| {
+                !name.eq_ignore_ascii_case("Content-Length")
+                    && !name.eq_ignore_ascii_case("Content-Type")
+            })
+            .prop_map(|(name, value)| HeaderField::Other { name, value })
+    }
+
+    proptest! {
+        #[test]
+        fn header_round_trip(
+            content_length in any::<usize>(),
+            others in prop::collection::vec(other_field(), 0..4),
+            position in any::<prop::sample::Index>(),
+        ) {
+            let mut fields = others;
+            let position = position.index(fields.len() + 1);
+            fields.insert(position, HeaderField::ContentLength(content_length));
+            let header = Header::from_raw_fields(fields).unwrap();
+
+            let mut output = Vec::new();
+            header.write_to(&mut output).unwrap();
+            let mut input = output.as_slice();
+            let read_back = Header::from_input(&mut input).unwrap();
+
+            // the empty line ending the header is consumed, and nothing more
+            prop_assert!(input.is_empty());
+            prop_assert_eq!(read_back.content_length, header.content_length);
+            prop_assert_eq!(read_back.fields, header.fields);
+        }
+    }
+}
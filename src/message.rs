@@ -0,0 +1,889 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{BufRead, Write};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Event, Header, Request, RequestKind, Response, DEFAULT_MAX_MESSAGE_SIZE};
+
+mod builder;
+pub(crate) mod dispatch;
+
+pub use builder::{EventBuilder, MessageBuilder, RequestBuilder};
+
+#[derive(Debug, Clone)]
+pub struct Message {
+    info: MessageInfo,
+    /// The json content of the message. It is never modified, so that it always match `content`.
+    raw_value: serde_json::Value,
+    /// The json content of the message, byte for byte as it was read.
+    content: Vec<u8>,
+    message_kind: Option<MessageKind>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct MessageInfo {
+    /// Sequence number (also known as message ID). For protocol messages of type
+    /// 'request' this ID can be used to cancel the request.
+    seq: usize,
+    #[serde(alias = "type")]
+    message_type: String,
+}
+
+/// The type of a protocol message, as found in its `type` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MessageType {
+    Request,
+    Event,
+    Response,
+}
+
+impl MessageType {
+    /// Return the value of the `type` field for this type of message.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MessageType::Request => "request",
+            MessageType::Event => "event",
+            MessageType::Response => "response",
+        }
+    }
+}
+
+/// The fields shared by every message of the protocol.
+///
+/// This lets code handle requests, events and responses alike, for example to log or forward
+/// them.
+pub trait ProtocolMessage {
+    /// Sequence number of the message.
+    fn seq(&self) -> usize;
+
+    /// Type of the message, written in its `type` field.
+    fn message_type(&self) -> MessageType;
+
+    /// Return the message as sent on the wire, `type` field included.
+    #[allow(clippy::wrong_self_convention)]
+    fn into_json_value(&self) -> Result<serde_json::Value, Error>;
+
+    /// Write the message in the dap wire format: a header, followed by the json content.
+    fn write_to<W: Write>(&self, output: &mut W) -> Result<(), Error>
+    where
+        Self: Sized,
+    {
+        write_value(&self.into_json_value()?, output)
+    }
+}
+
+impl ProtocolMessage for Request {
+    fn seq(&self) -> usize {
+        self.request_info.seq
+    }
+
+    fn message_type(&self) -> MessageType {
+        MessageType::Request
+    }
+
+    fn into_json_value(&self) -> Result<serde_json::Value, Error> {
+        let mut value = serde_json::to_value(&self.request_info)?;
+        value["type"] = self.message_type().as_str().into();
+        Ok(value)
+    }
+}
+
+impl ProtocolMessage for Event {
+    fn seq(&self) -> usize {
+        self.event_info.seq
+    }
+
+    fn message_type(&self) -> MessageType {
+        MessageType::Event
+    }
+
+    fn into_json_value(&self) -> Result<serde_json::Value, Error> {
+        let mut value = serde_json::to_value(&self.event_info)?;
+        value["type"] = self.message_type().as_str().into();
+        Ok(value)
+    }
+}
+
+impl ProtocolMessage for Response {
+    fn seq(&self) -> usize {
+        self.response_info.seq
+    }
+
+    fn message_type(&self) -> MessageType {
+        MessageType::Response
+    }
+
+    fn into_json_value(&self) -> Result<serde_json::Value, Error> {
+        let mut value = serde_json::to_value(&self.response_info)?;
+        value["type"] = self.message_type().as_str().into();
+        Ok(value)
+    }
+}
+
+/// The specialized form of a message, chosen from its `type` field.
+#[derive(Debug, Clone)]
+pub enum MessageKind {
+    Request(Request),
+    Event(Event),
+    Response(Response),
+}
+
+impl Message {
+    /// Read a message, rejecting a message larger than `DEFAULT_MAX_MESSAGE_SIZE` before reading its content.
+    pub fn try_from_input<R: BufRead>(input: &mut R) -> Result<Self, Error> {
+        Message::try_from_input_with_limit(input, DEFAULT_MAX_MESSAGE_SIZE)
+    }
+
+    /// Like `try_from_input`, rejecting a message larger than `limit` bytes instead.
+    pub fn try_from_input_with_limit<R: BufRead>(
+        input: &mut R,
+        limit: usize,
+    ) -> Result<Self, Error> {
+        let header = Header::from_input(input)?;
+        header.validate(limit)?;
+        Message::try_from_content(input, &header)
+    }
+
+    /// Read the content of a message whose header was already read.
+    pub(crate) fn try_from_content<R: BufRead>(
+        input: &mut R,
+        header: &Header,
+    ) -> Result<Self, Error> {
+        let mut buffer = vec![0; header.content_length];
+
+        input.read_exact(buffer.as_mut_slice())?;
+        Message::from_content(buffer)
+    }
+
+    /// Parse the json content of a message, keeping its bytes to write them back unchanged.
+    pub(crate) fn from_content(content: Vec<u8>) -> Result<Self, Error> {
+        let raw_value = serde_json::from_slice(content.as_slice())?;
+        Message::from_parts(raw_value, content)
+    }
+
+    /// Specialize the message according to its `type` field.
+    ///
+    /// A message without `seq` or `type`, or whose fields do not match its type, is reported as
+    /// `Error::InvalidMessage`. A message of a unknown type is kept unspecialized.
+    fn from_parts(raw_value: serde_json::Value, content: Vec<u8>) -> Result<Self, Error> {
+        // the fields are read from a borrowed value, so the json tree is parsed once and never
+        // copied
+        let info = MessageInfo::deserialize(&raw_value).or(Err(Error::InvalidMessage))?;
+        let message_kind = dispatch::message_kind(info.message_type.as_str(), &raw_value)?;
+
+        Ok(Self {
+            info,
+            raw_value,
+            content,
+            message_kind,
+        })
+    }
+
+    /// Write the message in the dap wire format: a header, followed by the json content.
+    ///
+    /// A message read from a input is written byte for byte as it was read, so that its key
+    /// order, whitespace and number formatting are kept.
+    pub fn write_to<W: Write>(&self, output: &mut W) -> Result<(), Error> {
+        write_content(&self.content, output)
+    }
+
+    /// Return the json content of the message, as it was read, or as written by `write_to`.
+    pub fn raw(&self) -> &[u8] {
+        self.content.as_slice()
+    }
+
+    /// Wrap a specialized message, keeping its json form as the raw value.
+    fn from_protocol_message<M: ProtocolMessage>(
+        message: &M,
+        message_kind: MessageKind,
+    ) -> Result<Self, Error> {
+        let raw_value = message.into_json_value()?;
+        let content = serde_json::to_vec(&raw_value)?;
+
+        Ok(Self {
+            info: MessageInfo {
+                seq: message.seq(),
+                message_type: message.message_type().as_str().to_string(),
+            },
+            raw_value,
+            content,
+            message_kind: Some(message_kind),
+        })
+    }
+
+    /// Return the message in the dap wire format.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::new();
+        self.write_to(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    #[doc(hidden)]
+    pub fn seq(&self) -> usize {
+        self.info.seq
+    }
+
+    #[doc(hidden)]
+    pub fn message_type(&self) -> &str {
+        self.info.message_type.as_str()
+    }
+
+    pub fn message_kind(&self) -> Option<&MessageKind> {
+        self.message_kind.as_ref()
+    }
+
+    /// Look up a field of the message by its json pointer (RFC 6901), such as `/arguments/threadId`.
+    ///
+    /// This is mostly useful to read the fields of a custom request, which has no type of its own.
+    pub fn get(&self, pointer: &str) -> Option<&serde_json::Value> {
+        self.raw_value.pointer(pointer)
+    }
+
+    /// Like `get`, returning `None` if the field is not a string.
+    pub fn get_str(&self, pointer: &str) -> Option<&str> {
+        self.get(pointer)?.as_str()
+    }
+
+    /// Like `get`, returning `None` if the field is not a unsigned integer.
+    pub fn get_u64(&self, pointer: &str) -> Option<u64> {
+        self.get(pointer)?.as_u64()
+    }
+
+    /// Like `get`, returning `None` if the field is not a boolean.
+    pub fn get_bool(&self, pointer: &str) -> Option<bool> {
+        self.get(pointer)?.as_bool()
+    }
+
+    /// Return the whole json content of the message, not to be confused with the `body` field of
+    /// events and responses.
+    pub fn body(&self) -> &serde_json::Value {
+        &self.raw_value
+    }
+
+    /// Consume the message, returning its json content.
+    pub fn into_value(self) -> serde_json::Value {
+        self.raw_value
+    }
+
+    /// Deserialize the json content of the message, or a part of it such as the arguments of a
+    /// request, see `get`.
+    ///
+    /// A content not matching `T` is reported as `Error::InvalidMessage`.
+    pub fn parse_as<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        T::deserialize(&self.raw_value).or(Err(Error::InvalidMessage))
+    }
+
+    /// Return the command of a request, or `None` for the other messages.
+    pub fn command(&self) -> Option<&str> {
+        match self.message_type() {
+            "request" => self.get_str("/command"),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<serde_json::Value> for Message {
+    type Error = Error;
+
+    fn try_from(raw_value: serde_json::Value) -> Result<Self, Error> {
+        let content = serde_json::to_vec(&raw_value)?;
+        Message::from_parts(raw_value, content)
+    }
+}
+
+impl TryFrom<Request> for Message {
+    type Error = Error;
+
+    fn try_from(request: Request) -> Result<Self, Error> {
+        Message::from_protocol_message(&request, MessageKind::Request(request.clone()))
+    }
+}
+
+impl TryFrom<Event> for Message {
+    type Error = Error;
+
+    fn try_from(event: Event) -> Result<Self, Error> {
+        Message::from_protocol_message(&event, MessageKind::Event(event.clone()))
+    }
+}
+
+impl TryFrom<Response> for Message {
+    type Error = Error;
+
+    fn try_from(response: Response) -> Result<Self, Error> {
+        Message::from_protocol_message(&response, MessageKind::Response(response.clone()))
+    }
+}
+
+/// Messages are displayed as pretty-printed json, for use in log messages.
+///
+/// The alternate format (`{:#}`) prefix the json with the `Content-Length` of the message, as
+/// written by `write_to`.
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            writeln!(f, "Content-Length: {}", self.content.len())?;
+        }
+        display_pretty(&self.raw_value, f)
+    }
+}
+
+impl fmt::Display for Request {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        display_value(&self.into_json_value().or(Err(fmt::Error))?, f)
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        display_value(&self.into_json_value().or(Err(fmt::Error))?, f)
+    }
+}
+
+impl fmt::Display for Response {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        display_value(&self.into_json_value().or(Err(fmt::Error))?, f)
+    }
+}
+
+fn display_value(value: &serde_json::Value, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if f.alternate() {
+        let content_length = serde_json::to_vec(value).or(Err(fmt::Error))?.len();
+        writeln!(f, "Content-Length: {}", content_length)?;
+    }
+    display_pretty(value, f)
+}
+
+fn display_pretty(value: &serde_json::Value, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let pretty = serde_json::to_string_pretty(value).or(Err(fmt::Error))?;
+    f.write_str(&pretty)
+}
+
+/// Write `value` in the dap wire format.
+fn write_value<W: Write>(value: &serde_json::Value, output: &mut W) -> Result<(), Error> {
+    write_content(&serde_json::to_vec(value)?, output)
+}
+
+/// Write the json `content` of a message in the dap wire format.
+fn write_content<W: Write>(content: &[u8], output: &mut W) -> Result<(), Error> {
+    // the content length is a number of bytes, not of characters
+    Header::new(content.len()).write_to(output)?;
+    output.write_all(content)?;
+    Ok(())
+}
+
+impl TryFrom<Message> for Request {
+    type Error = Error;
+
+    fn try_from(message: Message) -> Result<Self, Error> {
+        match message.message_kind {
+            Some(MessageKind::Request(request)) => Ok(request),
+            _ => Err(Error::InvalidMessage),
+        }
+    }
+}
+
+impl TryFrom<Message> for RequestKind {
+    type Error = Error;
+
+    fn try_from(message: Message) -> Result<Self, Error> {
+        Request::try_from(message).map(RequestKind::from)
+    }
+}
+
+impl TryFrom<Message> for Event {
+    type Error = Error;
+
+    fn try_from(message: Message) -> Result<Self, Error> {
+        match message.message_kind {
+            Some(MessageKind::Event(event)) => Ok(event),
+            _ => Err(Error::InvalidMessage),
+        }
+    }
+}
+
+impl TryFrom<Message> for Response {
+    type Error = Error;
+
+    fn try_from(message: Message) -> Result<Self, Error> {
+        match message.message_kind {
+            Some(MessageKind::Response(response)) => Ok(response),
+            _ => Err(Error::InvalidMessage),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+
+    fn message_from_body(body: &str) -> Result<Message, Error> {
+        let raw_message = format!("Content-Length:{}\r\n\r\n{}", body.len(), body);
+        Message::try_from_input(&mut raw_message.as_bytes())
+    }
+
+    #[test]
+    fn message_get() {
+        let message = Message::try_from(serde_json::json!({
+            "seq": 3,
+            "type": "request",
+            "command": "custom",
+            "arguments": {
+                "threadId": 7,
+                "name": "main",
+                "paused": true,
+                "lines": [10, 20],
+                "a/b": "slash",
+                "m~n": "tilde",
+            },
+        }))
+        .unwrap();
+
+        assert_eq!(message.get(""), Some(&message.raw_value));
+        assert_eq!(message.get_u64("/seq"), Some(3));
+        assert_eq!(message.get_str("/command"), Some("custom"));
+        assert_eq!(message.get_u64("/arguments/threadId"), Some(7));
+        assert_eq!(message.get_str("/arguments/name"), Some("main"));
+        assert_eq!(message.get_bool("/arguments/paused"), Some(true));
+        assert_eq!(message.get_u64("/arguments/lines/1"), Some(20));
+        assert_eq!(message.get_str("/arguments/a~1b"), Some("slash"));
+        assert_eq!(message.get_str("/arguments/m~0n"), Some("tilde"));
+
+        // missing fields, and fields of a other type
+        assert_eq!(message.get("/arguments/frameId"), None);
+        assert_eq!(message.get("arguments"), None);
+        assert_eq!(message.get_str("/arguments/threadId"), None);
+        assert_eq!(message.get_u64("/arguments/name"), None);
+        assert_eq!(message.get_bool("/arguments/lines"), None);
+    }
+
+    #[test]
+    fn message_from_input_too_large() {
+        // the content is never allocated, so the error is immediate
+        let input = "Content-Length: 4294967295\r\n\r\n{}";
+        match Message::try_from_input(&mut input.as_bytes()) {
+            Err(Error::MessageTooLarge { actual, limit }) => {
+                assert_eq!(actual, u32::MAX as usize);
+                assert_eq!(limit, DEFAULT_MAX_MESSAGE_SIZE);
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn message_from_input_with_limit() {
+        let input = "Content-Length: 2\r\n\r\n{}";
+        match Message::try_from_input_with_limit(&mut input.as_bytes(), 1) {
+            Err(Error::MessageTooLarge {
+                actual: 2,
+                limit: 1,
+            }) => (),
+            _ => panic!(),
+        }
+
+        let input = "Content-Length: 20\r\n\r\n{\"seq\":1,\"type\":\"x\"}";
+        let message = Message::try_from_input_with_limit(&mut input.as_bytes(), 20).unwrap();
+        assert_eq!(message.seq(), 1);
+    }
+
+    #[test]
+    fn message_from_input_valid() {
+        use serde_json::Value;
+
+        let body = r#"{
+            "seq": 1,
+            "type": "fake"
+          }"#;
+
+        let message = message_from_body(body).unwrap();
+
+        assert_eq!(message.seq(), 1);
+        assert_eq!(message.message_type(), "fake");
+        assert_eq!(
+            message.raw_value,
+            serde_json::from_str::<Value>(body).unwrap()
+        );
+        assert!(message.message_kind().is_none());
+    }
+
+    #[test]
+    fn message_body() {
+        let body = r#"{
+            "seq": 1,
+            "type": "request",
+            "command": "initialize",
+            "arguments": { "adapterID": "headcrab-rs", "linesStartAt1": true }
+          }"#;
+
+        let message = message_from_body(body).unwrap();
+        assert_eq!(message.command(), Some("initialize"));
+        assert_eq!(message.body()["arguments"]["adapterID"], "headcrab-rs");
+
+        #[derive(Deserialize)]
+        struct Initialize {
+            arguments: crate::InitializeRequestArguments,
+        }
+        let request: Initialize = message.parse_as().unwrap();
+        assert_eq!(request.arguments.adapter_id, "headcrab-rs");
+        assert_eq!(request.arguments.lines_start_at1, Some(true));
+        match message.parse_as::<Vec<usize>>() {
+            Err(Error::InvalidMessage) => (),
+            _ => panic!(),
+        }
+
+        let value = message.into_value();
+        assert_eq!(value["seq"], 1);
+
+        let event = r#"{ "seq": 3, "type": "event", "event": "initialized", "command": "next" }"#;
+        assert_eq!(message_from_body(event).unwrap().command(), None);
+    }
+
+    #[test]
+    fn message_from_input_initialize_request() {
+        let body = r#"{
+            "seq": 1,
+            "type": "request",
+            "command": "initialize",
+            "arguments": { "adapterID": "headcrab-rs" }
+          }"#;
+
+        let request = Request::try_from(message_from_body(body).unwrap()).unwrap();
+
+        assert_eq!(request.command(), "initialize");
+        match request.request_kind() {
+            RequestKind::Initialize(_) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn message_from_input_set_exception_breakpoints_request() {
+        let body = r#"{
+            "seq": 3,
+            "type": "request",
+            "command": "setExceptionBreakpoints",
+            "arguments": {
+                "filters": ["panic"],
+                "filterOptions": [
+                    { "filterId": "signal", "condition": "sig == 11" },
+                    { "filterId": "abort" }
+                ]
+            }
+          }"#;
+
+        let request = Request::try_from(message_from_body(body).unwrap()).unwrap();
+        let arguments = match request.request_kind() {
+            RequestKind::SetExceptionBreakpoints(request) => request.arguments(),
+            _ => panic!(),
+        };
+
+        assert_eq!(arguments.filters, vec!["panic".to_string()]);
+        let filter_options = arguments.filter_options.as_ref().unwrap();
+        assert_eq!(filter_options.len(), 2);
+        assert_eq!(filter_options[0].filter_id, "signal");
+        assert_eq!(filter_options[0].condition.as_deref(), Some("sig == 11"));
+        assert_eq!(filter_options[1].filter_id, "abort");
+        assert_eq!(filter_options[1].condition, None);
+
+        let body = r#"{
+            "seq": 4,
+            "type": "request",
+            "command": "setExceptionBreakpoints",
+            "arguments": { "filters": [] }
+          }"#;
+
+        let request = Request::try_from(message_from_body(body).unwrap()).unwrap();
+        match request.request_kind() {
+            RequestKind::SetExceptionBreakpoints(request) => {
+                assert!(request.arguments().filter_options.is_none())
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn message_from_input_optional_arguments() {
+        let commands = [
+            "configurationDone",
+            "disconnect",
+            "terminate",
+            "restart",
+            "breakpointLocations",
+        ];
+        let shapes = ["", r#", "arguments": {}"#, r#", "arguments": null"#];
+
+        for command in commands.iter() {
+            for shape in shapes.iter() {
+                let body = format!(
+                    r#"{{ "seq": 1, "type": "request", "command": "{}"{} }}"#,
+                    command, shape
+                );
+                let request = Request::try_from(message_from_body(&body).unwrap())
+                    .unwrap_or_else(|_| panic!("{} was rejected", body));
+
+                let has_arguments = match request.request_kind() {
+                    RequestKind::ConfigurationDone(request) => request.arguments().is_some(),
+                    RequestKind::Disconnect(request) => request.arguments().is_some(),
+                    RequestKind::Terminate(request) => request.arguments().is_some(),
+                    RequestKind::Restart(request) => request.arguments().is_some(),
+                    RequestKind::BreakpointLocations(request) => request.arguments().is_some(),
+                    _ => panic!("{} was not specialized", body),
+                };
+                assert!(!has_arguments, "{} has arguments", body);
+            }
+        }
+    }
+
+    #[test]
+    fn message_from_input_unknown_request() {
+        let body = r#"{ "seq": 2, "type": "request", "command": "custom" }"#;
+
+        let request = Request::try_from(message_from_body(body).unwrap()).unwrap();
+
+        assert_eq!(request.command(), "custom");
+        match RequestKind::from(request) {
+            RequestKind::Other { command, arguments } => {
+                assert_eq!(command, "custom");
+                assert_eq!(arguments, None);
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn message_from_input_request_without_command() {
+        let body = r#"{ "seq": 1, "type": "request" }"#;
+
+        match message_from_body(body) {
+            Err(Error::InvalidMessage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn message_from_input_without_envelope() {
+        for body in &[
+            r#"{ "type": "event", "event": "initialized" }"#,
+            r#"{ "seq": 1, "command": "next" }"#,
+            r#"{ "seq": "1", "type": "request", "command": "next" }"#,
+            r#"[1, "request"]"#,
+        ] {
+            match message_from_body(body) {
+                Err(Error::InvalidMessage) => (),
+                _ => panic!("{}", body),
+            }
+        }
+
+        // invalid json is still reported as such
+        match message_from_body("{ \"seq\": 1,") {
+            Err(Error::Json(_)) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn message_seq_of_each_kind() {
+        let bodies = [
+            r#"{ "seq": 1, "type": "request", "command": "next", "arguments": { "threadId": 1 } }"#,
+            r#"{ "seq": 2, "type": "event", "event": "initialized" }"#,
+            r#"{ "seq": 3, "type": "response", "request_seq": 1, "success": true, "command": "next" }"#,
+            r#"{ "seq": 4, "type": "fake" }"#,
+        ];
+
+        let messages: Vec<_> = bodies
+            .iter()
+            .map(|body| message_from_body(body).unwrap())
+            .collect();
+        let seqs: Vec<_> = messages.iter().map(Message::seq).collect();
+        assert_eq!(seqs, [1, 2, 3, 4]);
+
+        match messages[0].message_kind() {
+            Some(MessageKind::Request(request)) => assert_eq!(request.seq(), 1),
+            _ => panic!(),
+        }
+        match messages[1].message_kind() {
+            Some(MessageKind::Event(event)) => assert_eq!(event.seq(), 2),
+            _ => panic!(),
+        }
+        match messages[2].message_kind() {
+            Some(MessageKind::Response(response)) => assert_eq!(response.seq(), 3),
+            _ => panic!(),
+        }
+        assert!(messages[3].message_kind().is_none());
+    }
+
+    #[test]
+    fn message_from_input_event() {
+        let body = r#"{ "seq": 3, "type": "event", "event": "initialized" }"#;
+
+        let event = Event::try_from(message_from_body(body).unwrap()).unwrap();
+
+        assert_eq!(event.event(), "initialized");
+        assert_eq!(event.body(), None);
+    }
+
+    #[test]
+    fn message_from_input_event_without_event() {
+        let body = r#"{ "seq": 3, "type": "event" }"#;
+
+        match message_from_body(body) {
+            Err(Error::InvalidMessage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn message_from_input_response() {
+        let body = r#"{
+            "seq": 4,
+            "type": "response",
+            "request_seq": 1,
+            "success": true,
+            "command": "runInTerminal",
+            "body": { "processId": 42 }
+          }"#;
+
+        let response = Response::try_from(message_from_body(body).unwrap()).unwrap();
+
+        assert_eq!(response.request_seq(), 1);
+        assert!(response.success());
+        assert_eq!(response.command(), "runInTerminal");
+    }
+
+    #[test]
+    fn message_from_input_response_without_request_seq() {
+        let body = r#"{ "seq": 4, "type": "response", "success": true, "command": "next" }"#;
+
+        match message_from_body(body) {
+            Err(Error::InvalidMessage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn try_from_message_wrong_kind() {
+        let body = r#"{ "seq": 3, "type": "event", "event": "initialized" }"#;
+
+        match Request::try_from(message_from_body(body).unwrap()) {
+            Err(Error::InvalidMessage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn message_write_to_round_trip() {
+        let body =
+            r#"{ "seq": 5, "type": "event", "event": "output", "body": { "output": "héllo" } }"#;
+        let message = message_from_body(body).unwrap();
+
+        let bytes = message.to_bytes().unwrap();
+        let read_back = Message::try_from_input(&mut bytes.as_slice()).unwrap();
+
+        assert_eq!(read_back.seq(), 5);
+        assert_eq!(read_back.message_type(), "event");
+        assert_eq!(read_back.raw_value, message.raw_value);
+    }
+
+    #[test]
+    fn message_write_to_keep_raw_content() {
+        let body = "{\"type\":  \"event\",\n  \"seq\": 5,\t\"event\": \"custom\",\n  \"body\": { \"ratio\": 1.50e+3, \"id\": 1E2 } }";
+        let raw_message = format!("Content-Length: {}\r\n\r\n{}", body.len(), body);
+        let message = message_from_body(body).unwrap();
+        assert_eq!(message.raw(), body.as_bytes());
+        assert_eq!(message.seq(), 5);
+
+        let bytes = message.to_bytes().unwrap();
+        assert_eq!(String::from_utf8(bytes).unwrap(), raw_message);
+
+        // a message built from a value is written in the compact form, losing the formatting of
+        // its numbers
+        let message = Message::try_from(message.into_value()).unwrap();
+        let compact =
+            r#"{"type":"event","seq":5,"event":"custom","body":{"ratio":1500.0,"id":100.0}}"#;
+        assert_eq!(message.raw(), compact.as_bytes());
+    }
+
+    #[test]
+    fn protocol_message_write_to() {
+        let body = r#"{ "seq": 6, "type": "event", "event": "initialized" }"#;
+        let event = Event::try_from(message_from_body(body).unwrap()).unwrap();
+
+        assert_eq!(ProtocolMessage::seq(&event), 6);
+        assert_eq!(event.message_type(), MessageType::Event);
+
+        let mut bytes = Vec::new();
+        ProtocolMessage::write_to(&event, &mut bytes).unwrap();
+        let read_back = Message::try_from_input(&mut bytes.as_slice()).unwrap();
+
+        assert_eq!(read_back.message_type(), "event");
+        assert_eq!(
+            read_back.raw_value,
+            message_from_body(body).unwrap().raw_value
+        );
+    }
+
+    #[test]
+    fn display_message() {
+        let body = r#"{"seq":8,"type":"event","event":"stopped","body":{"threadId":1}}"#;
+        let message = message_from_body(body).unwrap();
+
+        let expected = r#"{
+  "seq": 8,
+  "type": "event",
+  "event": "stopped",
+  "body": {
+    "threadId": 1
+  }
+}"#;
+        assert_eq!(message.to_string(), expected);
+        assert_eq!(
+            format!("{:#}", message),
+            format!("Content-Length: {}\n{}", body.len(), expected)
+        );
+    }
+
+    #[test]
+    fn display_request() {
+        let body = r#"{"seq":9,"type":"request","command":"threads"}"#;
+        let request = Request::try_from(message_from_body(body).unwrap()).unwrap();
+
+        let expected = r#"{
+  "seq": 9,
+  "command": "threads",
+  "type": "request"
+}"#;
+        assert_eq!(request.to_string(), expected);
+        assert_eq!(
+            format!("{:#}", request),
+            format!("Content-Length: 46\n{}", expected)
+        );
+    }
+
+    #[test]
+    fn write_multi_byte_content() {
+        use std::io::Read;
+
+        let body = r#"{ "seq": 7, "type": "event", "event": "output", "body": { "output": "変数 = 42" } }"#;
+        let message = message_from_body(body).unwrap();
+
+        let mut bytes = Vec::new();
+        message.write_to(&mut bytes).unwrap();
+
+        let mut input = bytes.as_slice();
+        let header = Header::from_input(&mut input).unwrap();
+        let content = message.raw().to_vec();
+        assert_eq!(header.content_length, content.len());
+        assert!(header.content_length > String::from_utf8(content).unwrap().chars().count());
+
+        let mut buffer = vec![0; header.content_length];
+        input.read_exact(&mut buffer).unwrap();
+        assert!(input.is_empty());
+
+        let raw_value: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(raw_value["body"]["output"], "変数 = 42");
+    }
+}
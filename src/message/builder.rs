@@ -0,0 +1,189 @@
+//! Builders for the messages sent by a client or a adapter, numbered by a shared counter.
+
+use std::convert::TryFrom;
+
+use serde::Serialize;
+
+use super::dispatch;
+use crate::event::EventInfo;
+use crate::request::RequestInfo;
+use crate::{Error, Event, Message, Request, SeqCounter};
+
+/// Build outgoing requests and events, taking their sequence numbers from `counter`.
+///
+/// The counter should be the one of the session, such as `Adapter::seq_counter`, so that the
+/// built messages are numbered along with every other message sent.
+#[derive(Debug, Clone, Copy)]
+pub struct MessageBuilder<'a> {
+    counter: &'a SeqCounter,
+}
+
+impl<'a> MessageBuilder<'a> {
+    pub fn new(counter: &'a SeqCounter) -> Self {
+        Self { counter }
+    }
+
+    /// Start building a request for `command`. Its sequence number is taken right away.
+    pub fn request(&self, command: &str) -> RequestBuilder {
+        RequestBuilder {
+            seq: self.counter.next(),
+            command: command.to_string(),
+            arguments: None,
+        }
+    }
+
+    /// Start building a event named `event_name`. Its sequence number is taken right away.
+    pub fn event(&self, event_name: &str) -> EventBuilder {
+        EventBuilder {
+            seq: self.counter.next(),
+            event: event_name.to_string(),
+            body: None,
+        }
+    }
+}
+
+/// A request being built by a `MessageBuilder`.
+#[derive(Debug, Clone)]
+pub struct RequestBuilder {
+    seq: usize,
+    command: String,
+    arguments: Option<serde_json::Value>,
+}
+
+impl RequestBuilder {
+    /// The sequence number of the request.
+    pub fn seq(&self) -> usize {
+        self.seq
+    }
+
+    /// Set the arguments of the request.
+    ///
+    /// The arguments are serialized right away, and omitted if they serialize as `null`.
+    pub fn arguments<T: Serialize>(mut self, arguments: T) -> Result<Self, Error> {
+        let arguments = serde_json::to_value(arguments)?;
+        self.arguments = Some(arguments).filter(|arguments| !arguments.is_null());
+        Ok(self)
+    }
+
+    /// Build the request message, ready to be sent. `Message::to_bytes` return it with its
+    /// `Content-Length` header.
+    ///
+    /// Fail with `Error::InvalidMessage` if the arguments do not match the command.
+    pub fn build(self) -> Result<Message, Error> {
+        let request_info = RequestInfo {
+            seq: self.seq,
+            command: self.command,
+            arguments: self.arguments,
+        };
+        let request_kind = Box::new(dispatch::request_kind(&request_info)?);
+
+        Message::try_from(Request {
+            request_info,
+            request_kind,
+        })
+    }
+}
+
+/// A event being built by a `MessageBuilder`.
+#[derive(Debug, Clone)]
+pub struct EventBuilder {
+    seq: usize,
+    event: String,
+    body: Option<serde_json::Value>,
+}
+
+impl EventBuilder {
+    /// The sequence number of the event.
+    pub fn seq(&self) -> usize {
+        self.seq
+    }
+
+    /// Set the body of the event.
+    ///
+    /// The body is serialized right away, and omitted if it serialize as `null`.
+    pub fn body<T: Serialize>(mut self, body: T) -> Result<Self, Error> {
+        let body = serde_json::to_value(body)?;
+        self.body = Some(body).filter(|body| !body.is_null());
+        Ok(self)
+    }
+
+    /// Build the event message, ready to be sent. `Message::to_bytes` return it with its
+    /// `Content-Length` header.
+    pub fn build(self) -> Result<Message, Error> {
+        Message::try_from(Event {
+            event_info: EventInfo {
+                seq: self.seq,
+                event: self.event,
+                body: self.body,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+    use crate::{EventKind, RequestKind};
+
+    #[test]
+    fn build_messages() {
+        let counter = SeqCounter::default();
+        let builder = MessageBuilder::new(&counter);
+
+        let request = builder.request("threads").build().unwrap();
+        assert_eq!(
+            *request.body(),
+            serde_json::json!({ "seq": 1, "type": "request", "command": "threads" })
+        );
+        assert!(matches!(
+            RequestKind::try_from(request).unwrap(),
+            RequestKind::Threads(_)
+        ));
+
+        let event = builder
+            .event("exited")
+            .body(serde_json::json!({ "exitCode": 0 }))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(event.seq(), 2);
+        assert_eq!(
+            EventKind::try_from(event.clone()).unwrap(),
+            EventKind::Exited(crate::ExitedEvent::new(0))
+        );
+
+        let content = r#"{"seq":2,"event":"exited","body":{"exitCode":0},"type":"event"}"#;
+        assert_eq!(
+            String::from_utf8(event.to_bytes().unwrap()).unwrap(),
+            format!("Content-Length: {}\r\n\r\n{}", content.len(), content)
+        );
+
+        // a seq is never reused, even by a request which failed to build
+        let source = builder.request("source");
+        assert_eq!(source.seq(), 3);
+        assert!(source.build().is_err());
+        assert_eq!(counter.next(), 4);
+    }
+
+    #[test]
+    fn build_request_arguments() {
+        let counter = SeqCounter::new(10);
+        let request = MessageBuilder::new(&counter)
+            .request("continue")
+            .arguments(crate::ContinueArguments::new(3))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            *request.body(),
+            serde_json::json!({
+                "seq": 10,
+                "type": "request",
+                "command": "continue",
+                "arguments": { "threadId": 3 }
+            })
+        );
+    }
+}
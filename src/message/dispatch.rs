@@ -0,0 +1,192 @@
+//! The specialization table.
+//!
+//! A message is first read as a raw json value. The functions of this module then look at
+//! its `type` field (and, for a request, at its `command` field) to pick the specialized type
+//! it should be converted into. Supporting a new request only require adding an arm to
+//! `request_kind`.
+
+use serde::de::DeserializeOwned;
+
+use crate::event::{Event, EventInfo};
+use crate::request::{
+    AttachRequest, BreakpointLocationsRequest, CancelRequest, CompletionsRequest,
+    ConfigurationDoneRequest, ContinueRequest, DataBreakpointInfoRequest, DisassembleRequest,
+    DisconnectRequest, EvaluateRequest, GotoTargetsRequest, InitializeRequest, LaunchRequest,
+    LoadedSourcesRequest, ModulesRequest, NextRequest, ReadMemoryRequest, Request, RequestInfo,
+    RequestKind, RestartRequest, ReverseContinueRequest, RunInTerminalRequest,
+    SetBreakpointsRequest, SetExceptionBreakpointsRequest, SourceRequest, StackTraceRequest,
+    StartDebuggingRequest, StepBackRequest, StepInRequest, StepInTargetsRequest, StepOutRequest,
+    TerminateRequest, TerminateThreadsRequest, ThreadsRequest, VariablesRequest,
+    WriteMemoryRequest,
+};
+use crate::response::{Response, ResponseInfo};
+use crate::{Error, MessageKind};
+
+pub(super) fn message_kind(
+    message_type: &str,
+    value: &serde_json::Value,
+) -> Result<Option<MessageKind>, Error> {
+    match message_type {
+        "request" => Ok(Some(MessageKind::Request(request(value)?))),
+        "event" => Ok(Some(MessageKind::Event(event(value)?))),
+        "response" => Ok(Some(MessageKind::Response(response(value)?))),
+        _ => Ok(None),
+    }
+}
+
+fn request(value: &serde_json::Value) -> Result<Request, Error> {
+    let request_info: RequestInfo = specialize(value)?;
+    let request_kind = Box::new(request_kind(&request_info)?);
+
+    Ok(Request {
+        request_info,
+        request_kind,
+    })
+}
+
+pub(crate) fn request_kind(info: &RequestInfo) -> Result<RequestKind, Error> {
+    let kind = match info.command.as_str() {
+        "attach" => RequestKind::Attach(AttachRequest {
+            arguments: arguments(info)?,
+        }),
+        "breakpointLocations" => RequestKind::BreakpointLocations(BreakpointLocationsRequest {
+            arguments: optional_arguments(info)?,
+        }),
+        "cancel" => RequestKind::Cancel(CancelRequest {
+            arguments: optional_arguments(info)?,
+        }),
+        "completions" => RequestKind::Completions(CompletionsRequest {
+            arguments: arguments(info)?,
+        }),
+        "configurationDone" => RequestKind::ConfigurationDone(ConfigurationDoneRequest {
+            arguments: optional_arguments(info)?,
+        }),
+        "dataBreakpointInfo" => RequestKind::DataBreakpointInfo(DataBreakpointInfoRequest {
+            arguments: arguments(info)?,
+        }),
+        "disassemble" => RequestKind::Disassemble(DisassembleRequest {
+            arguments: arguments(info)?,
+        }),
+        "continue" => RequestKind::Continue(ContinueRequest {
+            arguments: arguments(info)?,
+        }),
+        "disconnect" => RequestKind::Disconnect(DisconnectRequest {
+            arguments: optional_arguments(info)?,
+        }),
+        "evaluate" => RequestKind::Evaluate(EvaluateRequest {
+            arguments: arguments(info)?,
+        }),
+        "gotoTargets" => RequestKind::GotoTargets(GotoTargetsRequest {
+            arguments: arguments(info)?,
+        }),
+        "initialize" => RequestKind::Initialize(InitializeRequest {
+            arguments: arguments(info)?,
+        }),
+        "launch" => RequestKind::Launch(LaunchRequest {
+            arguments: arguments(info)?,
+        }),
+        "loadedSources" => RequestKind::LoadedSources(LoadedSourcesRequest),
+        "modules" => RequestKind::Modules(ModulesRequest {
+            arguments: optional_arguments(info)?,
+        }),
+        "next" => RequestKind::Next(NextRequest {
+            arguments: arguments(info)?,
+        }),
+        "readMemory" => RequestKind::ReadMemory(ReadMemoryRequest {
+            arguments: arguments(info)?,
+        }),
+        "restart" => RequestKind::Restart(RestartRequest {
+            arguments: optional_arguments(info)?,
+        }),
+        "reverseContinue" => RequestKind::ReverseContinue(ReverseContinueRequest {
+            arguments: arguments(info)?,
+        }),
+        "runInTerminal" => RequestKind::RunInTerminal(RunInTerminalRequest {
+            arguments: arguments(info)?,
+        }),
+        "setBreakpoints" => RequestKind::SetBreakpoints(SetBreakpointsRequest {
+            arguments: arguments(info)?,
+        }),
+        "setExceptionBreakpoints" => {
+            RequestKind::SetExceptionBreakpoints(SetExceptionBreakpointsRequest {
+                arguments: arguments(info)?,
+            })
+        }
+        "source" => RequestKind::Source(SourceRequest {
+            arguments: arguments(info)?,
+        }),
+        "stackTrace" => RequestKind::StackTrace(StackTraceRequest {
+            arguments: arguments(info)?,
+        }),
+        "startDebugging" => RequestKind::StartDebugging(StartDebuggingRequest {
+            arguments: arguments(info)?,
+        }),
+        "stepBack" => RequestKind::StepBack(StepBackRequest {
+            arguments: arguments(info)?,
+        }),
+        "stepIn" => RequestKind::StepIn(StepInRequest {
+            arguments: arguments(info)?,
+        }),
+        "stepInTargets" => RequestKind::StepInTargets(StepInTargetsRequest {
+            arguments: arguments(info)?,
+        }),
+        "stepOut" => RequestKind::StepOut(StepOutRequest {
+            arguments: arguments(info)?,
+        }),
+        "terminate" => RequestKind::Terminate(TerminateRequest {
+            arguments: optional_arguments(info)?,
+        }),
+        "terminateThreads" => RequestKind::TerminateThreads(TerminateThreadsRequest {
+            arguments: arguments(info)?,
+        }),
+        "threads" => RequestKind::Threads(ThreadsRequest),
+        "variables" => RequestKind::Variables(VariablesRequest {
+            arguments: arguments(info)?,
+        }),
+        "writeMemory" => RequestKind::WriteMemory(WriteMemoryRequest {
+            arguments: arguments(info)?,
+        }),
+        // unknown command are not an error, they simply stay unspecialized
+        _ => RequestKind::Other {
+            command: info.command.clone(),
+            arguments: info.arguments.clone(),
+        },
+    };
+
+    Ok(kind)
+}
+
+fn event(value: &serde_json::Value) -> Result<Event, Error> {
+    let event_info: EventInfo = specialize(value)?;
+    Ok(Event { event_info })
+}
+
+fn response(value: &serde_json::Value) -> Result<Response, Error> {
+    let response_info: ResponseInfo = specialize(value)?;
+    Ok(Response { response_info })
+}
+
+/// Deserialize the arguments of a request whose command require some.
+fn arguments<T: DeserializeOwned>(info: &RequestInfo) -> Result<T, Error> {
+    let arguments = info.arguments.as_ref().ok_or(Error::InvalidMessage)?;
+    specialize(arguments)
+}
+
+/// Deserialize the arguments of a request whose command accept them, but does not require them.
+///
+/// Clients omit such arguments, or send them as `null` or as a empty object. All three are
+/// treated as the absence of arguments.
+fn optional_arguments<T: DeserializeOwned>(info: &RequestInfo) -> Result<Option<T>, Error> {
+    match &info.arguments {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::Object(object)) if object.is_empty() => Ok(None),
+        Some(arguments) => specialize(arguments).map(Some),
+    }
+}
+
+/// Deserialize `value`, reporting a mismatch as an invalid message rather than as invalid json.
+///
+/// The value is borrowed, so a message is specialized without copying its json tree.
+fn specialize<T: DeserializeOwned>(value: &serde_json::Value) -> Result<T, Error> {
+    T::deserialize(value).or(Err(Error::InvalidMessage))
+}
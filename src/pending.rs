@@ -0,0 +1,118 @@
+//! The requests sent by one side of a session which are still waiting for a response.
+
+use std::collections::HashMap;
+use std::sync::{mpsc, Mutex};
+
+use crate::{Error, Response};
+
+/// The sending end for the response to a request, or for its cancellation.
+pub(crate) trait ResponseSender: Sized {
+    /// The receiving end, through which the requester wait for the response.
+    type Receiver;
+
+    /// Create the channel through which a single response is handed to its requester.
+    fn channel() -> (Self, Self::Receiver);
+
+    /// Hand `response` to the requester.
+    fn respond(self, response: Result<Response, Error>);
+}
+
+impl ResponseSender for mpsc::Sender<Result<Response, Error>> {
+    type Receiver = mpsc::Receiver<Result<Response, Error>>;
+
+    fn channel() -> (Self, Self::Receiver) {
+        mpsc::channel()
+    }
+
+    fn respond(self, response: Result<Response, Error>) {
+        // the requester may have given up on the response, which is fine.
+        let _ = self.send(response);
+    }
+}
+
+#[cfg(feature = "tokio-client")]
+impl ResponseSender for tokio::sync::oneshot::Sender<Result<Response, Error>> {
+    type Receiver = tokio::sync::oneshot::Receiver<Result<Response, Error>>;
+
+    fn channel() -> (Self, Self::Receiver) {
+        tokio::sync::oneshot::channel()
+    }
+
+    fn respond(self, response: Result<Response, Error>) {
+        // the requester may have given up on the response, which is fine.
+        let _ = self.send(response);
+    }
+}
+
+/// The requests which are still waiting for a response, each with the sender of its response.
+#[derive(Debug)]
+pub(crate) struct PendingResponses<S> {
+    /// `None` once the session is over, since no response can arrive anymore.
+    requests: Mutex<Option<HashMap<usize, S>>>,
+}
+
+impl<S> Default for PendingResponses<S> {
+    fn default() -> Self {
+        Self {
+            requests: Mutex::new(Some(HashMap::new())),
+        }
+    }
+}
+
+impl<S: ResponseSender> PendingResponses<S> {
+    /// Register the request `seq`, returning the receiving end for its response.
+    pub(crate) fn register(&self, seq: usize) -> S::Receiver {
+        let (sender, receiver) = S::channel();
+
+        // if the session is over, the sender is dropped right away
+        if let Some(requests) = self.requests.lock().unwrap().as_mut() {
+            requests.insert(seq, sender);
+        }
+
+        receiver
+    }
+
+    /// Forget the request `seq`, which could not be sent.
+    pub(crate) fn forget(&self, seq: usize) {
+        if let Some(requests) = self.requests.lock().unwrap().as_mut() {
+            requests.remove(&seq);
+        }
+    }
+
+    /// Hand `response` to the request it answer, returning false if no such request is pending.
+    pub(crate) fn resolve(&self, response: &Response) -> bool {
+        let sender = self
+            .requests
+            .lock()
+            .unwrap()
+            .as_mut()
+            .and_then(|requests| requests.remove(&response.request_seq()));
+
+        match sender {
+            Some(sender) => {
+                let response = match response.check_success() {
+                    Err(cancelled @ Error::Cancelled { .. }) => Err(cancelled),
+                    _ => Ok(response.clone()),
+                };
+                sender.respond(response);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The number of requests still waiting for a response.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.requests
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map_or(0, HashMap::len)
+    }
+
+    /// Drop every pending request, waking up their requester.
+    pub(crate) fn close(&self) {
+        self.requests.lock().unwrap().take();
+    }
+}
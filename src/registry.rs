@@ -0,0 +1,381 @@
+//! Keeping track of the breakpoints and sources a adapter reported to the client.
+
+use std::collections::HashSet;
+
+use crate::{
+    Breakpoint, BreakpointEvent, Error, EventEmitter, LoadedSourceEvent, LoadedSourceEventReason,
+    SetBreakpointsArguments, Source, SourceBreakpoint,
+};
+
+type Resolver = Box<dyn FnMut(&Source, &SourceBreakpoint) -> Option<usize> + Send>;
+
+/// Keep track of the breakpoints set by the client through ‘setBreakpoints’ requests.
+///
+/// Each request replace every breakpoint of its source. A breakpoint requested again on the same
+/// line keep its id, and if the backend now resolve it differently, a ‘breakpoint’ event report
+/// the change to the client.
+pub struct BreakpointRegistry {
+    emitter: EventEmitter,
+    resolve: Resolver,
+    sources: Vec<SourceBreakpoints>,
+    next_id: usize,
+}
+
+/// The breakpoints of a source, along with the line they were requested on.
+struct SourceBreakpoints {
+    source: Source,
+    breakpoints: Vec<(usize, Breakpoint)>,
+}
+
+impl BreakpointRegistry {
+    /// Create a empty registry, sending its events through `emitter`.
+    ///
+    /// `resolve` return the line where the backend placed a breakpoint of a source,
+    /// or `None` if it cannot place it, in which case the breakpoint is not verified.
+    pub fn new<F>(emitter: EventEmitter, resolve: F) -> Self
+    where
+        F: FnMut(&Source, &SourceBreakpoint) -> Option<usize> + Send + 'static,
+    {
+        Self {
+            emitter,
+            resolve: Box::new(resolve),
+            sources: Vec::new(),
+            next_id: 1,
+        }
+    }
+
+    /// Every breakpoint currently set, grouped by source.
+    pub fn breakpoints(&self) -> impl Iterator<Item = &Breakpoint> {
+        self.sources
+            .iter()
+            .flat_map(|source| source.breakpoints.iter().map(|(_, breakpoint)| breakpoint))
+    }
+
+    /// Replace the breakpoints of the source of `arguments`, returning the body of the response.
+    ///
+    /// The breakpoints are returned in the order of the request. A request setting the same line
+    /// twice is rejected with `Error::InvalidMessage`, leaving the breakpoints unchanged.
+    pub fn set_breakpoints(
+        &mut self,
+        arguments: SetBreakpointsArguments,
+    ) -> Result<Vec<Breakpoint>, Error> {
+        let requested = arguments.effective_breakpoints();
+
+        let mut lines = HashSet::new();
+        if !requested
+            .iter()
+            .all(|breakpoint| lines.insert(breakpoint.line))
+        {
+            return Err(Error::InvalidMessage);
+        }
+
+        let index = self
+            .sources
+            .iter()
+            .position(|other| other.source.is_same(&arguments.source));
+        let previous = match index {
+            Some(index) => std::mem::take(&mut self.sources[index].breakpoints),
+            None => Vec::new(),
+        };
+
+        let mut breakpoints = Vec::with_capacity(requested.len());
+        let mut changed = Vec::new();
+        for request in requested.iter() {
+            let resolved = (self.resolve)(&arguments.source, request);
+            let old = previous
+                .iter()
+                .find(|(line, _)| *line == request.line)
+                .map(|(_, breakpoint)| breakpoint);
+
+            let id = match old {
+                Some(old) => old.id,
+                None => {
+                    self.next_id += 1;
+                    Some(self.next_id - 1)
+                }
+            };
+            let breakpoint = Breakpoint {
+                id,
+                verified: resolved.is_some(),
+                source: Some(arguments.source.clone()),
+                line: Some(resolved.unwrap_or(request.line)),
+                column: request.column,
+                ..Breakpoint::default()
+            };
+
+            if matches!(old, Some(old) if *old != breakpoint) {
+                changed.push(breakpoint.clone());
+            }
+            breakpoints.push((request.line, breakpoint));
+        }
+
+        let response = breakpoints
+            .iter()
+            .map(|(_, breakpoint)| breakpoint.clone())
+            .collect();
+        // the source keep its place, so the breakpoints of the other sources are not reordered
+        let entry = SourceBreakpoints {
+            source: arguments.source,
+            breakpoints,
+        };
+        match index {
+            Some(index) if entry.breakpoints.is_empty() => {
+                self.sources.remove(index);
+            }
+            Some(index) => self.sources[index] = entry,
+            None if entry.breakpoints.is_empty() => (),
+            None => self.sources.push(entry),
+        }
+
+        for breakpoint in changed {
+            self.emitter.emit(BreakpointEvent::changed(breakpoint))?;
+        }
+        Ok(response)
+    }
+}
+
+/// Keep track of the sources reported to the client, sending the ‘loadedSource’ events which
+/// keep the client up to date.
+///
+/// Sources are identified by their `source_reference`, or by their `path` when they have no
+/// reference.
+pub struct SourceRegistry {
+    emitter: EventEmitter,
+    sources: Vec<Source>,
+}
+
+impl SourceRegistry {
+    /// Create a empty registry, sending its events through `emitter`.
+    pub fn new(emitter: EventEmitter) -> Self {
+        Self {
+            emitter,
+            sources: Vec::new(),
+        }
+    }
+
+    /// Every loaded source, as returned by a 'loadedSources' request.
+    pub fn sources(&self) -> &[Source] {
+        &self.sources
+    }
+
+    /// Record a newly loaded source, and report it to the client.
+    pub fn register(&mut self, source: Source) -> Result<(), Error> {
+        self.insert(source.clone());
+        self.emit(LoadedSourceEventReason::New, source)
+    }
+
+    /// Record a change of a loaded source, and report it to the client.
+    pub fn update(&mut self, source: Source) -> Result<(), Error> {
+        self.insert(source.clone());
+        self.emit(LoadedSourceEventReason::Changed, source)
+    }
+
+    /// Forget `source`, and report its removal to the client.
+    ///
+    /// Return the source as it was registered, or `None` if it was never registered, in which
+    /// case no event is sent.
+    pub fn remove(&mut self, source: &Source) -> Result<Option<Source>, Error> {
+        let index = match self.sources.iter().position(|other| other.is_same(source)) {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+
+        let removed = self.sources.remove(index);
+        self.emit(LoadedSourceEventReason::Removed, removed.clone())?;
+        Ok(Some(removed))
+    }
+
+    fn insert(&mut self, source: Source) {
+        match self.sources.iter_mut().find(|other| other.is_same(&source)) {
+            Some(other) => *other = source,
+            None => self.sources.push(source),
+        }
+    }
+
+    fn emit(&self, reason: LoadedSourceEventReason, source: Source) -> Result<(), Error> {
+        self.emitter.emit(LoadedSourceEvent { reason, source })
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+    use std::io::{self, Cursor};
+    use std::sync::{Arc, Mutex};
+
+    use crate::test_util::SharedOutput;
+    use crate::{Adapter, EventKind};
+
+    fn events(output: &SharedOutput) -> Vec<EventKind> {
+        output
+            .events()
+            .into_iter()
+            .map(|event| event.event_kind().unwrap())
+            .collect()
+    }
+
+    fn changed_breakpoints(output: &SharedOutput) -> Vec<Breakpoint> {
+        events(output)
+            .into_iter()
+            .map(|event| match event {
+                EventKind::Breakpoint(event) => event.breakpoint,
+                _ => panic!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn source_registry_lifecycle() {
+        let output = SharedOutput::default();
+        let adapter = Adapter::with_reader_writer(Cursor::new(Vec::new()), output.clone());
+        let mut registry = SourceRegistry::new(adapter.event_emitter());
+
+        let generated = Source {
+            name: Some("<generated>".to_string()),
+            source_reference: Some(3),
+            ..Source::default()
+        };
+        registry.register(generated.clone()).unwrap();
+        let main = source("/src/main.rs");
+        registry.register(main.clone()).unwrap();
+        assert_eq!(registry.sources(), &[generated.clone(), main.clone()]);
+
+        let renamed = Source {
+            name: Some("<generated 2>".to_string()),
+            ..generated.clone()
+        };
+        registry.update(renamed.clone()).unwrap();
+        assert_eq!(registry.sources(), &[renamed.clone(), main.clone()]);
+
+        // a source is removed by reference, or by path when it has none
+        let reference = Source {
+            source_reference: Some(3),
+            ..Source::default()
+        };
+        assert_eq!(registry.remove(&reference).unwrap(), Some(renamed.clone()));
+        assert_eq!(registry.remove(&reference).unwrap(), None);
+        assert_eq!(registry.remove(&main).unwrap(), Some(main.clone()));
+        assert!(registry.sources().is_empty());
+
+        let events: Vec<_> = events(&output)
+            .into_iter()
+            .map(|event| match event {
+                EventKind::LoadedSource(event) => (event.reason, event.source),
+                _ => panic!(),
+            })
+            .collect();
+        assert_eq!(
+            events,
+            [
+                (LoadedSourceEventReason::New, generated),
+                (LoadedSourceEventReason::New, main.clone()),
+                (LoadedSourceEventReason::Changed, renamed.clone()),
+                (LoadedSourceEventReason::Removed, renamed),
+                (LoadedSourceEventReason::Removed, main),
+            ]
+        );
+    }
+
+    fn source(path: &str) -> Source {
+        Source {
+            path: Some(path.to_string()),
+            ..Source::default()
+        }
+    }
+
+    fn arguments(path: &str, lines: &[usize]) -> SetBreakpointsArguments {
+        SetBreakpointsArguments {
+            breakpoints: Some(lines.iter().copied().map(SourceBreakpoint::new).collect()),
+            ..SetBreakpointsArguments::new(source(path))
+        }
+    }
+
+    #[test]
+    fn set_breakpoints() {
+        let output = SharedOutput::default();
+        let adapter = Adapter::with_reader_writer(Cursor::new(Vec::new()), output.clone());
+        // line 5 has no code, and the code of line 20 only start on line 22
+        let code = Arc::new(Mutex::new(vec![10, 20]));
+        let backend = code.clone();
+        let mut registry =
+            BreakpointRegistry::new(adapter.event_emitter(), move |_, breakpoint| {
+                let code = backend.lock().unwrap();
+                match breakpoint.line {
+                    20 if !code.contains(&20) => Some(22),
+                    line if code.contains(&line) => Some(line),
+                    _ => None,
+                }
+            });
+
+        let breakpoints = registry
+            .set_breakpoints(arguments("main.rs", &[10, 5]))
+            .unwrap();
+        assert_eq!(breakpoints.len(), 2);
+        assert_eq!(breakpoints[0].id, Some(1));
+        assert!(breakpoints[0].verified);
+        assert_eq!(breakpoints[0].line, Some(10));
+        assert_eq!(breakpoints[0].source, Some(source("main.rs")));
+        assert_eq!(breakpoints[1].id, Some(2));
+        assert!(!breakpoints[1].verified);
+        assert_eq!(breakpoints[1].line, Some(5));
+
+        let other = registry
+            .set_breakpoints(arguments("lib.rs", &[20]))
+            .unwrap();
+        assert_eq!(other[0].id, Some(3));
+        assert_eq!(registry.breakpoints().count(), 3);
+        assert!(changed_breakpoints(&output).is_empty());
+
+        // line 10 is kept, line 5 is removed, and line 20 is added
+        code.lock().unwrap().retain(|line| *line != 20);
+        let breakpoints = registry
+            .set_breakpoints(arguments("main.rs", &[20, 10]))
+            .unwrap();
+        assert_eq!(breakpoints[0].id, Some(4));
+        assert_eq!(breakpoints[0].line, Some(22));
+        assert_eq!(breakpoints[1].id, Some(1));
+        // main.rs is still listed before lib.rs
+        let all: Vec<_> = registry.breakpoints().cloned().collect();
+        assert_eq!(all, [breakpoints.clone(), other.clone()].concat());
+        assert!(changed_breakpoints(&output).is_empty());
+
+        // the backend now fail to place line 10
+        code.lock().unwrap().clear();
+        let breakpoints = registry
+            .set_breakpoints(arguments("main.rs", &[10]))
+            .unwrap();
+        assert_eq!(breakpoints[0].id, Some(1));
+        assert!(!breakpoints[0].verified);
+        assert_eq!(changed_breakpoints(&output), breakpoints);
+
+        // clearing a source forget its breakpoints
+        registry
+            .set_breakpoints(SetBreakpointsArguments::new(source("main.rs")))
+            .unwrap();
+        let remaining: Vec<_> = registry.breakpoints().cloned().collect();
+        assert_eq!(remaining, other);
+    }
+
+    #[test]
+    fn set_breakpoints_duplicate_lines() {
+        let adapter = Adapter::with_reader_writer(Cursor::new(Vec::new()), io::sink());
+        let mut registry = BreakpointRegistry::new(adapter.event_emitter(), |_, breakpoint| {
+            Some(breakpoint.line)
+        });
+        registry
+            .set_breakpoints(arguments("main.rs", &[1, 2]))
+            .unwrap();
+
+        match registry.set_breakpoints(arguments("main.rs", &[3, 4, 3])) {
+            Err(Error::InvalidMessage) => (),
+            _ => panic!(),
+        }
+        // the breakpoints set before are left in place
+        let lines: Vec<_> = registry
+            .breakpoints()
+            .map(|breakpoint| breakpoint.line)
+            .collect();
+        assert_eq!(lines, vec![Some(1), Some(2)]);
+    }
+}
@@ -0,0 +1,2499 @@
+//! The requests, and their arguments.
+
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::message::dispatch;
+use crate::util::decode_base64;
+use crate::{
+    Base64Engine, Error, ExceptionFilterOptions, ExceptionOptions, Source, SourceBreakpoint,
+    StackFrameFormat, StandardBase64, SteppingGranularity, ValueFormat,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub(crate) request_info: RequestInfo,
+    // boxed, since some arguments are much larger than the other messages
+    pub(crate) request_kind: Box<RequestKind>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub(crate) struct RequestInfo {
+    /**
+     * Sequence number of the request.
+     */
+    pub(crate) seq: usize,
+
+    /**
+     * The command to execute.
+     */
+    pub(crate) command: String,
+
+    /**
+     * Object containing arguments for the command.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) arguments: Option<serde_json::Value>,
+}
+
+/// The specialized form of a request, chosen from its `command` field.
+///
+/// A adapter can handle every request with a single `match` over this enum.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequestKind {
+    Attach(AttachRequest),
+    BreakpointLocations(BreakpointLocationsRequest),
+    Cancel(CancelRequest),
+    Completions(CompletionsRequest),
+    ConfigurationDone(ConfigurationDoneRequest),
+    Continue(ContinueRequest),
+    DataBreakpointInfo(DataBreakpointInfoRequest),
+    Disassemble(DisassembleRequest),
+    Disconnect(DisconnectRequest),
+    Evaluate(EvaluateRequest),
+    GotoTargets(GotoTargetsRequest),
+    Initialize(InitializeRequest),
+    Launch(LaunchRequest),
+    LoadedSources(LoadedSourcesRequest),
+    Modules(ModulesRequest),
+    Next(NextRequest),
+    ReadMemory(ReadMemoryRequest),
+    Restart(RestartRequest),
+    ReverseContinue(ReverseContinueRequest),
+    RunInTerminal(RunInTerminalRequest),
+    SetBreakpoints(SetBreakpointsRequest),
+    SetExceptionBreakpoints(SetExceptionBreakpointsRequest),
+    Source(SourceRequest),
+    StackTrace(StackTraceRequest),
+    StartDebugging(StartDebuggingRequest),
+    StepBack(StepBackRequest),
+    StepIn(StepInRequest),
+    StepInTargets(StepInTargetsRequest),
+    StepOut(StepOutRequest),
+    Terminate(TerminateRequest),
+    TerminateThreads(TerminateThreadsRequest),
+    Threads(ThreadsRequest),
+    Variables(VariablesRequest),
+    WriteMemory(WriteMemoryRequest),
+    /// A request whose command is not supported by this crate.
+    Other {
+        command: String,
+        arguments: Option<serde_json::Value>,
+    },
+}
+
+impl From<Request> for RequestKind {
+    fn from(request: Request) -> Self {
+        *request.request_kind
+    }
+}
+
+impl Request {
+    /// Create a request for `command`, to be sent with the sequence number `seq`.
+    pub fn new<T: Serialize>(
+        seq: usize,
+        command: impl Into<String>,
+        arguments: T,
+    ) -> Result<Self, Error> {
+        let request_info = RequestInfo {
+            seq,
+            command: command.into(),
+            arguments: Some(serde_json::to_value(arguments)?),
+        };
+        let request_kind = Box::new(dispatch::request_kind(&request_info)?);
+
+        Ok(Self {
+            request_info,
+            request_kind,
+        })
+    }
+
+    pub fn seq(&self) -> usize {
+        self.request_info.seq
+    }
+
+    pub fn command(&self) -> &str {
+        self.request_info.command.as_str()
+    }
+
+    pub fn arguments(&self) -> Option<serde_json::Value> {
+        self.request_info.arguments.clone()
+    }
+
+    pub fn request_kind(&self) -> &RequestKind {
+        &self.request_kind
+    }
+}
+
+/// The ‘initialize’ request is sent as the first request from the client to the debug adapter
+///
+/// in order to configure it with client capabilities and to retrieve capabilities from the debug adapter.
+///
+/// Until the debug adapter has responded to with an ‘initialize’ response, the client must not send any additional requests or events to the debug adapter.
+///
+/// In addition the debug adapter is not allowed to send any requests or events to the client until it has responded with an ‘initialize’ response.
+///
+/// The ‘initialize’ request may only be sent once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InitializeRequest {
+    pub(crate) arguments: InitializeRequestArguments,
+}
+
+impl InitializeRequest {
+    pub fn arguments(&self) -> &InitializeRequestArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeRequestArguments {
+    /**
+     * The ID of the (frontend) client using this adapter.
+     */
+    #[serde(rename = "clientID", skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+
+    /**
+     * The human readable name of the (frontend) client using this adapter.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_name: Option<String>,
+
+    /**
+     * The ID of the debug adapter.
+     */
+    #[serde(rename = "adapterID")]
+    pub adapter_id: String,
+
+    /**
+     * The ISO-639 locale of the (frontend) client using this adapter, e.g. en-US
+     * or de-CH.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+
+    /**
+     * If true all line numbers are 1-based (default).
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lines_start_at1: Option<bool>,
+
+    /**
+     * If true all column numbers are 1-based (default).
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub columns_start_at1: Option<bool>,
+
+    /**
+     * Determines in what format paths are specified. The default is 'path', which
+     * is the native format.
+     * Values: 'path', 'uri', etc.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path_format: Option<PathFormat>,
+
+    /**
+     * Client supports the optional type attribute for variables.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_variable_type: Option<bool>,
+
+    /**
+     * Client supports the paging of variables.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_variable_paging: Option<bool>,
+
+    /**
+     * Client supports the runInTerminal request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_run_in_terminal_request: Option<bool>,
+
+    /**
+     * Client supports memory references.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_memory_references: Option<bool>,
+
+    /**
+     * Client supports progress reporting.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_progress_reporting: Option<bool>,
+
+    /**
+     * Client supports the invalidated event.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_invalidated_event: Option<bool>,
+
+    /**
+     * Client supports the memory event.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_memory_event: Option<bool>,
+}
+
+impl InitializeRequestArguments {
+    pub fn new(adapter_id: impl Into<String>) -> Self {
+        Self {
+            client_id: None,
+            client_name: None,
+            adapter_id: adapter_id.into(),
+            locale: None,
+            lines_start_at1: None,
+            columns_start_at1: None,
+            path_format: None,
+            supports_variable_type: None,
+            supports_variable_paging: None,
+            supports_run_in_terminal_request: None,
+            supports_memory_references: None,
+            supports_progress_reporting: None,
+            supports_invalidated_event: None,
+            supports_memory_event: None,
+        }
+    }
+}
+
+/// The format in which the client specify paths.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PathFormat {
+    Path,
+    Uri,
+    /// A format which is not part of the specification.
+    #[serde(untagged)]
+    Other(String),
+}
+
+/// The known formats are matched case-insensitively, since some clients send `"Path"` or `"URI"`.
+/// Any other format is kept as is.
+impl<'de> Deserialize<'de> for PathFormat {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let format = String::deserialize(deserializer)?;
+        let path_format = match format.to_ascii_lowercase().as_str() {
+            "path" => PathFormat::Path,
+            "uri" => PathFormat::Uri,
+            _ => PathFormat::Other(format),
+        };
+        Ok(path_format)
+    }
+}
+
+/// The ‘cancel’ request is used by the frontend in two situations:
+///
+/// - to indicate that it is no longer interested in the result produced by a specific request issued earlier
+/// - to cancel a progress sequence.
+///
+/// This request has a hint characteristic: a debug adapter can only be expected to make a ‘best effort’ in honouring this request but there are no guarantees.
+///
+/// A frontend client should only call this request if the capability ‘supportsCancelRequest’ is true.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CancelRequest {
+    pub(crate) arguments: Option<CancelArguments>,
+}
+
+impl CancelRequest {
+    pub fn arguments(&self) -> Option<&CancelArguments> {
+        self.arguments.as_ref()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelArguments {
+    /**
+     * The ID (attribute 'seq') of the request to cancel. If missing no request is
+     * cancelled.
+     * Both a 'requestId' and a 'progressId' can be specified in one request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<usize>,
+
+    /**
+     * The ID (attribute 'progressId') of the progress to cancel. If missing no
+     * progress is cancelled.
+     * Both a 'requestId' and a 'progressId' can be specified in one request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress_id: Option<String>,
+}
+
+/// This optional request is sent from the debug adapter to the client to run a command in a terminal.
+///
+/// This is typically used to launch the debuggee in a terminal provided by the client.
+///
+/// This request should only be called if the client has passed the value true for the ‘supportsRunInTerminalRequest’ capability of the ‘initialize’ request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunInTerminalRequest {
+    pub(crate) arguments: RunInTerminalRequestArguments,
+}
+
+impl RunInTerminalRequest {
+    pub fn arguments(&self) -> &RunInTerminalRequestArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunInTerminalRequestArguments {
+    /**
+     * What kind of terminal to launch.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<RunInTerminalKind>,
+
+    /**
+     * Optional title of the terminal.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /**
+     * Working directory for the command. For non-empty, valid paths this
+     * typically results in execution of a change directory command.
+     */
+    pub cwd: String,
+
+    /**
+     * List of arguments. The first argument is the command to run.
+     */
+    pub args: Vec<String>,
+
+    /**
+     * Environment key-value pairs that are added to or removed from the default
+     * environment.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<HashMap<String, Option<String>>>,
+
+    /**
+     * This property should only be set if the corresponding capability
+     * `supportsArgsCanBeInterpretedByShell` is true. If the client uses an
+     * intermediary shell to launch the application, then the client must not
+     * attempt to escape characters with special meanings for the shell. The user
+     * is fully responsible for escaping as needed and that arguments using
+     * special characters may not be portable across shells.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub args_can_be_interpreted_by_shell: Option<bool>,
+}
+
+impl RunInTerminalRequestArguments {
+    pub fn new(cwd: impl Into<String>, args: Vec<String>) -> Self {
+        Self {
+            kind: None,
+            title: None,
+            cwd: cwd.into(),
+            args,
+            env: None,
+            args_can_be_interpreted_by_shell: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RunInTerminalKind {
+    Integrated,
+    External,
+}
+
+/// The request configures the debuggers response to thrown exceptions.
+///
+/// If an exception is configured to break, a ‘stopped’ event is fired (with reason ‘exception’).
+///
+/// Clients should only call this request if the capability ‘exceptionBreakpointFilters’ returns one or more filters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetExceptionBreakpointsRequest {
+    pub(crate) arguments: SetExceptionBreakpointsArguments,
+}
+
+impl SetExceptionBreakpointsRequest {
+    pub fn arguments(&self) -> &SetExceptionBreakpointsArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetExceptionBreakpointsArguments {
+    /**
+     * Set of exception filters specified by their ID. The set of all possible
+     * exception filters is defined by the 'exceptionBreakpointFilters'
+     * capability. The 'filter' and 'filterOptions' sets are additive.
+     */
+    pub filters: Vec<String>,
+
+    /**
+     * Set of exception filters and their options. The set of all possible
+     * exception filters is defined by the 'exceptionBreakpointFilters'
+     * capability. This attribute is only honored by a debug adapter if the
+     * capability 'supportsExceptionFilterOptions' is true. The 'filter' and
+     * 'filterOptions' sets are additive.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter_options: Option<Vec<ExceptionFilterOptions>>,
+
+    /**
+     * Configuration options for selected exceptions.
+     * The attribute is only honored by a debug adapter if the capability
+     * 'supportsExceptionOptions' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exception_options: Option<Vec<ExceptionOptions>>,
+}
+
+impl SetExceptionBreakpointsArguments {
+    pub fn new(filters: Vec<String>) -> Self {
+        Self {
+            filters,
+            filter_options: None,
+            exception_options: None,
+        }
+    }
+}
+
+/// Returns a list of possible completions for a given caret position and text.
+///
+/// Clients should only call this request if the capability ‘supportsCompletionsRequest’ is true.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompletionsRequest {
+    pub(crate) arguments: CompletionsArguments,
+}
+
+impl CompletionsRequest {
+    pub fn arguments(&self) -> &CompletionsArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionsArguments {
+    /**
+     * Returns completions in the scope of this stack frame. If not specified,
+     * the completions are returned for the global scope.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frame_id: Option<usize>,
+
+    /**
+     * One or more source lines. Typically this is the text a user has typed into
+     * the debug console before he asked for completion.
+     */
+    pub text: String,
+
+    /**
+     * The character position for which to determine the completion proposals.
+     */
+    pub column: usize,
+
+    /**
+     * An optional line for which to determine the completion proposals. If
+     * missing the first line of the text is assumed.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+impl CompletionsArguments {
+    pub fn new(text: impl Into<String>, column: usize) -> Self {
+        Self {
+            frame_id: None,
+            text: text.into(),
+            column,
+            line: None,
+        }
+    }
+}
+
+/// Obtains information on a possible data breakpoint that could be set on an expression or variable.
+///
+/// Clients should only call this request if the capability ‘supportsDataBreakpoints’ is true.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataBreakpointInfoRequest {
+    pub(crate) arguments: DataBreakpointInfoArguments,
+}
+
+impl DataBreakpointInfoRequest {
+    pub fn arguments(&self) -> &DataBreakpointInfoArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataBreakpointInfoArguments {
+    /**
+     * Reference to the Variable container if the data breakpoint is requested
+     * for a child of the container.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables_reference: Option<usize>,
+
+    /**
+     * The name of the Variable's child to obtain data breakpoint information
+     * for.
+     * If variablesReference isn't provided, this can be an expression.
+     */
+    pub name: String,
+
+    /**
+     * When `name` is an expression, evaluate it in the scope of this stack
+     * frame. If not specified, the expression is evaluated in the global scope.
+     * When `variablesReference` is specified, this property has no effect.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frame_id: Option<usize>,
+}
+
+impl DataBreakpointInfoArguments {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            variables_reference: None,
+            name: name.into(),
+            frame_id: None,
+        }
+    }
+}
+
+/// This request retrieves the possible goto targets for the specified source location.
+///
+/// These targets can be used in the ‘goto’ request.
+///
+/// Clients should only call this request if the capability ‘supportsGotoTargetsRequest’ is true.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GotoTargetsRequest {
+    pub(crate) arguments: GotoTargetsArguments,
+}
+
+impl GotoTargetsRequest {
+    pub fn arguments(&self) -> &GotoTargetsArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GotoTargetsArguments {
+    /**
+     * The source location for which the goto targets are determined.
+     */
+    pub source: Source,
+
+    /**
+     * The line location for which the goto targets are determined.
+     */
+    pub line: usize,
+
+    /**
+     * An optional column location for which the goto targets are determined.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+impl GotoTargetsArguments {
+    pub fn new(source: Source, line: usize) -> Self {
+        Self {
+            source,
+            line,
+            column: None,
+        }
+    }
+}
+
+/// This request retrieves the possible stepIn targets for the specified stack frame.
+///
+/// These targets can be used in the ‘stepIn’ request.
+///
+/// Clients should only call this request if the capability ‘supportsStepInTargetsRequest’ is true.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepInTargetsRequest {
+    pub(crate) arguments: StepInTargetsArguments,
+}
+
+impl StepInTargetsRequest {
+    pub fn arguments(&self) -> &StepInTargetsArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepInTargetsArguments {
+    /**
+     * The stack frame for which to retrieve the possible stepIn targets.
+     */
+    pub frame_id: usize,
+}
+
+impl StepInTargetsArguments {
+    pub fn new(frame_id: usize) -> Self {
+        Self { frame_id }
+    }
+}
+
+/// The request retrieves the source code for a given source reference.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceRequest {
+    pub(crate) arguments: SourceArguments,
+}
+
+impl SourceRequest {
+    pub fn arguments(&self) -> &SourceArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceArguments {
+    /**
+     * Specifies the source content to load. Either source.path or
+     * source.sourceReference must be specified.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<Source>,
+
+    /**
+     * The reference to the source. This is the same as source.sourceReference.
+     * This is provided for backward compatibility since old backends do not
+     * understand the 'source' attribute.
+     */
+    pub source_reference: usize,
+}
+
+impl SourceArguments {
+    pub fn new(source_reference: usize) -> Self {
+        Self {
+            source: None,
+            source_reference,
+        }
+    }
+}
+
+/// Modules can be retrieved from the debug adapter with this request which can either return all modules or a range of modules to support paging.
+///
+/// Clients should only call this request if the capability ‘supportsModulesRequest’ is true.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModulesRequest {
+    pub(crate) arguments: Option<ModulesArguments>,
+}
+
+impl ModulesRequest {
+    pub fn arguments(&self) -> Option<&ModulesArguments> {
+        self.arguments.as_ref()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModulesArguments {
+    /**
+     * The index of the first module to return; if omitted modules start at 0.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_module: Option<usize>,
+
+    /**
+     * The number of modules to return. If moduleCount is not specified or 0, all
+     * modules are returned.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module_count: Option<usize>,
+}
+
+/// This request is sent from the debug adapter to the client to start a new debug session of the same type as the caller.
+///
+/// This request should only be sent if the corresponding client capability ‘supportsStartDebuggingRequest’ is true.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StartDebuggingRequest {
+    pub(crate) arguments: StartDebuggingRequestArguments,
+}
+
+impl StartDebuggingRequest {
+    pub fn arguments(&self) -> &StartDebuggingRequestArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartDebuggingRequestArguments {
+    /**
+     * Arguments passed to the new debug session. The arguments must only contain
+     * properties understood by the `launch` or `attach` requests of the debug
+     * adapter and they must not contain any client-specific properties (e.g.
+     * `type`) or client-specific features (e.g. substitutable 'variables').
+     */
+    pub configuration: serde_json::Map<String, serde_json::Value>,
+
+    /**
+     * Indicates whether the new debug session should be started with a `launch`
+     * or `attach` request.
+     */
+    pub request: StartDebuggingRequestKind,
+}
+
+impl StartDebuggingRequestArguments {
+    pub fn new(request: StartDebuggingRequestKind) -> Self {
+        Self {
+            configuration: serde_json::Map::new(),
+            request,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StartDebuggingRequestKind {
+    Launch,
+    Attach,
+}
+
+/// This launch request is sent from the client to the debug adapter to start the debuggee with or without debugging (if ‘noDebug’ is true).
+///
+/// Since launching is debugger/runtime specific, the arguments for this request are not part of this specification.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchRequest {
+    pub(crate) arguments: LaunchArguments,
+}
+
+impl LaunchRequest {
+    pub fn arguments(&self) -> &LaunchArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchArguments {
+    /**
+     * If noDebug is true the launch request should launch the program without
+     * enabling debugging.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_debug: Option<bool>,
+
+    /**
+     * Optional data from the previous, restarted session.
+     * The data is sent as the 'restart' attribute of the 'terminated' event.
+     * The client should leave the data intact.
+     */
+    #[serde(rename = "__restart", skip_serializing_if = "Option::is_none")]
+    pub restart: Option<serde_json::Value>,
+
+    /// The adapter-specific attributes, such as the ones of a launch configuration.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl LaunchArguments {
+    /// Deserialize the adapter-specific attributes into the adapter's own launch configuration.
+    ///
+    /// `noDebug` and `__restart` are not part of `extra`, and so are not seen by `T`.
+    pub fn parse_extra<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        parse_extra(&self.extra)
+    }
+}
+
+/// The attach request is sent from the client to the debug adapter to attach to a debuggee that is already running.
+///
+/// Since attaching is debugger/runtime specific, the arguments for this request are not part of this specification.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttachRequest {
+    pub(crate) arguments: AttachArguments,
+}
+
+impl AttachRequest {
+    pub fn arguments(&self) -> &AttachArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AttachArguments {
+    /**
+     * Optional data from the previous, restarted session.
+     * The data is sent as the 'restart' attribute of the 'terminated' event.
+     * The client should leave the data intact.
+     */
+    #[serde(rename = "__restart", skip_serializing_if = "Option::is_none")]
+    pub restart: Option<serde_json::Value>,
+
+    /// The adapter-specific attributes, such as the ones of a attach configuration.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl AttachArguments {
+    /// Deserialize the adapter-specific attributes into the adapter's own attach configuration.
+    ///
+    /// `__restart` is not part of `extra`, and so is not seen by `T`.
+    pub fn parse_extra<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        parse_extra(&self.extra)
+    }
+}
+
+fn parse_extra<T: DeserializeOwned>(
+    extra: &serde_json::Map<String, serde_json::Value>,
+) -> Result<T, Error> {
+    serde_json::from_value(serde_json::Value::Object(extra.clone())).or(Err(Error::InvalidMessage))
+}
+
+/// Restarts a debug session. Clients should only call this request if the capability ‘supportsRestartRequest’ is true.
+///
+/// If the capability is missing or has the value false, a typical client will emulate ‘restart’ by terminating the debug adapter first and then launching it anew.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestartRequest {
+    pub(crate) arguments: Option<RestartArguments>,
+}
+
+impl RestartRequest {
+    pub fn arguments(&self) -> Option<&RestartArguments> {
+        self.arguments.as_ref()
+    }
+}
+
+/// The latest version of the launch or attach configuration, passed to a ‘restart’ request.
+///
+/// The configuration carry no discriminator, so the kind of configuration is guessed from its
+/// attributes: a `request` attribute (which clients copy from the launch configuration), then
+/// `noDebug` or a attribute commonly used by launch configurations, then a attribute commonly used
+/// by attach configurations. When the guess is ambiguous, the configuration is kept as `Other`.
+///
+/// Both the current `{ "arguments": configuration }` shape and the older shape, where the
+/// configuration is passed directly, are accepted. The current shape is always serialized.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RestartArguments {
+    Launch(LaunchArguments),
+    Attach(AttachArguments),
+    /// A configuration which could not be identified.
+    Other(serde_json::Value),
+}
+
+impl RestartArguments {
+    /// Attributes which suggest a launch configuration.
+    const LAUNCH_KEYS: &'static [&'static str] =
+        &["noDebug", "program", "args", "env", "stopOnEntry"];
+    /// Attributes which suggest a attach configuration.
+    const ATTACH_KEYS: &'static [&'static str] = &["pid", "processId", "port", "host"];
+
+    fn from_configuration(configuration: serde_json::Value) -> Result<Self, serde_json::Error> {
+        let object = match configuration.as_object() {
+            Some(object) => object,
+            None => return Ok(RestartArguments::Other(configuration)),
+        };
+        let has_any = |keys: &[&str]| keys.iter().any(|key| object.contains_key(*key));
+
+        let is_launch = match object.get("request").and_then(|request| request.as_str()) {
+            Some("launch") => Some(true),
+            Some("attach") => Some(false),
+            _ => match (has_any(Self::LAUNCH_KEYS), has_any(Self::ATTACH_KEYS)) {
+                (true, false) => Some(true),
+                (false, true) => Some(false),
+                _ => None,
+            },
+        };
+
+        match is_launch {
+            Some(true) => serde_json::from_value(configuration).map(RestartArguments::Launch),
+            Some(false) => serde_json::from_value(configuration).map(RestartArguments::Attach),
+            None => Ok(RestartArguments::Other(configuration)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RestartArguments {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut value = serde_json::Value::deserialize(deserializer)?;
+
+        // `{ "arguments": configuration }` is the current shape, anything else is a configuration
+        let configuration = match value.as_object_mut() {
+            Some(object) if object.len() == 1 && object.contains_key("arguments") => {
+                object.remove("arguments").unwrap()
+            }
+            _ => value,
+        };
+
+        RestartArguments::from_configuration(configuration).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for RestartArguments {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Wrapper<'a, T> {
+            arguments: &'a T,
+        }
+
+        match self {
+            RestartArguments::Launch(arguments) => Wrapper { arguments }.serialize(serializer),
+            RestartArguments::Attach(arguments) => Wrapper { arguments }.serialize(serializer),
+            RestartArguments::Other(arguments) => Wrapper { arguments }.serialize(serializer),
+        }
+    }
+}
+
+/// This optional request indicates that the client has finished initialization of the debug adapter.
+///
+/// So it is the last request in the sequence of configuration requests (which was started by the ‘initialized’ event).
+///
+/// Clients should only call this request if the capability ‘supportsConfigurationDoneRequest’ is true.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigurationDoneRequest {
+    pub(crate) arguments: Option<ConfigurationDoneArguments>,
+}
+
+impl ConfigurationDoneRequest {
+    pub fn arguments(&self) -> Option<&ConfigurationDoneArguments> {
+        self.arguments.as_ref()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfigurationDoneArguments {}
+
+/// The ‘disconnect’ request is sent from the client to the debug adapter in order to stop debugging.
+///
+/// It asks the debug adapter to disconnect from the debuggee and to terminate the debug adapter. If the debuggee has been started with the ‘launch’ request, the ‘disconnect’ request terminates the debuggee. If the ‘attach’ request was used to connect to the debuggee, ‘disconnect’ does not terminate the debuggee. This behavior can be controlled with the ‘terminateDebuggee’ argument (if supported by the debug adapter).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisconnectRequest {
+    pub(crate) arguments: Option<DisconnectArguments>,
+}
+
+impl DisconnectRequest {
+    pub fn arguments(&self) -> Option<&DisconnectArguments> {
+        self.arguments.as_ref()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisconnectArguments {
+    /**
+     * A value of true indicates that this 'disconnect' request is part of a
+     * restart sequence.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restart: Option<bool>,
+
+    /**
+     * Indicates whether the debuggee should be terminated when the debugger is
+     * disconnected.
+     * If unspecified, the debug adapter is free to do whatever it thinks is best.
+     * The attribute is only honored by a debug adapter if the capability
+     * 'supportTerminateDebuggee' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub terminate_debuggee: Option<bool>,
+
+    /**
+     * Indicates whether the debuggee should stay suspended when the debugger is
+     * disconnected.
+     * If unspecified, the debuggee should resume execution.
+     * The attribute is only honored by a debug adapter if the capability
+     * 'supportSuspendDebuggee' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suspend_debuggee: Option<bool>,
+}
+
+/// The ‘terminate’ request is sent from the client to the debug adapter in order to give the debuggee a chance for terminating itself.
+///
+/// Clients should only call this request if the capability ‘supportsTerminateRequest’ is true.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerminateRequest {
+    pub(crate) arguments: Option<TerminateArguments>,
+}
+
+impl TerminateRequest {
+    pub fn arguments(&self) -> Option<&TerminateArguments> {
+        self.arguments.as_ref()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminateArguments {
+    /**
+     * A value of true indicates that this 'terminate' request is part of a
+     * restart sequence.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restart: Option<bool>,
+}
+
+/// The ‘breakpointLocations’ request returns all possible locations for source breakpoints in a given range.
+///
+/// Clients should only call this request if the capability ‘supportsBreakpointLocationsRequest’ is true.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BreakpointLocationsRequest {
+    pub(crate) arguments: Option<BreakpointLocationsArguments>,
+}
+
+impl BreakpointLocationsRequest {
+    pub fn arguments(&self) -> Option<&BreakpointLocationsArguments> {
+        self.arguments.as_ref()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BreakpointLocationsArguments {
+    /**
+     * The source location of the breakpoints; either 'source.path' or
+     * 'source.reference' must be specified.
+     */
+    pub source: Source,
+
+    /**
+     * Start line of range to search possible breakpoint locations in. If only the
+     * line is specified, the request returns all possible locations in that line.
+     */
+    pub line: usize,
+
+    /**
+     * Optional start column of range to search possible breakpoint locations in.
+     * If no start column is given, the first column in the start line is assumed.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+
+    /**
+     * Optional end line of range to search possible breakpoint locations in. If
+     * no end line is given, then the end line is assumed to be the start line.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+
+    /**
+     * Optional end column of range to search possible breakpoint locations in. If
+     * no end column is given, then it is assumed to be in the last column of the
+     * end line.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
+}
+
+impl BreakpointLocationsArguments {
+    pub fn new(source: Source, line: usize) -> Self {
+        Self {
+            source,
+            line,
+            column: None,
+            end_line: None,
+            end_column: None,
+        }
+    }
+}
+
+/// The request resumes execution of all threads. If the debug adapter supports single thread execution (see capability ‘supportsSingleThreadExecutionRequests’), setting the ‘singleThread’ argument to true resumes only the specified thread.
+///
+/// If not all threads were resumed, the ‘allThreadsContinued’ attribute of the response should be set to false.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContinueRequest {
+    pub(crate) arguments: ContinueArguments,
+}
+
+impl ContinueRequest {
+    pub fn arguments(&self) -> &ContinueArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContinueArguments {
+    /**
+     * Specifies the active thread. If the debug adapter supports single thread
+     * execution (see 'supportsSingleThreadExecutionRequests') and the argument
+     * 'singleThread' is true, only the thread with this ID is resumed.
+     */
+    pub thread_id: usize,
+
+    /**
+     * If this flag is true, execution is resumed only for the thread with given
+     * 'threadId'. The attribute is only honored by a debug adapter if the
+     * capability 'supportsSingleThreadExecutionRequests' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub single_thread: Option<bool>,
+}
+
+impl ContinueArguments {
+    pub fn new(thread_id: usize) -> Self {
+        Self {
+            thread_id,
+            single_thread: None,
+        }
+    }
+}
+
+/// The request executes one step (in the given granularity) for the specified thread and allows all other threads to run freely by resuming them.
+///
+/// If the debug adapter supports single thread execution (see capability ‘supportsSingleThreadExecutionRequests’), setting the ‘singleThread’ argument to true prevents other suspended threads from resuming.
+///
+/// The debug adapter first sends the response and then a ‘stopped’ event (with reason ‘step’) after the step has completed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NextRequest {
+    pub(crate) arguments: NextArguments,
+}
+
+impl NextRequest {
+    pub fn arguments(&self) -> &NextArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NextArguments {
+    /**
+     * Specifies the thread for which to resume execution for one step (of the
+     * given granularity).
+     */
+    pub thread_id: usize,
+
+    /**
+     * If this flag is true, execution is resumed only for the thread with given
+     * 'threadId'. The attribute is only honored by a debug adapter if the
+     * capability 'supportsSingleThreadExecutionRequests' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub single_thread: Option<bool>,
+
+    /**
+     * Optional granularity to step. If no granularity is specified, a
+     * granularity of 'statement' is assumed.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<SteppingGranularity>,
+}
+
+impl NextArguments {
+    pub fn new(thread_id: usize) -> Self {
+        Self {
+            thread_id,
+            single_thread: None,
+            granularity: None,
+        }
+    }
+}
+
+/// The request resumes the given thread to step into a function/method and allows all other threads to run freely by resuming them.
+///
+/// If the debug adapter supports single thread execution (see capability ‘supportsSingleThreadExecutionRequests’), setting the ‘singleThread’ argument to true prevents other suspended threads from resuming.
+///
+/// If the request cannot step into a target, ‘stepIn’ behaves like the ‘next’ request.
+///
+/// The debug adapter first sends the response and then a ‘stopped’ event (with reason ‘step’) after the step has completed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepInRequest {
+    pub(crate) arguments: StepInArguments,
+}
+
+impl StepInRequest {
+    pub fn arguments(&self) -> &StepInArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepInArguments {
+    /**
+     * Specifies the thread for which to resume execution for one step-into (of
+     * the given granularity).
+     */
+    pub thread_id: usize,
+
+    /**
+     * If this flag is true, execution is resumed only for the thread with given
+     * 'threadId'. The attribute is only honored by a debug adapter if the
+     * capability 'supportsSingleThreadExecutionRequests' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub single_thread: Option<bool>,
+
+    /**
+     * Optional id of the target to step into.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_id: Option<usize>,
+
+    /**
+     * Optional granularity to step. If no granularity is specified, a
+     * granularity of 'statement' is assumed.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<SteppingGranularity>,
+}
+
+impl StepInArguments {
+    pub fn new(thread_id: usize) -> Self {
+        Self {
+            thread_id,
+            single_thread: None,
+            target_id: None,
+            granularity: None,
+        }
+    }
+}
+
+/// The request resumes the given thread to step out (return) from a function/method and allows all other threads to run freely by resuming them.
+///
+/// If the debug adapter supports single thread execution (see capability ‘supportsSingleThreadExecutionRequests’), setting the ‘singleThread’ argument to true prevents other suspended threads from resuming.
+///
+/// The debug adapter first sends the response and then a ‘stopped’ event (with reason ‘step’) after the step has completed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepOutRequest {
+    pub(crate) arguments: StepOutArguments,
+}
+
+impl StepOutRequest {
+    pub fn arguments(&self) -> &StepOutArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepOutArguments {
+    /**
+     * Specifies the thread for which to resume execution for one step-out (of
+     * the given granularity).
+     */
+    pub thread_id: usize,
+
+    /**
+     * If this flag is true, execution is resumed only for the thread with given
+     * 'threadId'. The attribute is only honored by a debug adapter if the
+     * capability 'supportsSingleThreadExecutionRequests' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub single_thread: Option<bool>,
+
+    /**
+     * Optional granularity to step. If no granularity is specified, a
+     * granularity of 'statement' is assumed.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<SteppingGranularity>,
+}
+
+impl StepOutArguments {
+    pub fn new(thread_id: usize) -> Self {
+        Self {
+            thread_id,
+            single_thread: None,
+            granularity: None,
+        }
+    }
+}
+
+/// The request executes one backward step (in the given granularity) for the specified thread and allows all other threads to run backward freely by resuming them.
+///
+/// If the debug adapter supports single thread execution (see capability ‘supportsSingleThreadExecutionRequests’), setting the ‘singleThread’ argument to true prevents other suspended threads from resuming.
+///
+/// The debug adapter first sends the response and then a ‘stopped’ event (with reason ‘step’) after the step has completed.
+///
+/// Clients should only call this request if the capability ‘supportsStepBack’ is true.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepBackRequest {
+    pub(crate) arguments: StepBackArguments,
+}
+
+impl StepBackRequest {
+    pub fn arguments(&self) -> &StepBackArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepBackArguments {
+    /**
+     * Specifies the thread for which to resume execution for one step backwards
+     * (of the given granularity).
+     */
+    pub thread_id: usize,
+
+    /**
+     * If this flag is true, execution is resumed only for the thread with given
+     * 'threadId'. The attribute is only honored by a debug adapter if the
+     * capability 'supportsSingleThreadExecutionRequests' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub single_thread: Option<bool>,
+
+    /**
+     * Optional granularity to step. If no granularity is specified, a
+     * granularity of 'statement' is assumed.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<SteppingGranularity>,
+}
+
+impl StepBackArguments {
+    pub fn new(thread_id: usize) -> Self {
+        Self {
+            thread_id,
+            single_thread: None,
+            granularity: None,
+        }
+    }
+}
+
+/// The request resumes backward execution of all threads. If the debug adapter supports single thread execution (see capability ‘supportsSingleThreadExecutionRequests’), setting the ‘singleThread’ argument to true resumes only the specified thread.
+///
+/// If not all threads were resumed, the ‘allThreadsContinued’ attribute of the response should be set to false.
+///
+/// Clients should only call this request if the capability ‘supportsStepBack’ is true.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReverseContinueRequest {
+    pub(crate) arguments: ReverseContinueArguments,
+}
+
+impl ReverseContinueRequest {
+    pub fn arguments(&self) -> &ReverseContinueArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReverseContinueArguments {
+    /**
+     * Specifies the active thread. If the debug adapter supports single thread
+     * execution (see 'supportsSingleThreadExecutionRequests') and the
+     * 'singleThread' argument is true, only the thread with this ID is resumed.
+     */
+    pub thread_id: usize,
+
+    /**
+     * If this flag is true, execution is resumed only for the thread with given
+     * 'threadId'. The attribute is only honored by a debug adapter if the
+     * capability 'supportsSingleThreadExecutionRequests' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub single_thread: Option<bool>,
+}
+
+impl ReverseContinueArguments {
+    pub fn new(thread_id: usize) -> Self {
+        Self {
+            thread_id,
+            single_thread: None,
+        }
+    }
+}
+
+/// Sets multiple breakpoints for a single source and clears all previous breakpoints in that source.
+///
+/// To clear all breakpoint for a source, specify an empty array.
+///
+/// When a breakpoint is hit, a ‘stopped’ event (with reason ‘breakpoint’) is generated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetBreakpointsRequest {
+    pub(crate) arguments: SetBreakpointsArguments,
+}
+
+impl SetBreakpointsRequest {
+    pub fn arguments(&self) -> &SetBreakpointsArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetBreakpointsArguments {
+    /**
+     * The source location of the breakpoints; either 'source.path' or
+     * 'source.reference' must be specified.
+     */
+    pub source: Source,
+
+    /**
+     * The code locations of the breakpoints.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub breakpoints: Option<Vec<SourceBreakpoint>>,
+
+    /**
+     * Deprecated: The code locations of the breakpoints.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lines: Option<Vec<usize>>,
+
+    /**
+     * A value of true indicates that the underlying source has been modified
+     * which results in new breakpoint locations.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_modified: Option<bool>,
+}
+
+impl SetBreakpointsArguments {
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            breakpoints: None,
+            lines: None,
+            source_modified: None,
+        }
+    }
+
+    /// The breakpoints the source should have once the request is handled.
+    ///
+    /// `breakpoints` is preferred over the deprecated `lines`, which only old clients send.
+    /// A empty result means every breakpoint of the source should be cleared.
+    pub fn effective_breakpoints(&self) -> Vec<SourceBreakpoint> {
+        match (&self.breakpoints, &self.lines) {
+            (Some(breakpoints), _) => breakpoints.clone(),
+            (None, Some(lines)) => lines.iter().copied().map(SourceBreakpoint::new).collect(),
+            (None, None) => Vec::new(),
+        }
+    }
+}
+
+/// The request returns a stacktrace from the current execution state of a given thread.
+///
+/// A client can request all stack frames by omitting the startFrame and levels arguments. For performance conscious clients and if the debug adapter’s ‘supportsDelayedStackTraceLoading’ capability is true, stack frames can be retrieved in a piecemeal way with the startFrame and levels arguments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StackTraceRequest {
+    pub(crate) arguments: StackTraceArguments,
+}
+
+impl StackTraceRequest {
+    pub fn arguments(&self) -> &StackTraceArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackTraceArguments {
+    /**
+     * Retrieve the stacktrace for this thread.
+     */
+    pub thread_id: usize,
+
+    /**
+     * The index of the first frame to return; if omitted frames start at 0.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_frame: Option<usize>,
+
+    /**
+     * The maximum number of frames to return. If levels is not specified or 0,
+     * all frames are returned.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub levels: Option<usize>,
+
+    /**
+     * Specifies details on how to format the stack frames.
+     * The attribute is only honored by a debug adapter if the capability
+     * 'supportsValueFormattingOptions' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<StackFrameFormat>,
+}
+
+impl StackTraceArguments {
+    pub fn new(thread_id: usize) -> Self {
+        Self {
+            thread_id,
+            start_frame: None,
+            levels: None,
+            format: None,
+        }
+    }
+}
+
+/// Retrieves all child variables for the given variable reference.
+///
+/// An optional filter can be used to limit the fetched children to either named or indexed children.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariablesRequest {
+    pub(crate) arguments: VariablesArguments,
+}
+
+impl VariablesRequest {
+    pub fn arguments(&self) -> &VariablesArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariablesArguments {
+    /**
+     * The Variable reference.
+     */
+    pub variables_reference: usize,
+
+    /**
+     * Optional filter to limit the child variables to either named or indexed.
+     * If omitted, both types are fetched.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<VariablesArgumentsFilter>,
+
+    /**
+     * The index of the first variable to return; if omitted children start at 0.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<usize>,
+
+    /**
+     * The number of variables to return. If count is missing or 0, all variables
+     * are returned.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<usize>,
+
+    /**
+     * Specifies details on how to format the Variable values.
+     * The attribute is only honored by a debug adapter if the capability
+     * 'supportsValueFormattingOptions' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<ValueFormat>,
+}
+
+impl VariablesArguments {
+    pub fn new(variables_reference: usize) -> Self {
+        Self {
+            variables_reference,
+            filter: None,
+            start: None,
+            count: None,
+            format: None,
+        }
+    }
+}
+
+/// The kind of child variables fetched by a ‘variables’ request.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VariablesArgumentsFilter {
+    Indexed,
+    Named,
+    /// A filter which is not part of the specification.
+    #[serde(untagged)]
+    Other(String),
+}
+
+/// Evaluates the given expression in the context of the top most stack frame.
+///
+/// The expression has access to any variables and arguments that are in scope.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvaluateRequest {
+    pub(crate) arguments: EvaluateArguments,
+}
+
+impl EvaluateRequest {
+    pub fn arguments(&self) -> &EvaluateArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvaluateArguments {
+    /**
+     * The expression to evaluate.
+     */
+    pub expression: String,
+
+    /**
+     * Evaluate the expression in the scope of this stack frame. If not specified,
+     * the expression is evaluated in the global scope.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frame_id: Option<usize>,
+
+    /**
+     * The context in which the evaluate request is run.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<EvaluateArgumentsContext>,
+
+    /**
+     * Specifies details on how to format the Evaluate result.
+     * The attribute is only honored by a debug adapter if the capability
+     * 'supportsValueFormattingOptions' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<ValueFormat>,
+}
+
+impl EvaluateArguments {
+    pub fn new(expression: impl Into<String>) -> Self {
+        Self {
+            expression: expression.into(),
+            frame_id: None,
+            context: None,
+            format: None,
+        }
+    }
+}
+
+/// The context in which a ‘evaluate’ request is run.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EvaluateArgumentsContext {
+    /// evaluate is run in a watch.
+    Watch,
+    /// evaluate is run from REPL console.
+    Repl,
+    /// evaluate is run from a data hover.
+    Hover,
+    /// evaluate is run to generate the value that will be stored in the clipboard.
+    /// The attribute is only honored by a debug adapter if the capability ‘supportsClipboardContext’ is true.
+    Clipboard,
+    /// A context which is not part of the specification.
+    #[serde(untagged)]
+    Other(String),
+}
+
+/// The request retrieves a list of all threads.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreadsRequest;
+
+/// Retrieves the set of all sources currently loaded by the debugged process.
+///
+/// Clients should only call this request if the capability ‘supportsLoadedSourcesRequest’ is true.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadedSourcesRequest;
+
+/// The request terminates the threads with the given ids.
+///
+/// Clients should only call this request if the capability ‘supportsTerminateThreadsRequest’ is true.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerminateThreadsRequest {
+    pub(crate) arguments: TerminateThreadsArguments,
+}
+
+impl TerminateThreadsRequest {
+    pub fn arguments(&self) -> &TerminateThreadsArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminateThreadsArguments {
+    /**
+     * Ids of threads to be terminated, as found in `Thread::id`.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_ids: Option<Vec<usize>>,
+}
+
+/// Reads bytes from memory at the provided location.
+///
+/// Clients should only call this request if the capability ‘supportsReadMemoryRequest’ is true.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadMemoryRequest {
+    pub(crate) arguments: ReadMemoryArguments,
+}
+
+impl ReadMemoryRequest {
+    pub fn arguments(&self) -> &ReadMemoryArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadMemoryArguments {
+    /**
+     * Memory reference to the base location from which data should be read.
+     */
+    pub memory_reference: String,
+
+    /**
+     * Optional offset (in bytes) to be applied to the reference location before
+     * reading data. Can be negative.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+
+    /**
+     * Number of bytes to read at the specified location and offset.
+     */
+    pub count: usize,
+}
+
+impl ReadMemoryArguments {
+    pub fn new(memory_reference: impl Into<String>, count: usize) -> Self {
+        Self {
+            memory_reference: memory_reference.into(),
+            offset: None,
+            count,
+        }
+    }
+}
+
+/// Writes bytes to memory at the provided location.
+///
+/// Clients should only call this request if the capability ‘supportsWriteMemoryRequest’ is true.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WriteMemoryRequest {
+    pub(crate) arguments: WriteMemoryArguments,
+}
+
+impl WriteMemoryRequest {
+    pub fn arguments(&self) -> &WriteMemoryArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteMemoryArguments {
+    /**
+     * Memory reference to the base location to which data should be written.
+     */
+    pub memory_reference: String,
+
+    /**
+     * Optional offset (in bytes) to be applied to the reference location before
+     * writing data. Can be negative.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+
+    /**
+     * Optional property to control partial writes. If true, the debug adapter
+     * should attempt to write memory even if the entire memory region is not
+     * writable. In such a case the debug adapter should stop after hitting the
+     * first byte of memory that cannot be written and return the number of bytes
+     * written in the response via the 'offset' and 'bytesWritten' properties.
+     * If false or missing, a debug adapter should attempt to verify the region is
+     * writable before writing, and fail the response if it is not.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_partial: Option<bool>,
+
+    /**
+     * Bytes to write, encoded using base64.
+     */
+    pub data: String,
+}
+
+impl WriteMemoryArguments {
+    pub fn new(memory_reference: impl Into<String>, data: impl Into<String>) -> Self {
+        Self {
+            memory_reference: memory_reference.into(),
+            offset: None,
+            allow_partial: None,
+            data: data.into(),
+        }
+    }
+
+    /// Decode the bytes to write, which are encoded with the standard base64 alphabet.
+    pub fn data_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.data_bytes_with::<StandardBase64>()
+    }
+
+    /// Decode the bytes to write with the base64 engine `E`.
+    pub fn data_bytes_with<E: Base64Engine>(&self) -> Result<Vec<u8>, Error> {
+        decode_base64::<E>(&self.data)
+    }
+}
+
+/// Disassembles code stored at the provided location.
+///
+/// Clients should only call this request if the capability ‘supportsDisassembleRequest’ is true.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisassembleRequest {
+    pub(crate) arguments: DisassembleArguments,
+}
+
+impl DisassembleRequest {
+    pub fn arguments(&self) -> &DisassembleArguments {
+        &self.arguments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisassembleArguments {
+    /**
+     * Memory reference to the base location containing the instructions to
+     * disassemble.
+     */
+    pub memory_reference: String,
+
+    /**
+     * Optional offset (in bytes) to be applied to the reference location before
+     * disassembling. Can be negative.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+
+    /**
+     * Optional offset (in instructions) to be applied after the byte offset (if
+     * any) before disassembling. Can be negative.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instruction_offset: Option<i64>,
+
+    /**
+     * Number of instructions to disassemble starting at the specified location
+     * and offset.
+     * An adapter must return exactly this number of instructions - any
+     * unavailable instructions should be replaced with an implementation-defined
+     * 'invalid instruction' value.
+     */
+    pub instruction_count: usize,
+
+    /**
+     * If true, the adapter should attempt to resolve memory addresses and other
+     * values to symbolic names.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolve_symbols: Option<bool>,
+}
+
+impl DisassembleArguments {
+    pub fn new(memory_reference: impl Into<String>, instruction_count: usize) -> Self {
+        Self {
+            memory_reference: memory_reference.into(),
+            offset: None,
+            instruction_offset: None,
+            instruction_count,
+            resolve_symbols: None,
+        }
+    }
+}
+
+/// The arguments of a request, which know the command they are sent with.
+///
+/// This lets a client send a request from its arguments alone, see `DapClient::send_request`.
+pub trait RequestArguments: Serialize {
+    /// The `command` field of the request.
+    const COMMAND: &'static str;
+}
+
+macro_rules! request_arguments {
+    ($($arguments:ident => $command:literal,)*) => {
+        $(
+            impl RequestArguments for $arguments {
+                const COMMAND: &'static str = $command;
+            }
+        )*
+    };
+}
+
+request_arguments! {
+    AttachArguments => "attach",
+    BreakpointLocationsArguments => "breakpointLocations",
+    CancelArguments => "cancel",
+    CompletionsArguments => "completions",
+    ConfigurationDoneArguments => "configurationDone",
+    ContinueArguments => "continue",
+    DataBreakpointInfoArguments => "dataBreakpointInfo",
+    DisassembleArguments => "disassemble",
+    DisconnectArguments => "disconnect",
+    EvaluateArguments => "evaluate",
+    GotoTargetsArguments => "gotoTargets",
+    InitializeRequestArguments => "initialize",
+    LaunchArguments => "launch",
+    ModulesArguments => "modules",
+    NextArguments => "next",
+    ReadMemoryArguments => "readMemory",
+    ReverseContinueArguments => "reverseContinue",
+    RunInTerminalRequestArguments => "runInTerminal",
+    SetBreakpointsArguments => "setBreakpoints",
+    SetExceptionBreakpointsArguments => "setExceptionBreakpoints",
+    SourceArguments => "source",
+    StackTraceArguments => "stackTrace",
+    StartDebuggingRequestArguments => "startDebugging",
+    StepBackArguments => "stepBack",
+    StepInArguments => "stepIn",
+    StepInTargetsArguments => "stepInTargets",
+    StepOutArguments => "stepOut",
+    TerminateArguments => "terminate",
+    TerminateThreadsArguments => "terminateThreads",
+    VariablesArguments => "variables",
+    WriteMemoryArguments => "writeMemory",
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn initialize_request_valid() {
+        let arg = r#"{
+            "adapterID": "headcrab-rs",
+            "clientID": "vscode",
+            "clientName": "Visual Studio Code",
+            "columnsStartAt1": true,
+            "linesStartAt1": true,
+            "locale": "en-us",
+            "pathFormat": "path",
+            "supportsInvalidatedEvent": true,
+            "supportsMemoryReferences": true,
+            "supportsProgressReporting": true,
+            "supportsRunInTerminalRequest": true,
+            "supportsVariablePaging": true,
+            "supportsVariableType": true
+          }"#;
+
+        let arguments: InitializeRequestArguments = serde_json::from_str(arg).unwrap();
+
+        assert_eq!(arguments.adapter_id, "headcrab-rs");
+        assert_eq!(arguments.client_id.as_deref(), Some("vscode"));
+        assert_eq!(arguments.columns_start_at1, Some(true));
+        assert_eq!(arguments.path_format, Some(PathFormat::Path));
+        assert_eq!(arguments.supports_variable_paging, Some(true));
+        assert_eq!(arguments.supports_invalidated_event, Some(true));
+
+        let value = serde_json::to_value(&arguments).unwrap();
+        assert_eq!(
+            value,
+            serde_json::from_str::<serde_json::Value>(arg).unwrap()
+        );
+    }
+
+    #[test]
+    fn set_breakpoints_arguments_valid() {
+        let arg = r#"{
+            "source": { "name": "main.rs", "path": "/home/user/project/src/main.rs" },
+            "lines": [5, 12, 20],
+            "breakpoints": [
+                { "line": 5 },
+                { "line": 12, "condition": "x > 1" },
+                { "line": 20, "logMessage": "x = {x}" }
+            ],
+            "sourceModified": false
+          }"#;
+
+        let arguments: SetBreakpointsArguments = serde_json::from_str(arg).unwrap();
+
+        assert_eq!(arguments.source.name.as_deref(), Some("main.rs"));
+        assert_eq!(arguments.lines, Some(vec![5, 12, 20]));
+        let breakpoints = arguments.breakpoints.unwrap();
+        assert_eq!(breakpoints[1].condition.as_deref(), Some("x > 1"));
+        assert_eq!(breakpoints[2].log_message.as_deref(), Some("x = {x}"));
+        assert_eq!(arguments.source_modified, Some(false));
+    }
+
+    #[test]
+    fn set_breakpoints_effective_breakpoints() {
+        let mut arguments = SetBreakpointsArguments::new(Source::default());
+        assert!(arguments.effective_breakpoints().is_empty());
+
+        arguments.lines = Some(vec![3, 7]);
+        assert_eq!(
+            arguments.effective_breakpoints(),
+            vec![SourceBreakpoint::new(3), SourceBreakpoint::new(7)]
+        );
+
+        let conditional = SourceBreakpoint {
+            condition: Some("x > 1".to_string()),
+            ..SourceBreakpoint::new(12)
+        };
+        arguments.breakpoints = Some(vec![conditional.clone()]);
+        assert_eq!(arguments.effective_breakpoints(), vec![conditional.clone()]);
+
+        arguments.lines = None;
+        assert_eq!(arguments.effective_breakpoints(), vec![conditional]);
+
+        // a empty list of breakpoints clear the source, even if lines are given
+        arguments.breakpoints = Some(Vec::new());
+        arguments.lines = Some(vec![3]);
+        assert!(arguments.effective_breakpoints().is_empty());
+    }
+
+    #[test]
+    fn stack_trace_arguments_valid() {
+        let arg = r#"{ "threadId": 1, "startFrame": 0, "levels": 20 }"#;
+
+        let arguments: StackTraceArguments = serde_json::from_str(arg).unwrap();
+
+        assert_eq!(arguments.thread_id, 1);
+        assert_eq!(arguments.start_frame, Some(0));
+        assert_eq!(arguments.levels, Some(20));
+        assert_eq!(arguments.format, None);
+    }
+
+    #[test]
+    fn evaluate_arguments_valid() {
+        let arg = r#"{ "expression": "x + 1", "frameId": 1000, "context": "hover" }"#;
+
+        let arguments: EvaluateArguments = serde_json::from_str(arg).unwrap();
+
+        assert_eq!(arguments.expression, "x + 1");
+        assert_eq!(arguments.frame_id, Some(1000));
+        assert_eq!(arguments.context, Some(EvaluateArgumentsContext::Hover));
+
+        let arg = r#"{ "expression": "x", "context": "variables" }"#;
+        let arguments: EvaluateArguments = serde_json::from_str(arg).unwrap();
+        assert_eq!(
+            arguments.context,
+            Some(EvaluateArgumentsContext::Other("variables".to_string()))
+        );
+    }
+
+    #[test]
+    fn variables_arguments_valid() {
+        let arg =
+            r#"{ "variablesReference": 1001, "filter": "indexed", "start": 0, "count": 100 }"#;
+
+        let arguments: VariablesArguments = serde_json::from_str(arg).unwrap();
+
+        assert_eq!(arguments.variables_reference, 1001);
+        assert_eq!(arguments.filter, Some(VariablesArgumentsFilter::Indexed));
+        assert_eq!(arguments.count, Some(100));
+    }
+
+    #[test]
+    fn cancel_arguments_valid() {
+        let arg = r#"{ "requestId": 3, "progressId": "build" }"#;
+
+        let arguments: CancelArguments = serde_json::from_str(arg).unwrap();
+
+        assert_eq!(arguments.request_id, Some(3));
+        assert_eq!(arguments.progress_id, Some("build".to_string()));
+    }
+
+    #[test]
+    fn source_arguments_valid() {
+        let arg = r#"{ "source": { "sourceReference": 7 }, "sourceReference": 7 }"#;
+
+        let arguments: SourceArguments = serde_json::from_str(arg).unwrap();
+
+        assert_eq!(arguments.source_reference, 7);
+    }
+
+    #[test]
+    fn run_in_terminal_arguments_valid() {
+        let arg = r#"{
+            "kind": "integrated",
+            "title": "debuggee",
+            "cwd": "/tmp",
+            "args": ["./a.out", "--verbose"],
+            "env": { "RUST_LOG": "debug", "HOME": null }
+          }"#;
+
+        let arguments: RunInTerminalRequestArguments = serde_json::from_str(arg).unwrap();
+
+        assert_eq!(arguments.kind, Some(RunInTerminalKind::Integrated));
+        assert_eq!(arguments.args, vec!["./a.out", "--verbose"]);
+
+        let env = arguments.env.unwrap();
+        assert_eq!(env["RUST_LOG"], Some("debug".to_string()));
+        assert_eq!(env["HOME"], None);
+    }
+
+    #[test]
+    fn start_debugging_arguments_valid() {
+        let arg = r#"{ "configuration": { "pid": 4242, "name": "worker" }, "request": "attach" }"#;
+
+        let arguments: StartDebuggingRequestArguments = serde_json::from_str(arg).unwrap();
+
+        assert_eq!(arguments.request, StartDebuggingRequestKind::Attach);
+        assert_eq!(arguments.configuration["pid"], 4242);
+    }
+
+    #[test]
+    fn terminate_threads_arguments_valid() {
+        let arg = r#"{ "threadIds": [3, 4] }"#;
+
+        let arguments: TerminateThreadsArguments = serde_json::from_str(arg).unwrap();
+
+        assert_eq!(arguments.thread_ids, Some(vec![3, 4]));
+    }
+
+    #[test]
+    fn completions_arguments_valid() {
+        let arg = r#"{ "frameId": 1000, "text": "self.co", "column": 8 }"#;
+        let arguments: CompletionsArguments = serde_json::from_str(arg).unwrap();
+
+        assert_eq!(arguments.frame_id, Some(1000));
+        assert_eq!(arguments.text, "self.co");
+        assert_eq!(arguments.column, 8);
+        assert_eq!(arguments.line, None);
+    }
+
+    #[test]
+    fn goto_targets_arguments_valid() {
+        let arg = r#"{ "source": { "path": "/src/main.rs" }, "line": 12 }"#;
+        let arguments: GotoTargetsArguments = serde_json::from_str(arg).unwrap();
+
+        assert_eq!(arguments.source.path.as_deref(), Some("/src/main.rs"));
+        assert_eq!(arguments.line, 12);
+        assert_eq!(arguments.column, None);
+    }
+
+    #[test]
+    fn data_breakpoint_info_arguments_valid() {
+        let arg = r#"{ "variablesReference": 1001, "name": "counter" }"#;
+        let arguments: DataBreakpointInfoArguments = serde_json::from_str(arg).unwrap();
+
+        assert_eq!(
+            arguments,
+            DataBreakpointInfoArguments {
+                variables_reference: Some(1001),
+                ..DataBreakpointInfoArguments::new("counter")
+            }
+        );
+    }
+
+    #[test]
+    fn read_memory_arguments_valid() {
+        let arg = r#"{ "memoryReference": "0x1000", "offset": -16, "count": 32 }"#;
+        let arguments: ReadMemoryArguments = serde_json::from_str(arg).unwrap();
+
+        assert_eq!(arguments.memory_reference, "0x1000");
+        assert_eq!(arguments.offset, Some(-16));
+        assert_eq!(arguments.count, 32);
+    }
+
+    #[test]
+    fn write_memory_arguments_valid() {
+        let arg = r#"{ "memoryReference": "0x1000", "offset": -4, "data": "kJA=" }"#;
+        let arguments: WriteMemoryArguments = serde_json::from_str(arg).unwrap();
+
+        assert_eq!(arguments.offset, Some(-4));
+        assert_eq!(arguments.allow_partial, None);
+        assert_eq!(arguments.data, "kJA=");
+        assert_eq!(arguments.data_bytes().unwrap(), [0x90, 0x90]);
+
+        let arguments = WriteMemoryArguments::new("0x1000", "kJA");
+        assert!(arguments.data_bytes().is_err());
+    }
+
+    #[test]
+    fn disassemble_arguments_negative_instruction_offset() {
+        // what a client send when scrolling up from the program counter
+        let arg = r#"{
+            "memoryReference": "0x401000",
+            "offset": 0,
+            "instructionOffset": -50,
+            "instructionCount": 50,
+            "resolveSymbols": true
+        }"#;
+        let arguments: DisassembleArguments = serde_json::from_str(arg).unwrap();
+
+        assert_eq!(arguments.offset, Some(0));
+        assert_eq!(arguments.instruction_offset, Some(-50));
+        assert_eq!(arguments.instruction_count, 50);
+        assert_eq!(arguments.resolve_symbols, Some(true));
+
+        let value = serde_json::to_value(&arguments).unwrap();
+        assert_eq!(value["instructionOffset"], serde_json::json!(-50));
+    }
+
+    #[test]
+    fn launch_arguments_valid() {
+        let arg = r#"{ "noDebug": true, "program": "/bin/ls", "args": ["-l"] }"#;
+        let arguments: LaunchArguments = serde_json::from_str(arg).unwrap();
+
+        assert_eq!(arguments.no_debug, Some(true));
+        assert_eq!(arguments.restart, None);
+        assert_eq!(arguments.extra["program"], "/bin/ls");
+        assert_eq!(
+            serde_json::to_value(&arguments).unwrap(),
+            serde_json::from_str::<serde_json::Value>(arg).unwrap()
+        );
+    }
+
+    #[test]
+    fn launch_arguments_parse_extra() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct MyLaunchConfig {
+            program: String,
+            #[serde(default)]
+            args: Vec<String>,
+            cwd: Option<String>,
+            env: HashMap<String, String>,
+        }
+
+        let arg = r#"{
+            "program": "/usr/bin/grep",
+            "args": ["-r", "todo"],
+            "env": { "LANG": "C" },
+            "noDebug": false,
+            "__restart": { "generation": 2 }
+          }"#;
+        let arguments: LaunchArguments = serde_json::from_str(arg).unwrap();
+
+        let config: MyLaunchConfig = arguments.parse_extra().unwrap();
+        assert_eq!(
+            config,
+            MyLaunchConfig {
+                program: "/usr/bin/grep".to_string(),
+                args: vec!["-r".to_string(), "todo".to_string()],
+                cwd: None,
+                env: vec![("LANG".to_string(), "C".to_string())]
+                    .into_iter()
+                    .collect(),
+            }
+        );
+
+        // the restart data is kept, so it can be sent back to the client untouched
+        assert_eq!(
+            arguments.restart,
+            Some(serde_json::json!({ "generation": 2 }))
+        );
+        assert_eq!(
+            serde_json::to_value(&arguments).unwrap()["__restart"],
+            serde_json::json!({ "generation": 2 })
+        );
+
+        // a configuration missing a required attribute is rejected
+        let arguments: LaunchArguments = serde_json::from_str(r#"{ "args": [] }"#).unwrap();
+        match arguments.parse_extra::<MyLaunchConfig>() {
+            Err(Error::InvalidMessage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn attach_arguments_parse_extra() {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct MyAttachConfig {
+            process_id: u32,
+        }
+
+        let arg = r#"{ "processId": 4242, "__restart": "token" }"#;
+        let arguments: AttachArguments = serde_json::from_str(arg).unwrap();
+
+        let config: MyAttachConfig = arguments.parse_extra().unwrap();
+        assert_eq!(config.process_id, 4242);
+        assert_eq!(arguments.restart, Some(serde_json::json!("token")));
+    }
+
+    #[test]
+    fn restart_arguments_launch_round_trip() {
+        let arg = r#"{
+            "arguments": {
+                "type": "headcrab",
+                "request": "launch",
+                "program": "/bin/ls",
+                "cwd": "/tmp",
+                "__restart": { "pid": 12 }
+            }
+          }"#;
+        let arguments: RestartArguments = serde_json::from_str(arg).unwrap();
+
+        let launch = match &arguments {
+            RestartArguments::Launch(launch) => launch,
+            _ => panic!(),
+        };
+        assert_eq!(launch.restart, Some(serde_json::json!({ "pid": 12 })));
+        assert_eq!(launch.extra["cwd"], "/tmp");
+        assert_eq!(launch.extra["type"], "headcrab");
+
+        let value = serde_json::to_value(&arguments).unwrap();
+        assert_eq!(
+            value,
+            serde_json::from_str::<serde_json::Value>(arg).unwrap()
+        );
+        assert_eq!(
+            serde_json::from_value::<RestartArguments>(value).unwrap(),
+            arguments
+        );
+    }
+
+    #[test]
+    fn restart_arguments_attach_round_trip() {
+        // older clients pass the configuration directly
+        let arg = r#"{ "processId": 4242, "symbolPath": "/usr/lib/debug" }"#;
+        let arguments: RestartArguments = serde_json::from_str(arg).unwrap();
+
+        let attach = match &arguments {
+            RestartArguments::Attach(attach) => attach,
+            _ => panic!(),
+        };
+        assert_eq!(attach.extra["processId"], 4242);
+        assert_eq!(attach.extra["symbolPath"], "/usr/lib/debug");
+
+        let value = serde_json::to_value(&arguments).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({ "arguments": { "processId": 4242, "symbolPath": "/usr/lib/debug" } })
+        );
+        assert_eq!(
+            serde_json::from_value::<RestartArguments>(value).unwrap(),
+            arguments
+        );
+    }
+
+    #[test]
+    fn restart_arguments_ambiguous() {
+        let arg = r#"{ "arguments": { "program": "/bin/ls", "pid": 12 } }"#;
+        let arguments: RestartArguments = serde_json::from_str(arg).unwrap();
+
+        assert_eq!(
+            arguments,
+            RestartArguments::Other(serde_json::json!({ "program": "/bin/ls", "pid": 12 }))
+        );
+        assert_eq!(
+            serde_json::to_value(&arguments).unwrap(),
+            serde_json::from_str::<serde_json::Value>(arg).unwrap()
+        );
+    }
+
+    #[test]
+    fn execution_arguments_single_thread() {
+        let arguments: ContinueArguments =
+            serde_json::from_str(r#"{ "threadId": 1, "singleThread": true }"#).unwrap();
+        assert_eq!(arguments.single_thread, Some(true));
+
+        let arguments: StepInArguments = serde_json::from_str(
+            r#"{ "threadId": 2, "singleThread": true, "targetId": 7, "granularity": "instruction" }"#,
+        )
+        .unwrap();
+        assert_eq!(arguments.single_thread, Some(true));
+        assert_eq!(arguments.target_id, Some(7));
+        assert_eq!(
+            arguments.granularity,
+            Some(SteppingGranularity::Instruction)
+        );
+
+        // payloads from clients older than the flag still parse
+        let old = r#"{ "threadId": 3 }"#;
+        let arguments: ContinueArguments = serde_json::from_str(old).unwrap();
+        assert_eq!(arguments.single_thread, None);
+        let arguments: NextArguments = serde_json::from_str(old).unwrap();
+        assert_eq!(arguments.single_thread, None);
+        assert_eq!(arguments.granularity, None);
+        let arguments: StepOutArguments = serde_json::from_str(old).unwrap();
+        assert_eq!(arguments.single_thread, None);
+        let arguments: StepBackArguments = serde_json::from_str(old).unwrap();
+        assert_eq!(arguments.single_thread, None);
+        let arguments: ReverseContinueArguments = serde_json::from_str(old).unwrap();
+        assert_eq!(arguments.thread_id, 3);
+        assert_eq!(arguments.single_thread, None);
+
+        assert_eq!(
+            serde_json::to_value(StepInArguments::new(3)).unwrap(),
+            serde_json::json!({ "threadId": 3 })
+        );
+    }
+
+    #[test]
+    fn compare_arguments() {
+        let arg = r#"{ "expression": "x + 1", "frameId": 2, "context": "watch" }"#;
+        let first: EvaluateArguments = serde_json::from_str(arg).unwrap();
+        let second: EvaluateArguments = serde_json::from_str(arg).unwrap();
+        assert_eq!(first, second);
+        assert_ne!(first, EvaluateArguments::new("x + 1"));
+
+        let contexts: std::collections::HashSet<_> = vec![
+            EvaluateArgumentsContext::Watch,
+            EvaluateArgumentsContext::Other("variables".to_string()),
+            EvaluateArgumentsContext::Watch,
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(contexts.len(), 2);
+    }
+
+    #[test]
+    fn build_arguments() {
+        let arguments = StackTraceArguments {
+            levels: Some(20),
+            ..StackTraceArguments::new(1)
+        };
+        assert_eq!(
+            serde_json::to_value(&arguments).unwrap(),
+            serde_json::json!({ "threadId": 1, "levels": 20 })
+        );
+
+        let arguments = InitializeRequestArguments {
+            lines_start_at1: Some(false),
+            ..InitializeRequestArguments::new("headcrab-rs")
+        };
+        assert_eq!(
+            serde_json::to_value(&arguments).unwrap(),
+            serde_json::json!({ "adapterID": "headcrab-rs", "linesStartAt1": false })
+        );
+
+        let arguments = CancelArguments {
+            request_id: Some(3),
+            ..CancelArguments::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&arguments).unwrap(),
+            serde_json::json!({ "requestId": 3 })
+        );
+    }
+
+    proptest! {
+        // any string is accepted, and serialized back unchanged
+        #[test]
+        fn evaluate_arguments_context_round_trip(context in "\\PC*") {
+            let value = serde_json::Value::String(context);
+            let parsed: EvaluateArgumentsContext = serde_json::from_value(value.clone()).unwrap();
+            prop_assert_eq!(serde_json::to_value(&parsed).unwrap(), value);
+        }
+
+        // except for the known formats, whose case is normalized
+        #[test]
+        fn path_format_round_trip(format in "\\PC*") {
+            let lowercase = format.to_ascii_lowercase();
+            prop_assume!(lowercase != "path" && lowercase != "uri");
+            let value = serde_json::Value::String(format);
+            let parsed: PathFormat = serde_json::from_value(value.clone()).unwrap();
+            prop_assert_eq!(serde_json::to_value(&parsed).unwrap(), value);
+        }
+
+        #[test]
+        fn variables_arguments_filter_round_trip(filter in "\\PC*") {
+            let value = serde_json::Value::String(filter);
+            let parsed: VariablesArgumentsFilter = serde_json::from_value(value.clone()).unwrap();
+            prop_assert_eq!(serde_json::to_value(&parsed).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn path_format_case_insensitive() {
+        for format in ["PATH", "Path", "path"].iter() {
+            let parsed: PathFormat = serde_json::from_value(serde_json::json!(format)).unwrap();
+            assert_eq!(parsed, PathFormat::Path);
+        }
+        for format in ["Uri", "URI", "uri"].iter() {
+            let parsed: PathFormat = serde_json::from_value(serde_json::json!(format)).unwrap();
+            assert_eq!(parsed, PathFormat::Uri);
+        }
+
+        // the normalized form is sent back
+        assert_eq!(
+            serde_json::to_value(PathFormat::Uri).unwrap(),
+            serde_json::json!("uri")
+        );
+        let parsed: PathFormat = serde_json::from_str(r#""URL""#).unwrap();
+        assert_eq!(parsed, PathFormat::Other("URL".to_string()));
+    }
+
+    #[test]
+    fn known_strings_are_not_other() {
+        let parsed: EvaluateArgumentsContext = serde_json::from_str(r#""clipboard""#).unwrap();
+        assert_eq!(parsed, EvaluateArgumentsContext::Clipboard);
+        let parsed: PathFormat = serde_json::from_str(r#""uri""#).unwrap();
+        assert_eq!(parsed, PathFormat::Uri);
+        let parsed: VariablesArgumentsFilter = serde_json::from_str(r#""named""#).unwrap();
+        assert_eq!(parsed, VariablesArgumentsFilter::Named);
+    }
+}
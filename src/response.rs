@@ -0,0 +1,956 @@
+//! The responses, and their bodies.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::util::{decode_base64, encode_base64};
+use crate::{
+    Base64Engine, Breakpoint, BreakpointLocation, BreakpointLocationsArguments, CompletionItem,
+    ContinueArguments, DataBreakpointAccessType, DisassembledInstruction, Error, ErrorMessage,
+    GotoTarget, Module, ReadMemoryArguments, Request, SetExceptionBreakpointsArguments, Source,
+    StandardBase64, StepInTarget, Thread,
+};
+
+/// Response for a request.
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub(crate) response_info: ResponseInfo,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct ResponseInfo {
+    /**
+     * Sequence number of the response.
+     */
+    pub(crate) seq: usize,
+
+    /**
+     * Sequence number of the corresponding request.
+     */
+    pub(crate) request_seq: usize,
+
+    /**
+     * Outcome of the request.
+     * If true, the request was successful and the 'body' attribute may contain
+     * the result of the request.
+     * If the value is false, the attribute 'message' contains the error in short
+     * form and the 'body' may contain additional information (see
+     * 'ErrorResponse.body.error').
+     */
+    pub(crate) success: bool,
+
+    /**
+     * The command requested.
+     */
+    pub(crate) command: String,
+
+    /**
+     * Contains the raw error in short form if 'success' is false.
+     * This raw error might be interpreted by the frontend and is not shown in the
+     * UI.
+     * Some predefined values exist.
+     * Values:
+     * 'cancelled': request was cancelled.
+     * etc.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) message: Option<String>,
+
+    /**
+     * Contains request result if success is true and optional error details if
+     * success is false.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) body: Option<serde_json::Value>,
+}
+
+impl Response {
+    pub fn builder() -> ResponseBuilder {
+        ResponseBuilder::default()
+    }
+
+    /// Create a successful response to `request`, carrying `body` as its result.
+    ///
+    /// A body serialized as `null`, such as `()`, is omitted.
+    pub fn for_request<T: Serialize>(
+        seq: usize,
+        request: &Request,
+        body: T,
+    ) -> Result<Self, Error> {
+        Response::builder()
+            .seq(seq)
+            .request_seq(request.seq())
+            .command(request.command())
+            .body(body)?
+            .build()
+    }
+
+    /// Create a response reporting that `request` failed.
+    ///
+    /// The short error message of the response is `error` with its variables expanded.
+    pub fn error_for_request(
+        seq: usize,
+        request: &Request,
+        error: ErrorMessage,
+    ) -> Result<Self, Error> {
+        Response::builder()
+            .seq(seq)
+            .request_seq(request.seq())
+            .command(request.command())
+            .success(false)
+            .message(error.expand())
+            .body(ErrorResponseBody { error: Some(error) })?
+            .build()
+    }
+
+    #[doc(hidden)]
+    pub fn seq(&self) -> usize {
+        self.response_info.seq
+    }
+
+    #[doc(hidden)]
+    pub fn request_seq(&self) -> usize {
+        self.response_info.request_seq
+    }
+
+    #[doc(hidden)]
+    pub fn success(&self) -> bool {
+        self.response_info.success
+    }
+
+    #[doc(hidden)]
+    pub fn command(&self) -> &str {
+        self.response_info.command.as_str()
+    }
+
+    #[doc(hidden)]
+    pub fn message(&self) -> Option<&str> {
+        self.response_info.message.as_deref()
+    }
+
+    #[doc(hidden)]
+    pub fn body(&self) -> Option<&serde_json::Value> {
+        self.response_info.body.as_ref()
+    }
+
+    /// Whether the response report that its request was cancelled, with the `cancelled` message.
+    pub fn is_cancelled(&self) -> bool {
+        !self.success() && self.message() == Some("cancelled")
+    }
+
+    /// Report a failed response as `Error::RequestFailed`, or as `Error::Cancelled` if its
+    /// request was cancelled.
+    pub fn check_success(&self) -> Result<(), Error> {
+        if self.success() {
+            Ok(())
+        } else if self.is_cancelled() {
+            Err(Error::Cancelled {
+                request_seq: self.request_seq(),
+            })
+        } else {
+            Err(Error::RequestFailed {
+                message: self.message().unwrap_or_default().to_string(),
+            })
+        }
+    }
+
+    /// Deserialize the body of a successful response.
+    ///
+    /// A failed response is reported as `Error::RequestFailed`.
+    pub fn body_as<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        self.check_success()?;
+
+        // a missing body is treated like a empty one,
+        // since the body of some response only contain optional fields.
+        let body = self
+            .body()
+            .cloned()
+            .unwrap_or_else(|| serde_json::json!({}));
+        serde_json::from_value(body).or(Err(Error::InvalidMessage))
+    }
+}
+
+/// Build a `Response` field by field.
+///
+/// The `seq`, `request_seq` and `command` fields are mandatory, while `success` default to true.
+#[derive(Debug, Clone, Default)]
+pub struct ResponseBuilder {
+    seq: Option<usize>,
+    request_seq: Option<usize>,
+    success: Option<bool>,
+    command: Option<String>,
+    message: Option<String>,
+    body: Option<serde_json::Value>,
+}
+
+impl ResponseBuilder {
+    pub fn seq(mut self, seq: usize) -> Self {
+        self.seq = Some(seq);
+        self
+    }
+
+    pub fn request_seq(mut self, seq: usize) -> Self {
+        self.request_seq = Some(seq);
+        self
+    }
+
+    pub fn success(mut self, ok: bool) -> Self {
+        self.success = Some(ok);
+        self
+    }
+
+    pub fn command(mut self, cmd: impl Into<String>) -> Self {
+        self.command = Some(cmd.into());
+        self
+    }
+
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Set the body of the response.
+    ///
+    /// The body is serialized right away, so that a body which cannot be serialized is reported here.
+    /// A body serialized as `null`, such as `()`, is omitted.
+    pub fn body<T: Serialize>(mut self, body: T) -> Result<Self, Error> {
+        let body = serde_json::to_value(body)?;
+        self.body = Some(body).filter(|body| !body.is_null());
+        Ok(self)
+    }
+
+    /// Build the response, failing with `Error::InvalidMessage` if a mandatory field is missing.
+    pub fn build(self) -> Result<Response, Error> {
+        Ok(Response {
+            response_info: ResponseInfo {
+                seq: self.seq.ok_or(Error::InvalidMessage)?,
+                request_seq: self.request_seq.ok_or(Error::InvalidMessage)?,
+                success: self.success.unwrap_or(true),
+                command: self.command.ok_or(Error::InvalidMessage)?,
+                message: self.message,
+                body: self.body,
+            },
+        })
+    }
+}
+
+/// On error (whenever ‘success’ is false), the body can provide more details.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorResponseBody {
+    /**
+     * An optional, structured error message.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorMessage>,
+}
+
+/// Response to 'runInTerminal' request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunInTerminalResponseBody {
+    /**
+     * The process ID. The value should be less than or equal to 2147483647
+     * (2^31-1).
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub process_id: Option<usize>,
+
+    /**
+     * The process ID of the terminal shell. The value should be less than or
+     * equal to 2147483647 (2^31-1).
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shell_process_id: Option<usize>,
+}
+
+/// Response to 'setExceptionBreakpoints' request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetExceptionBreakpointsResponseBody {
+    /**
+     * Information about the exception breakpoints or filters.
+     * The breakpoints returned are in the same order as the elements of the
+     * 'filters', 'filterOptions', 'exceptionOptions' arrays in the arguments.
+     * If both 'filters' and 'filterOptions' are given, the returned array must
+     * start with 'filters' information first, followed by 'filterOptions'
+     * information.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub breakpoints: Option<Vec<Breakpoint>>,
+}
+
+impl SetExceptionBreakpointsResponseBody {
+    /// Create the body answering `arguments`, with one breakpoint per filter.
+    ///
+    /// `breakpoints` must describe every entry of `arguments.filters`, followed by every entry of
+    /// `arguments.filter_options`, in order. A body with a different number of breakpoints is
+    /// rejected with `Error::InvalidMessage`.
+    pub fn for_arguments(
+        arguments: &SetExceptionBreakpointsArguments,
+        breakpoints: Vec<Breakpoint>,
+    ) -> Result<Self, Error> {
+        let filter_count = arguments.filters.len()
+            + arguments
+                .filter_options
+                .as_ref()
+                .map_or(0, |options| options.len());
+
+        if breakpoints.len() != filter_count {
+            return Err(Error::InvalidMessage);
+        }
+
+        Ok(Self {
+            breakpoints: Some(breakpoints),
+        })
+    }
+}
+
+/// Response to 'continue' request.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContinueResponseBody {
+    /**
+     * The value true (or a missing property) signals to the client that all
+     * threads have been resumed. The value false indicates that not all threads
+     * were resumed.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all_threads_continued: Option<bool>,
+}
+
+impl ContinueResponseBody {
+    /// Create the body of the response to a 'continue' request which was honored as asked.
+    ///
+    /// When `arguments` has `single_thread` set, only its thread was resumed, and so
+    /// `all_threads_continued` is false. Otherwise it is omitted, which mean every thread was
+    /// resumed.
+    pub fn for_arguments(arguments: &ContinueArguments) -> Self {
+        let all_threads_continued = match arguments.single_thread {
+            Some(true) => Some(false),
+            _ => None,
+        };
+        Self {
+            all_threads_continued,
+        }
+    }
+}
+
+/// Response to 'breakpointLocations' request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BreakpointLocationsResponseBody {
+    /**
+     * Sorted set of possible breakpoint locations.
+     */
+    pub breakpoints: Vec<BreakpointLocation>,
+}
+
+impl BreakpointLocationsResponseBody {
+    /// Check that every location start within the lines requested by `arguments`, from `line`
+    /// to `end_line`, or on `line` alone when `end_line` is not given.
+    pub fn validate(&self, arguments: &BreakpointLocationsArguments) -> Result<(), Error> {
+        let lines = arguments.line..=arguments.end_line.unwrap_or(arguments.line);
+        if self
+            .breakpoints
+            .iter()
+            .all(|location| lines.contains(&location.line))
+        {
+            Ok(())
+        } else {
+            Err(Error::InvalidMessage)
+        }
+    }
+}
+
+/// Response to 'completions' request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompletionsResponseBody {
+    /**
+     * The possible completions for the text of the request.
+     */
+    pub targets: Vec<CompletionItem>,
+}
+
+/// Response to 'dataBreakpointInfo' request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataBreakpointInfoResponseBody {
+    /**
+     * An identifier for the data on which a data breakpoint can be registered
+     * with the 'setDataBreakpoints' request or null if no data breakpoint is
+     * available.
+     */
+    pub data_id: Option<String>,
+
+    /**
+     * UI string that describes on what data the breakpoint is set on or why a
+     * data breakpoint is not available.
+     */
+    pub description: String,
+
+    /**
+     * Optional attribute listing the available access types for a potential
+     * data breakpoint. A UI frontend could surface this information.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_types: Option<Vec<DataBreakpointAccessType>>,
+
+    /**
+     * Optional attribute indicating that a potential data breakpoint could be
+     * persisted across sessions.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_persist: Option<bool>,
+}
+
+impl DataBreakpointInfoResponseBody {
+    /// Create the body of a response offering a data breakpoint on `data_id`.
+    pub fn available(data_id: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            data_id: Some(data_id.into()),
+            description: description.into(),
+            access_types: None,
+            can_persist: None,
+        }
+    }
+
+    /// Create the body of a response explaining why no data breakpoint is available.
+    pub fn unavailable(description: impl Into<String>) -> Self {
+        Self {
+            data_id: None,
+            description: description.into(),
+            access_types: None,
+            can_persist: None,
+        }
+    }
+}
+
+/// Response to 'disassemble' request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisassembleResponseBody {
+    /**
+     * The list of disassembled instructions.
+     */
+    pub instructions: Vec<DisassembledInstruction>,
+}
+
+impl DisassembleResponseBody {
+    /// Check that the response hold exactly `expected` instructions, the `instruction_count` of
+    /// the request.
+    ///
+    /// The specification requires a adapter to pad the instructions it could not disassemble
+    /// with implementation-defined 'invalid instructions', rather than returning fewer of them.
+    pub fn validate(&self, expected: usize) -> Result<(), Error> {
+        if self.instructions.len() == expected {
+            Ok(())
+        } else {
+            Err(Error::InvalidMessage)
+        }
+    }
+}
+
+/// Response to 'gotoTargets' request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GotoTargetsResponseBody {
+    /**
+     * The possible goto targets of the specified location.
+     */
+    pub targets: Vec<GotoTarget>,
+}
+
+/// Response to 'stepInTargets' request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepInTargetsResponseBody {
+    /**
+     * The possible stepIn targets of the specified source location.
+     */
+    pub targets: Vec<StepInTarget>,
+}
+
+/// Response to 'readMemory' request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadMemoryResponseBody {
+    /**
+     * The address of the first byte of data returned.
+     * Treated as a hex value if prefixed with '0x', or as a decimal value
+     * otherwise.
+     */
+    pub address: String,
+
+    /**
+     * The number of unreadable bytes encountered after the last successfully
+     * read byte.
+     * This can be used to determine the number of bytes that must be skipped
+     * before a subsequent 'readMemory' request will succeed.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unreadable_bytes: Option<usize>,
+
+    /**
+     * The bytes read from memory, encoded using base64.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+}
+
+impl ReadMemoryResponseBody {
+    /// Create the body of a response returning `bytes`, read at `address`.
+    ///
+    /// The bytes are encoded with the standard base64 alphabet.
+    pub fn from_bytes(address: impl Into<String>, bytes: &[u8]) -> Self {
+        Self::from_bytes_with::<StandardBase64>(address, bytes)
+    }
+
+    /// Create the body of a response returning `bytes`, encoded with the base64 engine `E`.
+    pub fn from_bytes_with<E: Base64Engine>(address: impl Into<String>, bytes: &[u8]) -> Self {
+        Self {
+            address: address.into(),
+            unreadable_bytes: None,
+            data: Some(encode_base64::<E>(bytes)),
+        }
+    }
+
+    /// Decode the bytes read, which are encoded with the standard base64 alphabet.
+    ///
+    /// A missing `data` is the same as no bytes at all.
+    pub fn data_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.data_bytes_with::<StandardBase64>()
+    }
+
+    /// Decode the bytes read with the base64 engine `E`.
+    pub fn data_bytes_with<E: Base64Engine>(&self) -> Result<Vec<u8>, Error> {
+        match &self.data {
+            Some(data) => decode_base64::<E>(data),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Decode the bytes read, checking that they answer `arguments`.
+    ///
+    /// The bytes read and the unreadable bytes must together account for the `count` bytes
+    /// which were requested, otherwise the response is a invalid message.
+    pub fn data_bytes_for(&self, arguments: &ReadMemoryArguments) -> Result<Vec<u8>, Error> {
+        let bytes = self.data_bytes()?;
+        // both numbers come from the peer, so their sum may overflow
+        let total = bytes
+            .len()
+            .checked_add(self.unreadable_bytes.unwrap_or(0))
+            .ok_or(Error::InvalidMessage)?;
+        if total != arguments.count {
+            return Err(Error::InvalidMessage);
+        }
+        Ok(bytes)
+    }
+}
+
+/// Response to 'source' request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceResponseBody {
+    /**
+     * Content of the source reference.
+     */
+    pub content: String,
+
+    /**
+     * Optional content type (mime type) of the source.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+/// Response to 'modules' request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModulesResponseBody {
+    /**
+     * All modules or range of modules.
+     */
+    pub modules: Vec<Module>,
+
+    /**
+     * The total number of modules available.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_modules: Option<usize>,
+}
+
+/// Response to 'loadedSources' request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoadedSourcesResponseBody {
+    /**
+     * Set of loaded sources.
+     */
+    pub sources: Vec<Source>,
+}
+
+/// Response to 'threads' request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadsResponseBody {
+    /**
+     * All threads.
+     */
+    pub threads: Vec<Thread>,
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+    use crate::{Message, MessageKind, RequestKind, UrlSafeBase64};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn source_response_valid() {
+        let body = r#"{ "seq": 3, "type": "request", "command": "source", "arguments": { "sourceReference": 1 } }"#;
+        let request = Request::try_from(
+            Message::try_from(serde_json::from_str::<serde_json::Value>(body).unwrap()).unwrap(),
+        )
+        .unwrap();
+
+        let source = match request.request_kind() {
+            RequestKind::Source(source) => source,
+            _ => panic!(),
+        };
+        assert_eq!(source.arguments().source_reference, 1);
+
+        let body = SourceResponseBody {
+            content: "mov rax, 1".to_string(),
+            mime_type: Some("text/x-asm".to_string()),
+        };
+        let response = Response::for_request(4, &request, body).unwrap();
+        let message = Message::try_from(response).unwrap();
+
+        assert_eq!(
+            *message.body(),
+            serde_json::json!({
+                "seq": 4,
+                "type": "response",
+                "request_seq": 3,
+                "success": true,
+                "command": "source",
+                "body": { "content": "mov rax, 1", "mimeType": "text/x-asm" }
+            })
+        );
+        match message.message_kind() {
+            Some(MessageKind::Response(_)) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn error_response_valid() {
+        let body = r#"{ "seq": 3, "type": "request", "command": "launch", "arguments": {} }"#;
+        let request = Request::try_from(
+            Message::try_from(serde_json::from_str::<serde_json::Value>(body).unwrap()).unwrap(),
+        )
+        .unwrap();
+
+        let mut error = ErrorMessage::new(12, "program {program} not found");
+        error.variables = Some(
+            vec![("program".to_string(), "a.out".to_string())]
+                .into_iter()
+                .collect(),
+        );
+        error.show_user = Some(true);
+
+        let response = Response::error_for_request(4, &request, error).unwrap();
+
+        assert!(!response.success());
+        assert_eq!(response.message(), Some("program a.out not found"));
+        assert_eq!(
+            response.body(),
+            Some(&serde_json::json!({
+                "error": {
+                    "id": 12,
+                    "format": "program {program} not found",
+                    "variables": { "program": "a.out" },
+                    "showUser": true
+                }
+            }))
+        );
+    }
+
+    #[test]
+    fn response_builder_valid() {
+        let response = Response::builder()
+            .seq(2)
+            .request_seq(1)
+            .command("threads")
+            .body(serde_json::json!({ "threads": [] }))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(response.seq(), 2);
+        assert_eq!(response.request_seq(), 1);
+        assert!(response.success());
+        assert_eq!(response.command(), "threads");
+        assert_eq!(response.message(), None);
+        assert_eq!(response.body(), Some(&serde_json::json!({ "threads": [] })));
+    }
+
+    #[test]
+    fn response_builder_missing_field() {
+        let result = Response::builder().seq(2).command("threads").build();
+
+        match result {
+            Err(Error::InvalidMessage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn response_builder_invalid_body() {
+        use std::collections::HashMap;
+
+        // json object keys must be strings
+        let mut body = HashMap::new();
+        body.insert((1, 2), "value");
+
+        match Response::builder().body(body) {
+            Err(Error::Json(_)) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn set_exception_breakpoints_response_valid() {
+        let arguments: SetExceptionBreakpointsArguments = serde_json::from_str(
+            r#"{ "filters": ["panic"], "filterOptions": [{ "filterId": "signal", "condition": "sig == 11" }] }"#,
+        )
+        .unwrap();
+
+        let mut rejected = Breakpoint::new(false);
+        rejected.message = Some("conditions are not supported".to_string());
+
+        let body = SetExceptionBreakpointsResponseBody::for_arguments(
+            &arguments,
+            vec![Breakpoint::new(true), rejected],
+        )
+        .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&body).unwrap(),
+            serde_json::json!({
+                "breakpoints": [
+                    { "verified": true },
+                    { "verified": false, "message": "conditions are not supported" }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn set_exception_breakpoints_response_wrong_length() {
+        let arguments: SetExceptionBreakpointsArguments =
+            serde_json::from_str(r#"{ "filters": ["panic", "signal"] }"#).unwrap();
+
+        let result = SetExceptionBreakpointsResponseBody::for_arguments(
+            &arguments,
+            vec![Breakpoint::new(true)],
+        );
+
+        match result {
+            Err(Error::InvalidMessage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn continue_response_single_thread() {
+        let body = r#"{ "seq": 4, "type": "request", "command": "continue", "arguments": { "threadId": 2, "singleThread": true } }"#;
+        let request = Request::try_from(
+            Message::try_from(serde_json::from_str::<serde_json::Value>(body).unwrap()).unwrap(),
+        )
+        .unwrap();
+        let arguments = match request.request_kind() {
+            RequestKind::Continue(request) => request.arguments(),
+            _ => panic!(),
+        };
+
+        let body = ContinueResponseBody::for_arguments(arguments);
+        assert_eq!(body.all_threads_continued, Some(false));
+
+        let response = Response::for_request(5, &request, body).unwrap();
+        assert_eq!(
+            response.body(),
+            Some(&serde_json::json!({ "allThreadsContinued": false }))
+        );
+
+        // without singleThread, every thread was resumed
+        let body = ContinueResponseBody::for_arguments(&ContinueArguments::new(2));
+        assert_eq!(serde_json::to_value(&body).unwrap(), serde_json::json!({}));
+    }
+
+    #[test]
+    fn breakpoint_locations_validate() {
+        let body = BreakpointLocationsResponseBody {
+            breakpoints: vec![
+                BreakpointLocation::new(10),
+                BreakpointLocation {
+                    column: Some(5),
+                    end_line: Some(14),
+                    ..BreakpointLocation::new(12)
+                },
+            ],
+        };
+        assert_eq!(
+            serde_json::to_value(&body).unwrap(),
+            serde_json::json!({
+                "breakpoints": [{ "line": 10 }, { "line": 12, "column": 5, "endLine": 14 }]
+            })
+        );
+
+        let source = Source::default();
+        let mut arguments = BreakpointLocationsArguments::new(source, 10);
+        // only line 10 was requested
+        assert!(body.validate(&arguments).is_err());
+
+        arguments.end_line = Some(12);
+        assert!(body.validate(&arguments).is_ok());
+
+        arguments.line = 11;
+        assert!(body.validate(&arguments).is_err());
+    }
+
+    #[test]
+    fn data_breakpoint_info_response() {
+        let body = DataBreakpointInfoResponseBody {
+            access_types: Some(vec![
+                DataBreakpointAccessType::Read,
+                DataBreakpointAccessType::Write,
+                DataBreakpointAccessType::ReadWrite,
+            ]),
+            ..DataBreakpointInfoResponseBody::available("0x7ffe10", "counter (4 bytes)")
+        };
+        let value = serde_json::json!({
+            "dataId": "0x7ffe10",
+            "description": "counter (4 bytes)",
+            "accessTypes": ["read", "write", "readWrite"]
+        });
+        assert_eq!(serde_json::to_value(&body).unwrap(), value);
+        assert_eq!(
+            serde_json::from_value::<DataBreakpointInfoResponseBody>(value).unwrap(),
+            body
+        );
+
+        // the data id is required, even when there is none
+        let body = DataBreakpointInfoResponseBody::unavailable("not a lvalue");
+        assert_eq!(
+            serde_json::to_value(&body).unwrap(),
+            serde_json::json!({ "dataId": null, "description": "not a lvalue" })
+        );
+    }
+
+    #[test]
+    fn disassemble_response_validate() {
+        let body: DisassembleResponseBody = serde_json::from_value(serde_json::json!({
+            "instructions": [
+                {
+                    "address": "0x401000",
+                    "instructionBytes": "55",
+                    "instruction": "push rbp",
+                    "symbol": "main",
+                    "location": { "path": "/src/main.rs" },
+                    "line": 1
+                },
+                { "address": "0x401001", "instruction": "mov rbp, rsp" }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(body.instructions[0].symbol.as_deref(), Some("main"));
+        assert_eq!(
+            body.instructions[1],
+            DisassembledInstruction::new("0x401001", "mov rbp, rsp")
+        );
+
+        assert!(body.validate(2).is_ok());
+        assert!(body.validate(3).is_err());
+        assert!(body.validate(1).is_err());
+    }
+
+    #[test]
+    fn read_memory_data() {
+        let body = ReadMemoryResponseBody::from_bytes("0x1000", b"\x7fELF");
+        assert_eq!(
+            serde_json::to_value(&body).unwrap(),
+            serde_json::json!({ "address": "0x1000", "data": "f0VMRg==" })
+        );
+        assert_eq!(body.data_bytes().unwrap(), b"\x7fELF");
+
+        let arguments = ReadMemoryArguments::new("0x1000", 4);
+        assert_eq!(body.data_bytes_for(&arguments).unwrap(), b"\x7fELF");
+        assert!(body
+            .data_bytes_for(&ReadMemoryArguments::new("0x1000", 6))
+            .is_err());
+
+        // a short read is valid, as long as the rest is reported as unreadable
+        let short = ReadMemoryResponseBody {
+            unreadable_bytes: Some(2),
+            ..body
+        };
+        assert!(short
+            .data_bytes_for(&ReadMemoryArguments::new("0x1000", 6))
+            .is_ok());
+
+        let unreadable = ReadMemoryResponseBody {
+            address: "0x2000".to_string(),
+            unreadable_bytes: Some(16),
+            data: None,
+        };
+        assert_eq!(unreadable.data_bytes().unwrap(), b"");
+
+        let url_safe = ReadMemoryResponseBody::from_bytes_with::<UrlSafeBase64>("0", &[0xfb, 0xff]);
+        assert_eq!(url_safe.data.as_deref(), Some("-_8="));
+        assert!(url_safe.data_bytes().is_err());
+        assert_eq!(
+            url_safe.data_bytes_with::<UrlSafeBase64>().unwrap(),
+            [0xfb, 0xff]
+        );
+    }
+
+    #[test]
+    fn read_memory_data_unreadable_overflow() {
+        let body: ReadMemoryResponseBody = serde_json::from_value(serde_json::json!({
+            "address": "0x1000",
+            "data": "f0VMRg==",
+            "unreadableBytes": usize::MAX
+        }))
+        .unwrap();
+
+        match body.data_bytes_for(&ReadMemoryArguments::new("0x1000", 3)) {
+            Err(Error::InvalidMessage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn threads_response_valid() {
+        let response = r#"{
+            "seq": 153,
+            "type": "response",
+            "request_seq": 9,
+            "success": true,
+            "command": "threads",
+            "body": {
+                "threads": [
+                    { "id": 1, "name": "main" },
+                    { "id": 2, "name": "worker" }
+                ]
+            }
+          }"#;
+
+        let message =
+            Message::try_from(serde_json::from_str::<serde_json::Value>(response).unwrap());
+        let response = Response::try_from(message.unwrap()).unwrap();
+        let body: ThreadsResponseBody = response.body_as().unwrap();
+
+        assert_eq!(
+            body.threads,
+            vec![Thread::new(1, "main"), Thread::new(2, "worker")]
+        );
+    }
+}
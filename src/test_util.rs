@@ -0,0 +1,64 @@
+//! Helpers shared by the tests of the crate.
+
+use std::convert::TryFrom;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::{Event, Message};
+
+/// A output which can be inspected while owned by the adapter.
+#[derive(Clone, Default)]
+pub(crate) struct SharedOutput(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl SharedOutput {
+    /// Read back every message written so far.
+    pub(crate) fn messages(&self) -> Vec<Message> {
+        read_messages(&self.0.lock().unwrap())
+    }
+
+    /// Read back every event written so far.
+    pub(crate) fn events(&self) -> Vec<Event> {
+        self.messages()
+            .into_iter()
+            .map(|message| Event::try_from(message).unwrap())
+            .collect()
+    }
+
+    /// Wait for the first message to be written, and return it.
+    pub(crate) fn wait_for_message(&self) -> Message {
+        // the message may be partially written, so retry until it can be read
+        loop {
+            let bytes = self.0.lock().unwrap().clone();
+            if let Ok(message) = Message::try_from_input(&mut bytes.as_slice()) {
+                return message;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    /// Forget every message written so far.
+    pub(crate) fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
+}
+
+/// Read the messages of `bytes`, which must hold whole messages only.
+pub(crate) fn read_messages(mut bytes: &[u8]) -> Vec<Message> {
+    let mut messages = Vec::new();
+    while !bytes.is_empty() {
+        messages.push(Message::try_from_input(&mut bytes).unwrap());
+    }
+    messages
+}
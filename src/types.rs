@@ -0,0 +1,39 @@
+//! The types shared by the requests, responses and events.
+
+mod breakpoint;
+mod capabilities;
+mod checksum;
+mod completion;
+mod error_message;
+mod exception;
+mod format;
+mod instruction;
+mod invalidated_areas;
+mod module;
+mod source;
+mod target;
+mod thread;
+mod variable;
+
+pub use breakpoint::{
+    Breakpoint, BreakpointLocation, DataBreakpoint, DataBreakpointAccessType, FunctionBreakpoint,
+    InstructionBreakpoint, SourceBreakpoint,
+};
+pub use capabilities::{
+    Capabilities, CapabilitiesBuilder, ClientCapabilities, ColumnDescriptor, ColumnDescriptorType,
+};
+pub use checksum::{Checksum, ChecksumAlgorithm};
+pub use completion::{CompletionItem, CompletionItemType};
+pub use error_message::ErrorMessage;
+pub use exception::{
+    ExceptionBreakMode, ExceptionBreakpointsFilter, ExceptionDetails, ExceptionFilterOptions,
+    ExceptionOptions, ExceptionPathSegment,
+};
+pub use format::{StackFrameFormat, SteppingGranularity, ValueFormat};
+pub use instruction::DisassembledInstruction;
+pub use invalidated_areas::InvalidatedAreas;
+pub use module::{Module, ModuleId};
+pub use source::{Source, SourcePresentationHint};
+pub use target::{GotoTarget, StepInTarget};
+pub use thread::Thread;
+pub use variable::{Variable, VariablePresentationHint, VariableSorter};
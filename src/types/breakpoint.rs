@@ -0,0 +1,429 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Source;
+
+/// Information about a Breakpoint created in setBreakpoints, setFunctionBreakpoints, setInstructionBreakpoints, or setDataBreakpoints.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Breakpoint {
+    /**
+     * An optional identifier for the breakpoint. It is needed if breakpoint
+     * events are used to update or remove breakpoints.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<usize>,
+
+    /**
+     * If true breakpoint could be set (but not necessarily at the desired
+     * location).
+     */
+    pub verified: bool,
+
+    /**
+     * An optional message about the state of the breakpoint.
+     * This is shown to the user and can be used to explain why a breakpoint
+     * could not be verified.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
+    /**
+     * The source where the breakpoint is located.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<Source>,
+
+    /**
+     * The start line of the actual range covered by the breakpoint.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+
+    /**
+     * An optional start column of the actual range covered by the breakpoint.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+
+    /**
+     * An optional end line of the actual range covered by the breakpoint.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+
+    /**
+     * An optional end column of the actual range covered by the breakpoint.
+     * If no end line is given, then the end column is assumed to be in the start
+     * line.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
+
+    /**
+     * An optional memory reference to where the breakpoint is set.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instruction_reference: Option<String>,
+
+    /**
+     * An optional offset from the instruction reference.
+     * This can be negative.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<isize>,
+}
+
+impl Breakpoint {
+    pub fn new(verified: bool) -> Self {
+        Self {
+            verified,
+            ..Self::default()
+        }
+    }
+
+    /// A breakpoint requested on `line` of `source`, which the adapter has not placed yet.
+    pub fn unverified(source: Source, line: usize) -> Self {
+        Self {
+            source: Some(source),
+            line: Some(line),
+            ..Self::new(false)
+        }
+    }
+
+    pub fn id(mut self, id: usize) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn verified(mut self, verified: bool) -> Self {
+        self.verified = verified;
+        self
+    }
+
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    pub fn line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    pub fn instruction_reference(mut self, instruction_reference: impl Into<String>) -> Self {
+        self.instruction_reference = Some(instruction_reference.into());
+        self
+    }
+}
+
+/// Properties of a breakpoint or logpoint passed to the setBreakpoints request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceBreakpoint {
+    /**
+     * The source line of the breakpoint or logpoint.
+     */
+    pub line: usize,
+
+    /**
+     * An optional source column of the breakpoint.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+
+    /**
+     * An optional expression for conditional breakpoints.
+     * It is only honored by a debug adapter if the capability
+     * 'supportsConditionalBreakpoints' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+
+    /**
+     * An optional expression that controls how many hits of the breakpoint are
+     * ignored.
+     * The backend is expected to interpret the expression as needed.
+     * The attribute is only honored by a debug adapter if the capability
+     * 'supportsHitConditionalBreakpoints' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hit_condition: Option<String>,
+
+    /**
+     * If this attribute exists and is non-empty, the backend must not 'break'
+     * (stop)
+     * but log the message instead. Expressions within {} are interpolated.
+     * The attribute is only honored by a debug adapter if the capability
+     * 'supportsLogPoints' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_message: Option<String>,
+}
+
+impl SourceBreakpoint {
+    pub fn new(line: usize) -> Self {
+        Self {
+            line,
+            column: None,
+            condition: None,
+            hit_condition: None,
+            log_message: None,
+        }
+    }
+}
+
+/// Properties of a breakpoint location returned from the ‘breakpointLocations’ request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BreakpointLocation {
+    /**
+     * Start line of breakpoint location.
+     */
+    pub line: usize,
+
+    /**
+     * Optional start column of breakpoint location.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+
+    /**
+     * Optional end line of breakpoint location if the location covers a range.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+
+    /**
+     * Optional end column of breakpoint location if the location covers a
+     * range.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
+}
+
+impl BreakpointLocation {
+    pub fn new(line: usize) -> Self {
+        Self {
+            line,
+            column: None,
+            end_line: None,
+            end_column: None,
+        }
+    }
+}
+
+/// Properties of a breakpoint passed to the setFunctionBreakpoints request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionBreakpoint {
+    /**
+     * The name of the function.
+     */
+    pub name: String,
+
+    /**
+     * An optional expression for conditional breakpoints.
+     * It is only honored by a debug adapter if the capability
+     * 'supportsConditionalBreakpoints' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+
+    /**
+     * An optional expression that controls how many hits of the breakpoint are
+     * ignored.
+     * The backend is expected to interpret the expression as needed.
+     * The attribute is only honored by a debug adapter if the capability
+     * 'supportsHitConditionalBreakpoints' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hit_condition: Option<String>,
+}
+
+/// This enumeration defines all possible access types for data breakpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DataBreakpointAccessType {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+/// Properties of a data breakpoint passed to the setDataBreakpoints request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataBreakpoint {
+    /**
+     * An id representing the data. This id is returned from the
+     * dataBreakpointInfo request.
+     */
+    pub data_id: String,
+
+    /**
+     * The access type of the data.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_type: Option<DataBreakpointAccessType>,
+
+    /**
+     * An optional expression for conditional breakpoints.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+
+    /**
+     * An optional expression that controls how many hits of the breakpoint are
+     * ignored.
+     * The backend is expected to interpret the expression as needed.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hit_condition: Option<String>,
+}
+
+impl DataBreakpoint {
+    pub fn new(data_id: impl Into<String>) -> Self {
+        Self {
+            data_id: data_id.into(),
+            access_type: None,
+            condition: None,
+            hit_condition: None,
+        }
+    }
+}
+
+/// Properties of a breakpoint passed to the setInstructionBreakpoints request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstructionBreakpoint {
+    /**
+     * The instruction reference of the breakpoint.
+     * This should be a memory or instruction pointer reference from an
+     * EvaluateResponse, Variable, StackFrame, GotoTarget, or Breakpoint.
+     */
+    pub instruction_reference: String,
+
+    /**
+     * An optional offset from the instruction reference.
+     * This can be negative.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<isize>,
+
+    /**
+     * An optional expression for conditional breakpoints.
+     * It is only honored by a debug adapter if the capability
+     * 'supportsConditionalBreakpoints' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+
+    /**
+     * An optional expression that controls how many hits of the breakpoint are
+     * ignored.
+     * The backend is expected to interpret the expression as needed.
+     * The attribute is only honored by a debug adapter if the capability
+     * 'supportsHitConditionalBreakpoints' is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hit_condition: Option<String>,
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn resolve_unverified_breakpoint() {
+        let source = Source {
+            path: Some("main.c".to_string()),
+            ..Source::default()
+        };
+
+        let breakpoint = Breakpoint::unverified(source.clone(), 12);
+        assert!(!breakpoint.verified);
+        assert_eq!(breakpoint.source, Some(source));
+        assert_eq!(breakpoint.line, Some(12));
+
+        let breakpoint = breakpoint
+            .id(1)
+            .verified(true)
+            .line(13)
+            .instruction_reference("0x4001a0")
+            .message("Breakpoint set at 0x4001a0");
+        assert_eq!(
+            serde_json::to_value(&breakpoint).unwrap(),
+            serde_json::json!({
+                "id": 1,
+                "verified": true,
+                "message": "Breakpoint set at 0x4001a0",
+                "source": { "path": "main.c" },
+                "line": 13,
+                "instructionReference": "0x4001a0"
+            })
+        );
+    }
+
+    #[test]
+    fn source_breakpoints_from_vscode() {
+        let breakpoints = r#"[
+            { "line": 12 },
+            { "line": 20, "column": 5, "condition": "i > 3" },
+            { "line": 31, "hitCondition": ">= 10" },
+            { "line": 42, "logMessage": "value of x: {x}" }
+          ]"#;
+
+        let breakpoints: Vec<SourceBreakpoint> = serde_json::from_str(breakpoints).unwrap();
+
+        assert_eq!(breakpoints[0].line, 12);
+        assert_eq!(breakpoints[0].condition, None);
+        assert_eq!(breakpoints[1].column, Some(5));
+        assert_eq!(breakpoints[1].condition.as_deref(), Some("i > 3"));
+        assert_eq!(breakpoints[2].hit_condition.as_deref(), Some(">= 10"));
+        assert_eq!(
+            breakpoints[3].log_message.as_deref(),
+            Some("value of x: {x}")
+        );
+    }
+
+    #[test]
+    fn function_breakpoint_from_vscode() {
+        let breakpoint = r#"{ "name": "main", "condition": "argc == 2", "hitCondition": "3" }"#;
+
+        let breakpoint: FunctionBreakpoint = serde_json::from_str(breakpoint).unwrap();
+
+        assert_eq!(breakpoint.name, "main");
+        assert_eq!(breakpoint.condition.as_deref(), Some("argc == 2"));
+        assert_eq!(breakpoint.hit_condition.as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn data_breakpoint_from_vscode() {
+        let breakpoint = r#"{ "dataId": "0x7ffd5c1c", "accessType": "readWrite" }"#;
+
+        let breakpoint: DataBreakpoint = serde_json::from_str(breakpoint).unwrap();
+
+        assert_eq!(breakpoint.data_id, "0x7ffd5c1c");
+        assert_eq!(
+            breakpoint.access_type,
+            Some(DataBreakpointAccessType::ReadWrite)
+        );
+    }
+
+    #[test]
+    fn instruction_breakpoint_from_vscode() {
+        let breakpoint =
+            r#"{ "instructionReference": "0x401000", "offset": -4, "hitCondition": "2" }"#;
+
+        let breakpoint: InstructionBreakpoint = serde_json::from_str(breakpoint).unwrap();
+
+        assert_eq!(breakpoint.instruction_reference, "0x401000");
+        assert_eq!(breakpoint.offset, Some(-4));
+        assert_eq!(breakpoint.hit_condition.as_deref(), Some("2"));
+    }
+}
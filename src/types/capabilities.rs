@@ -0,0 +1,613 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ChecksumAlgorithm, Error, ExceptionBreakpointsFilter, InitializeRequestArguments,
+    SetExceptionBreakpointsArguments,
+};
+
+/// Information about the capabilities of a debug adapter.
+///
+/// Every capability is optional, and unset capabilities are omitted when serialized.
+/// `Capabilities::builder` is the most convenient way to build a set of capabilities.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    /**
+     * The debug adapter supports the 'configurationDone' request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_configuration_done_request: Option<bool>,
+
+    /**
+     * The debug adapter supports function breakpoints.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_function_breakpoints: Option<bool>,
+
+    /**
+     * The debug adapter supports conditional breakpoints.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_conditional_breakpoints: Option<bool>,
+
+    /**
+     * The debug adapter supports breakpoints that break execution after a
+     * specified number of hits.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_hit_conditional_breakpoints: Option<bool>,
+
+    /**
+     * The debug adapter supports a (side effect free) evaluate request for data
+     * hovers.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_evaluate_for_hovers: Option<bool>,
+
+    /**
+     * Available exception filter options for the 'setExceptionBreakpoints'
+     * request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exception_breakpoint_filters: Option<Vec<ExceptionBreakpointsFilter>>,
+
+    /**
+     * The debug adapter supports stepping back via the 'stepBack' and
+     * 'reverseContinue' requests.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_step_back: Option<bool>,
+
+    /**
+     * The debug adapter supports setting a variable to a value.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_set_variable: Option<bool>,
+
+    /**
+     * The debug adapter supports restarting a frame.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_restart_frame: Option<bool>,
+
+    /**
+     * The debug adapter supports the 'gotoTargets' request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_goto_targets_request: Option<bool>,
+
+    /**
+     * The debug adapter supports the 'stepInTargets' request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_step_in_targets_request: Option<bool>,
+
+    /**
+     * The debug adapter supports the 'completions' request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_completions_request: Option<bool>,
+
+    /**
+     * The set of characters that should trigger completion in a REPL. If not
+     * specified, the UI should assume the '.' character.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completion_trigger_characters: Option<Vec<String>>,
+
+    /**
+     * The debug adapter supports the 'modules' request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_modules_request: Option<bool>,
+
+    /**
+     * The set of additional module information exposed by the debug adapter.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_module_columns: Option<Vec<ColumnDescriptor>>,
+
+    /**
+     * Checksum algorithms supported by the debug adapter.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supported_checksum_algorithms: Option<Vec<ChecksumAlgorithm>>,
+
+    /**
+     * The debug adapter supports the 'restart' request. In this case a client
+     * should not implement 'restart' by terminating and relaunching the adapter
+     * but by calling the RestartRequest.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_restart_request: Option<bool>,
+
+    /**
+     * The debug adapter supports 'exceptionOptions' on the
+     * setExceptionBreakpoints request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_exception_options: Option<bool>,
+
+    /**
+     * The debug adapter supports a 'format' attribute on the stackTraceRequest,
+     * variablesRequest, and evaluateRequest.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_value_formatting_options: Option<bool>,
+
+    /**
+     * The debug adapter supports the 'exceptionInfo' request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_exception_info_request: Option<bool>,
+
+    /**
+     * The debug adapter supports the 'terminateDebuggee' attribute on the
+     * 'disconnect' request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub support_terminate_debuggee: Option<bool>,
+
+    /**
+     * The debug adapter supports the delayed loading of parts of the stack, which
+     * requires that both the 'startFrame' and 'levels' arguments and an optional
+     * 'totalFrames' result of the 'StackTrace' request are supported.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_delayed_stack_trace_loading: Option<bool>,
+
+    /**
+     * The debug adapter supports the 'loadedSources' request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_loaded_sources_request: Option<bool>,
+
+    /**
+     * The debug adapter supports logpoints by interpreting the 'logMessage'
+     * attribute of the SourceBreakpoint.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_log_points: Option<bool>,
+
+    /**
+     * The debug adapter supports the 'terminateThreads' request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_terminate_threads_request: Option<bool>,
+
+    /**
+     * The debug adapter supports the 'setExpression' request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_set_expression: Option<bool>,
+
+    /**
+     * The debug adapter supports the 'terminate' request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_terminate_request: Option<bool>,
+
+    /**
+     * The debug adapter supports data breakpoints.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_data_breakpoints: Option<bool>,
+
+    /**
+     * The debug adapter supports the 'readMemory' request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_read_memory_request: Option<bool>,
+
+    /**
+     * The debug adapter supports the 'writeMemory' request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_write_memory_request: Option<bool>,
+
+    /**
+     * The debug adapter supports the 'disassemble' request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_disassemble_request: Option<bool>,
+
+    /**
+     * The debug adapter supports the 'cancel' request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_cancel_request: Option<bool>,
+
+    /**
+     * The debug adapter supports the 'breakpointLocations' request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_breakpoint_locations_request: Option<bool>,
+
+    /**
+     * The debug adapter supports the 'clipboard' context value in the 'evaluate'
+     * request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_clipboard_context: Option<bool>,
+
+    /**
+     * The debug adapter supports stepping granularities (argument 'granularity')
+     * for the stepping requests.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_stepping_granularity: Option<bool>,
+
+    /**
+     * The debug adapter supports adding breakpoints based on instruction
+     * references.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_instruction_breakpoints: Option<bool>,
+
+    /**
+     * The debug adapter supports 'filterOptions' as an argument on the
+     * 'setExceptionBreakpoints' request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_exception_filter_options: Option<bool>,
+
+    /**
+     * The debug adapter supports the 'singleThread' property on the execution
+     * requests ('continue', 'next', 'stepIn', 'stepOut', 'reverseContinue',
+     * 'stepBack').
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_single_thread_execution_requests: Option<bool>,
+}
+
+impl Capabilities {
+    pub fn builder() -> CapabilitiesBuilder {
+        CapabilitiesBuilder::default()
+    }
+
+    /// Check that a 'setExceptionBreakpoints' request only use the exception filters advertised
+    /// in `exception_breakpoint_filters`.
+    ///
+    /// A condition is only accepted for the filters which advertise `supports_condition`.
+    pub fn check_exception_filters(
+        &self,
+        arguments: &SetExceptionBreakpointsArguments,
+    ) -> Result<(), Error> {
+        let advertised = self
+            .exception_breakpoint_filters
+            .as_deref()
+            .unwrap_or_default();
+        let find = |id: &str| {
+            advertised
+                .iter()
+                .find(|filter| filter.filter == id)
+                .ok_or(Error::InvalidMessage)
+        };
+
+        for id in arguments.filters.iter() {
+            find(id)?;
+        }
+
+        for options in arguments.filter_options.iter().flatten() {
+            let filter = find(&options.filter_id)?;
+            if options.condition.is_some() && !filter.supports_condition.unwrap_or(false) {
+                return Err(Error::InvalidMessage);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Return the capabilities, turning off those which rely on a feature the client did not
+    /// advertise.
+    ///
+    /// The memory requests and the instruction breakpoints work on memory references, which are
+    /// only sent to clients supporting them.
+    pub fn filter_by_client(&self, client: &ClientCapabilities) -> Capabilities {
+        let mut capabilities = self.clone();
+        if !client.supports_memory_references {
+            for supported in [
+                &mut capabilities.supports_read_memory_request,
+                &mut capabilities.supports_write_memory_request,
+                &mut capabilities.supports_disassemble_request,
+                &mut capabilities.supports_instruction_breakpoints,
+            ] {
+                if *supported == Some(true) {
+                    *supported = Some(false);
+                }
+            }
+        }
+        capabilities
+    }
+}
+
+/// The capabilities a client advertise in the arguments of its 'initialize' request.
+///
+/// A capability the client did not mention is not supported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClientCapabilities {
+    pub supports_variable_type: bool,
+    pub supports_variable_paging: bool,
+    pub supports_run_in_terminal_request: bool,
+    pub supports_memory_references: bool,
+    pub supports_progress_reporting: bool,
+    pub supports_invalidated_event: bool,
+    pub supports_memory_event: bool,
+}
+
+impl From<&InitializeRequestArguments> for ClientCapabilities {
+    fn from(arguments: &InitializeRequestArguments) -> Self {
+        let supported = |capability: Option<bool>| capability.unwrap_or(false);
+        Self {
+            supports_variable_type: supported(arguments.supports_variable_type),
+            supports_variable_paging: supported(arguments.supports_variable_paging),
+            supports_run_in_terminal_request: supported(arguments.supports_run_in_terminal_request),
+            supports_memory_references: supported(arguments.supports_memory_references),
+            supports_progress_reporting: supported(arguments.supports_progress_reporting),
+            supports_invalidated_event: supported(arguments.supports_invalidated_event),
+            supports_memory_event: supported(arguments.supports_memory_event),
+        }
+    }
+}
+
+/// Generate the code going through every capability: a setter for each of them, and
+/// `Capabilities::diff`.
+macro_rules! capabilities {
+    ($($name:ident: $ty:ty,)*) => {
+        impl CapabilitiesBuilder {
+            $(
+                pub fn $name(mut self, $name: $ty) -> Self {
+                    self.capabilities.$name = Some($name);
+                    self
+                }
+            )*
+        }
+
+        impl Capabilities {
+            /// Return the capabilities of `newer` which differ from these ones, as sent by a
+            /// 'capabilities' event. Every other capability is left unset.
+            ///
+            /// A capability can not be unset by a event, so a capability set here but not in
+            /// `newer` is not part of the difference. It should be set to false instead.
+            pub fn diff(&self, newer: &Capabilities) -> Capabilities {
+                let mut diff = Capabilities::default();
+                $(
+                    if newer.$name.is_some() && self.$name != newer.$name {
+                        diff.$name = newer.$name.clone();
+                    }
+                )*
+                diff
+            }
+        }
+    };
+}
+
+/// A builder for `Capabilities`, where every capability not set stays unset.
+#[derive(Debug, Clone, Default)]
+pub struct CapabilitiesBuilder {
+    capabilities: Capabilities,
+}
+
+capabilities! {
+    supports_configuration_done_request: bool,
+    supports_function_breakpoints: bool,
+    supports_conditional_breakpoints: bool,
+    supports_hit_conditional_breakpoints: bool,
+    supports_evaluate_for_hovers: bool,
+    exception_breakpoint_filters: Vec<ExceptionBreakpointsFilter>,
+    supports_step_back: bool,
+    supports_set_variable: bool,
+    supports_restart_frame: bool,
+    supports_goto_targets_request: bool,
+    supports_step_in_targets_request: bool,
+    supports_completions_request: bool,
+    completion_trigger_characters: Vec<String>,
+    supports_modules_request: bool,
+    additional_module_columns: Vec<ColumnDescriptor>,
+    supported_checksum_algorithms: Vec<ChecksumAlgorithm>,
+    supports_restart_request: bool,
+    supports_exception_options: bool,
+    supports_value_formatting_options: bool,
+    supports_exception_info_request: bool,
+    support_terminate_debuggee: bool,
+    supports_delayed_stack_trace_loading: bool,
+    supports_loaded_sources_request: bool,
+    supports_log_points: bool,
+    supports_terminate_threads_request: bool,
+    supports_set_expression: bool,
+    supports_terminate_request: bool,
+    supports_data_breakpoints: bool,
+    supports_read_memory_request: bool,
+    supports_write_memory_request: bool,
+    supports_disassemble_request: bool,
+    supports_cancel_request: bool,
+    supports_breakpoint_locations_request: bool,
+    supports_clipboard_context: bool,
+    supports_stepping_granularity: bool,
+    supports_instruction_breakpoints: bool,
+    supports_exception_filter_options: bool,
+    supports_single_thread_execution_requests: bool,
+}
+
+impl CapabilitiesBuilder {
+    pub fn build(self) -> Capabilities {
+        self.capabilities
+    }
+}
+
+/// A ColumnDescriptor specifies what module attribute to show in a column of the ModulesView, how to format it,
+/// and what the column's label should be.
+///
+/// It is only used if the underlying UI actually supports this level of customization.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnDescriptor {
+    /**
+     * Name of the attribute rendered in this column.
+     */
+    pub attribute_name: String,
+
+    /**
+     * Header UI label of column.
+     */
+    pub label: String,
+
+    /**
+     * Format to use for the rendered values in this column. TBD how the format
+     * strings looks like.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+
+    /**
+     * Datatype of values in this column.  Defaults to 'string' if not specified.
+     */
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub column_type: Option<ColumnDescriptorType>,
+
+    /**
+     * Width of this column in characters (hint only).
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<usize>,
+}
+
+/// Datatype of the values of a module column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ColumnDescriptorType {
+    String,
+    Number,
+    Boolean,
+    #[serde(rename = "unixTimestampUTC")]
+    UnixTimestampUtc,
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn filter_capabilities_by_client() {
+        let capabilities = Capabilities::builder()
+            .supports_read_memory_request(true)
+            .supports_disassemble_request(true)
+            .supports_step_back(true)
+            .build();
+
+        let mut arguments = InitializeRequestArguments::new("headcrab");
+        arguments.supports_progress_reporting = Some(true);
+        arguments.supports_memory_references = Some(false);
+        let client = ClientCapabilities::from(&arguments);
+        assert!(client.supports_progress_reporting);
+        assert!(!client.supports_memory_references);
+        assert!(!client.supports_variable_type);
+
+        let filtered = capabilities.filter_by_client(&client);
+        assert_eq!(filtered.supports_read_memory_request, Some(false));
+        assert_eq!(filtered.supports_disassemble_request, Some(false));
+        // capabilities which were not advertised stay unset
+        assert_eq!(filtered.supports_write_memory_request, None);
+        assert_eq!(filtered.supports_step_back, Some(true));
+
+        arguments.supports_memory_references = Some(true);
+        let client = ClientCapabilities::from(&arguments);
+        assert_eq!(capabilities.filter_by_client(&client), capabilities);
+    }
+
+    #[test]
+    fn capabilities_builder_omit_unset() {
+        let capabilities = Capabilities::builder()
+            .supports_configuration_done_request(true)
+            .support_terminate_debuggee(false)
+            .exception_breakpoint_filters(vec![ExceptionBreakpointsFilter::new("panic", "Panics")])
+            .supported_checksum_algorithms(vec![ChecksumAlgorithm::Sha256])
+            .build();
+
+        assert_eq!(
+            serde_json::to_value(&capabilities).unwrap(),
+            serde_json::json!({
+                "supportsConfigurationDoneRequest": true,
+                "supportTerminateDebuggee": false,
+                "exceptionBreakpointFilters": [{ "filter": "panic", "label": "Panics" }],
+                "supportedChecksumAlgorithms": ["SHA256"]
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(Capabilities::default()).unwrap(),
+            serde_json::json!({})
+        );
+    }
+
+    #[test]
+    fn check_exception_filters() {
+        let mut signal = ExceptionBreakpointsFilter::new("signal", "Signals");
+        signal.supports_condition = Some(true);
+        let capabilities = Capabilities::builder()
+            .exception_breakpoint_filters(vec![
+                ExceptionBreakpointsFilter::new("panic", "Panics"),
+                signal,
+            ])
+            .build();
+
+        let check = |arguments: &str| {
+            let arguments: SetExceptionBreakpointsArguments =
+                serde_json::from_str(arguments).unwrap();
+            capabilities.check_exception_filters(&arguments)
+        };
+
+        assert!(check(r#"{ "filters": ["panic", "signal"] }"#).is_ok());
+        assert!(check(r#"{ "filters": [], "filterOptions": [{ "filterId": "signal", "condition": "sig == 11" }] }"#).is_ok());
+        assert!(check(r#"{ "filters": ["abort"] }"#).is_err());
+        assert!(check(
+            r#"{ "filters": [], "filterOptions": [{ "filterId": "panic", "condition": "true" }] }"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn capabilities_diff() {
+        let older = Capabilities::builder()
+            .supports_configuration_done_request(true)
+            .supports_step_back(false)
+            .completion_trigger_characters(vec![".".to_string()])
+            .supports_cancel_request(true)
+            .build();
+        let newer = Capabilities::builder()
+            .supports_configuration_done_request(true)
+            .supports_step_back(true)
+            .completion_trigger_characters(vec![".".to_string(), "::".to_string()])
+            .supports_modules_request(true)
+            .build();
+
+        assert_eq!(
+            serde_json::to_value(older.diff(&newer)).unwrap(),
+            serde_json::json!({
+                "supportsStepBack": true,
+                "completionTriggerCharacters": [".", "::"],
+                "supportsModulesRequest": true
+            })
+        );
+        assert_eq!(newer.diff(&newer), Capabilities::default());
+    }
+
+    #[test]
+    fn capabilities_from_json() {
+        let text = r#"{
+            "supportsModulesRequest": true,
+            "additionalModuleColumns": [
+                { "attributeName": "loadTime", "label": "Loaded", "type": "unixTimestampUTC" }
+            ]
+          }"#;
+
+        let capabilities: Capabilities = serde_json::from_str(text).unwrap();
+
+        assert_eq!(capabilities.supports_modules_request, Some(true));
+        assert_eq!(capabilities.supports_cancel_request, None);
+        assert_eq!(
+            capabilities.additional_module_columns.unwrap()[0].column_type,
+            Some(ColumnDescriptorType::UnixTimestampUtc)
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use serde::{Deserialize, Serialize};
+
+/// Names of checksum algorithms that may be supported by a debug adapter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChecksumAlgorithm {
+    #[serde(rename = "MD5")]
+    Md5,
+    #[serde(rename = "SHA1")]
+    Sha1,
+    #[serde(rename = "SHA256")]
+    Sha256,
+    #[serde(rename = "timestamp")]
+    Timestamp,
+}
+
+/// The checksum of an item calculated by the specified algorithm.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Checksum {
+    /**
+     * The algorithm used to calculate this checksum.
+     */
+    pub algorithm: ChecksumAlgorithm,
+
+    /**
+     * Value of the checksum.
+     */
+    pub checksum: String,
+}
+
+#[cfg(feature = "checksum")]
+impl Checksum {
+    /// Compute the checksum of the file at `path`.
+    ///
+    /// The `timestamp` algorithm gives the modification time of the file, in seconds since the
+    /// unix epoch.
+    pub fn of_file(
+        path: impl AsRef<std::path::Path>,
+        algorithm: ChecksumAlgorithm,
+    ) -> Result<Self, crate::Error> {
+        use std::fs::File;
+        use std::time::UNIX_EPOCH;
+
+        let checksum = match algorithm {
+            ChecksumAlgorithm::Md5 => digest::<md5::Md5>(File::open(path)?)?,
+            ChecksumAlgorithm::Sha1 => digest::<sha1::Sha1>(File::open(path)?)?,
+            ChecksumAlgorithm::Sha256 => digest::<sha2::Sha256>(File::open(path)?)?,
+            ChecksumAlgorithm::Timestamp => {
+                let modified = std::fs::metadata(path)?.modified()?;
+                // a modification time before the epoch is not meaningful as a checksum
+                let since_epoch = modified.duration_since(UNIX_EPOCH).unwrap_or_default();
+                since_epoch.as_secs().to_string()
+            }
+        };
+
+        Ok(Self {
+            algorithm,
+            checksum,
+        })
+    }
+
+    /// Compute the checksum of `content`.
+    ///
+    /// A content has no modification time, so the `timestamp` algorithm is rejected with
+    /// `Error::InvalidMessage`.
+    pub fn of_bytes(content: &[u8], algorithm: ChecksumAlgorithm) -> Result<Self, crate::Error> {
+        let checksum = match algorithm {
+            ChecksumAlgorithm::Md5 => digest::<md5::Md5>(content)?,
+            ChecksumAlgorithm::Sha1 => digest::<sha1::Sha1>(content)?,
+            ChecksumAlgorithm::Sha256 => digest::<sha2::Sha256>(content)?,
+            ChecksumAlgorithm::Timestamp => return Err(crate::Error::InvalidMessage),
+        };
+
+        Ok(Self {
+            algorithm,
+            checksum,
+        })
+    }
+}
+
+/// Hash the whole content of `input`, returning the digest as lowercase hexadecimal.
+#[cfg(feature = "checksum")]
+fn digest<D: md5::Digest + std::io::Write>(
+    mut input: impl std::io::Read,
+) -> Result<String, crate::Error> {
+    let mut hasher = D::new();
+    std::io::copy(&mut input, &mut hasher)?;
+    let digest = hasher.finalize();
+    Ok(digest.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn checksum_algorithm_spec_names() {
+        let algorithms = vec![
+            ChecksumAlgorithm::Md5,
+            ChecksumAlgorithm::Sha1,
+            ChecksumAlgorithm::Sha256,
+            ChecksumAlgorithm::Timestamp,
+        ];
+
+        let value = serde_json::to_value(&algorithms).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!(["MD5", "SHA1", "SHA256", "timestamp"])
+        );
+
+        let read_back: Vec<ChecksumAlgorithm> = serde_json::from_value(value).unwrap();
+        assert_eq!(read_back, algorithms);
+
+        // the names are case sensitive
+        assert!(serde_json::from_str::<ChecksumAlgorithm>(r#""md5""#).is_err());
+        assert!(serde_json::from_str::<ChecksumAlgorithm>(r#""Timestamp""#).is_err());
+    }
+
+    #[cfg(feature = "checksum")]
+    #[test]
+    fn checksum_of_file() {
+        let path =
+            std::env::temp_dir().join(format!("headcrab-dap-checksum-{}", std::process::id()));
+        std::fs::write(&path, "abc").unwrap();
+
+        let md5 = Checksum::of_file(&path, ChecksumAlgorithm::Md5).unwrap();
+        let sha1 = Checksum::of_file(&path, ChecksumAlgorithm::Sha1).unwrap();
+        let sha256 = Checksum::of_file(&path, ChecksumAlgorithm::Sha256).unwrap();
+        let timestamp = Checksum::of_file(&path, ChecksumAlgorithm::Timestamp).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(md5.checksum, "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(sha1.checksum, "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(
+            sha256.checksum,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(timestamp.algorithm, ChecksumAlgorithm::Timestamp);
+        assert!(timestamp.checksum.parse::<u64>().unwrap() > 0);
+
+        assert_eq!(
+            Checksum::of_bytes(b"abc", ChecksumAlgorithm::Md5).unwrap(),
+            md5
+        );
+        assert_eq!(
+            Checksum::of_bytes(b"abc", ChecksumAlgorithm::Sha1).unwrap(),
+            sha1
+        );
+        assert_eq!(
+            Checksum::of_bytes(b"abc", ChecksumAlgorithm::Sha256).unwrap(),
+            sha256
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+
+/// CompletionItems are the suggestions returned from the CompletionsRequest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionItem {
+    /**
+     * The label of this completion item. By default this is also the text that
+     * is inserted when selecting this completion.
+     */
+    pub label: String,
+
+    /**
+     * If text is not falsy then it is inserted instead of the label.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    /**
+     * A string that should be used when comparing this item with other items.
+     * When `falsy` the label is used.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_text: Option<String>,
+
+    /**
+     * A human-readable string with additional information about this item, like
+     * type or symbol information.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+
+    /**
+     * The item's type. Typically the client uses this information to render the
+     * item in the UI with an icon.
+     */
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub item_type: Option<CompletionItemType>,
+
+    /**
+     * This value determines the location (in the CompletionsRequest's 'text'
+     * attribute) where the completion text is added.
+     * If missing the text is added at the location specified by the
+     * CompletionsRequest's 'column' attribute.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<usize>,
+
+    /**
+     * This value determines how many characters are overwritten by the
+     * completion text.
+     * If missing the value 0 is assumed which results in the completion text
+     * being inserted.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub length: Option<usize>,
+
+    /**
+     * Determines the start of the new selection after the text has been
+     * inserted (or replaced).
+     * The start position must in the range 0 and length of the completion text.
+     * If omitted the selection starts at the end of the completion text.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_start: Option<usize>,
+
+    /**
+     * Determines the length of the new selection after the text has been
+     * inserted (or replaced).
+     * The selection can not extend beyond the bounds of the completion text.
+     * If omitted the length is assumed to be 0.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_length: Option<usize>,
+}
+
+impl CompletionItem {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            text: None,
+            sort_text: None,
+            detail: None,
+            item_type: None,
+            start: None,
+            length: None,
+            selection_start: None,
+            selection_length: None,
+        }
+    }
+}
+
+/// Some predefined types for the CompletionItem. Please note that not all clients have specific icons for all of them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CompletionItemType {
+    Method,
+    Function,
+    Constructor,
+    Field,
+    Variable,
+    Class,
+    Interface,
+    Module,
+    Property,
+    Unit,
+    Value,
+    Enum,
+    Keyword,
+    Snippet,
+    Text,
+    Color,
+    File,
+    Reference,
+    #[serde(rename = "customcolor")]
+    CustomColor,
+    /// A type which is not part of the specification.
+    #[serde(untagged)]
+    Other(String),
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn completion_item_round_trip() {
+        let item = CompletionItem {
+            item_type: Some(CompletionItemType::Function),
+            start: Some(4),
+            length: Some(2),
+            ..CompletionItem::new("println!")
+        };
+
+        let value = serde_json::to_value(&item).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({ "label": "println!", "type": "function", "start": 4, "length": 2 })
+        );
+        assert_eq!(
+            serde_json::from_value::<CompletionItem>(value).unwrap(),
+            item
+        );
+    }
+
+    #[test]
+    fn completion_item_types() {
+        let types = vec![
+            CompletionItemType::Method,
+            CompletionItemType::Enum,
+            CompletionItemType::CustomColor,
+            CompletionItemType::Other("macro".to_string()),
+        ];
+
+        let value = serde_json::to_value(&types).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!(["method", "enum", "customcolor", "macro"])
+        );
+
+        let read_back: Vec<CompletionItemType> = serde_json::from_value(value).unwrap();
+        assert_eq!(read_back, types);
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// A structured message object. Used to return errors from requests.
+///
+/// This is the `Message` type of the specification, renamed to avoid confusion with the
+/// protocol messages.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorMessage {
+    /**
+     * Unique identifier for the message.
+     */
+    pub id: usize,
+
+    /**
+     * A format string for the message. Embedded variables have the form '{name}'.
+     * If variable name starts with an underscore character, the variable does not
+     * contain user data (PII) and can be safely used for telemetry purposes.
+     */
+    pub format: String,
+
+    /**
+     * An object used as a dictionary for looking up the variables in the format
+     * string.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables: Option<HashMap<String, String>>,
+
+    /**
+     * If true send to telemetry.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub send_telemetry: Option<bool>,
+
+    /**
+     * If true show user.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_user: Option<bool>,
+
+    /**
+     * An optional url where additional information about this message can be
+     * found.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /**
+     * An optional label that is presented to the user as the UI for opening the
+     * url.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url_label: Option<String>,
+}
+
+impl ErrorMessage {
+    pub fn new(id: usize, format: impl Into<String>) -> Self {
+        Self {
+            id,
+            format: format.into(),
+            variables: None,
+            send_telemetry: None,
+            show_user: None,
+            url: None,
+            url_label: None,
+        }
+    }
+
+    /// Return the format string with each `{name}` replaced by the value of the variable `name`.
+    ///
+    /// A placeholder whose variable is missing is kept as is.
+    pub fn expand(&self) -> String {
+        let mut expanded = String::with_capacity(self.format.len());
+        let mut rest = self.format.as_str();
+
+        while let Some(start) = rest.find('{') {
+            expanded.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let end = match rest.find('}') {
+                Some(end) => end,
+                // a unclosed brace is not a placeholder
+                None => break,
+            };
+
+            let name = &rest[1..end];
+            match self.variables.as_ref().and_then(|vars| vars.get(name)) {
+                Some(value) => expanded.push_str(value),
+                None => expanded.push_str(&rest[..=end]),
+            }
+            rest = &rest[end + 1..];
+        }
+        expanded.push_str(rest);
+
+        expanded
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+
+    fn message_with_variables(format: &str, variables: &[(&str, &str)]) -> ErrorMessage {
+        let mut message = ErrorMessage::new(1, format);
+        message.variables = Some(
+            variables
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        );
+        message
+    }
+
+    #[test]
+    fn expand_variables() {
+        let message = message_with_variables(
+            "unable to read {path}: {_reason}",
+            &[("path", "/tmp/a"), ("_reason", "not found")],
+        );
+
+        assert_eq!(message.expand(), "unable to read /tmp/a: not found");
+    }
+
+    #[test]
+    fn expand_missing_variable() {
+        let message = message_with_variables("{found} and {missing}", &[("found", "here")]);
+
+        assert_eq!(message.expand(), "here and {missing}");
+        assert_eq!(ErrorMessage::new(1, "{missing}").expand(), "{missing}");
+    }
+
+    #[test]
+    fn expand_unclosed_brace() {
+        assert_eq!(ErrorMessage::new(1, "a { b").expand(), "a { b");
+    }
+
+    #[test]
+    fn show_user_survive_serialization() {
+        let mut message = ErrorMessage::new(7, "oops");
+        message.show_user = Some(true);
+
+        let value = serde_json::to_value(&message).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({ "id": 7, "format": "oops", "showUser": true })
+        );
+
+        let read_back: ErrorMessage = serde_json::from_value(value).unwrap();
+        assert_eq!(read_back.show_user, Some(true));
+    }
+}
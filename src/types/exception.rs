@@ -0,0 +1,356 @@
+use serde::{Deserialize, Serialize};
+
+/// An ExceptionBreakpointsFilter is shown in the UI as an filter option for configuring how exceptions are dealt with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExceptionBreakpointsFilter {
+    /**
+     * The internal ID of the filter option. This value is passed to the
+     * 'setExceptionBreakpoints' request.
+     */
+    pub filter: String,
+
+    /**
+     * The name of the filter option. This will be shown in the UI.
+     */
+    pub label: String,
+
+    /**
+     * An optional help text providing additional information about the exception
+     * filter. This string is typically shown as a hover and must be translated.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /**
+     * Initial value of the filter option. If not specified a value 'false' is
+     * assumed.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<bool>,
+
+    /**
+     * Controls whether a condition can be specified for this filter option. If
+     * false or missing, a condition can not be set.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_condition: Option<bool>,
+
+    /**
+     * An optional help text providing information about the condition. This
+     * string is shown as the placeholder text for a text box and must be
+     * translated.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition_description: Option<String>,
+}
+
+impl ExceptionBreakpointsFilter {
+    pub fn new(filter: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            filter: filter.into(),
+            label: label.into(),
+            description: None,
+            default: None,
+            supports_condition: None,
+            condition_description: None,
+        }
+    }
+
+    /// Return true if the filter is enabled when the client does not configure it.
+    pub fn is_default(&self) -> bool {
+        self.default.unwrap_or(false)
+    }
+}
+
+/// An ExceptionFilterOptions is used to specify an exception filter together with a condition for the setExceptionsFilter request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExceptionFilterOptions {
+    /**
+     * ID of an exception filter returned by the 'exceptionBreakpointFilters'
+     * capability.
+     */
+    pub filter_id: String,
+
+    /**
+     * An optional expression for conditional exceptions.
+     * The exception will break into the debugger if the result of the condition
+     * is true.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+}
+
+/// An ExceptionOptions assigns configuration options to a set of exceptions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExceptionOptions {
+    /**
+     * A path that selects a single or multiple exceptions in a tree. If 'path' is
+     * missing, the whole tree is selected.
+     * By convention the first segment of the path is a category that is used to
+     * group exceptions in the UI.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<Vec<ExceptionPathSegment>>,
+
+    /**
+     * Condition when a thrown exception should result in a break.
+     */
+    pub break_mode: ExceptionBreakMode,
+}
+
+impl ExceptionOptions {
+    /// Return true if these options apply to the exception found at `path` in the exception tree.
+    ///
+    /// Each segment of the options' path must match the name at the same depth. Since a path
+    /// select a whole subtree, a path shorter than `path` can match, but a longer one can not.
+    pub fn matches(&self, path: &[&str]) -> bool {
+        let segments = match &self.path {
+            Some(segments) => segments,
+            // a missing path select the whole tree
+            None => return true,
+        };
+
+        segments.len() <= path.len()
+            && segments
+                .iter()
+                .zip(path)
+                .all(|(segment, name)| segment.matches(name))
+    }
+}
+
+/// This enumeration defines all possible conditions when a thrown exception should result in a break.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExceptionBreakMode {
+    /// never breaks
+    Never,
+    /// always breaks
+    Always,
+    /// breaks when exception unhandled
+    Unhandled,
+    /// breaks if the exception is not handled by user code
+    UserUnhandled,
+}
+
+/// An ExceptionPathSegment represents a segment in a path that is used to match leafs or nodes in a tree of exceptions.
+///
+/// If a segment consists of more than one name, it matches the names provided if ‘negate’ is false or missing or it matches anything except the names provided if ‘negate’ is true.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExceptionPathSegment {
+    /**
+     * If false or missing this segment matches the names provided, otherwise it
+     * matches anything except the names provided.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub negate: Option<bool>,
+
+    /**
+     * Depending on the value of 'negate' the names that should match or not
+     * match.
+     */
+    pub names: Vec<String>,
+}
+
+impl ExceptionPathSegment {
+    /// Return true if the segment match the node named `name`.
+    pub fn matches(&self, name: &str) -> bool {
+        let listed = self.names.iter().any(|listed| listed == name);
+        listed != self.negate.unwrap_or(false)
+    }
+}
+
+/// Detailed information about an exception that has occurred.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExceptionDetails {
+    /**
+     * Message contained in the exception.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
+    /**
+     * Short type name of the exception object.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<String>,
+
+    /**
+     * Fully-qualified type name of the exception object.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_type_name: Option<String>,
+
+    /**
+     * Optional expression that can be evaluated in the current scope to obtain
+     * the exception object.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evaluate_name: Option<String>,
+
+    /**
+     * Stack trace at the time the exception was thrown.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stack_trace: Option<String>,
+
+    /**
+     * Details of the exception contained by this exception, if any.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inner_exception: Option<Vec<ExceptionDetails>>,
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn exception_options_valid() {
+        let options = r#"{
+            "path": [
+                { "names": ["Rust Panics"] },
+                { "negate": true, "names": ["std::io::Error"] }
+            ],
+            "breakMode": "userUnhandled"
+          }"#;
+
+        let options: ExceptionOptions = serde_json::from_str(options).unwrap();
+
+        assert_eq!(options.break_mode, ExceptionBreakMode::UserUnhandled);
+
+        let path = options.path.unwrap();
+        assert_eq!(path[0].negate, None);
+        assert_eq!(path[0].names, vec!["Rust Panics"]);
+        assert_eq!(path[1].negate, Some(true));
+    }
+
+    #[test]
+    fn exception_break_mode_round_trip() {
+        let modes = vec![
+            ExceptionBreakMode::Never,
+            ExceptionBreakMode::Always,
+            ExceptionBreakMode::Unhandled,
+            ExceptionBreakMode::UserUnhandled,
+        ];
+
+        let value = serde_json::to_value(&modes).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!(["never", "always", "unhandled", "userUnhandled"])
+        );
+        assert_eq!(
+            serde_json::from_value::<Vec<ExceptionBreakMode>>(value).unwrap(),
+            modes
+        );
+    }
+
+    #[test]
+    fn exception_details_nested() {
+        let details = r#"{
+            "message": "outer",
+            "innerException": [{ "message": "inner", "typeName": "io::Error" }]
+          }"#;
+
+        let details: ExceptionDetails = serde_json::from_str(details).unwrap();
+        let inner = &details.inner_exception.unwrap()[0];
+
+        assert_eq!(inner.message.as_deref(), Some("inner"));
+        assert_eq!(inner.type_name.as_deref(), Some("io::Error"));
+    }
+
+    #[test]
+    fn exception_breakpoints_filter_round_trip() {
+        let text = r#"{"filter":"signal","label":"Signals","supportsCondition":true,"conditionDescription":"e.g. sig == 11"}"#;
+
+        let filter: ExceptionBreakpointsFilter = serde_json::from_str(text).unwrap();
+
+        assert_eq!(filter.supports_condition, Some(true));
+        // a filter is disabled unless it says otherwise
+        assert!(!filter.is_default());
+        assert_eq!(serde_json::to_string(&filter).unwrap(), text);
+
+        let mut filter = ExceptionBreakpointsFilter::new("panic", "Panics");
+        filter.default = Some(true);
+        assert!(filter.is_default());
+    }
+
+    #[test]
+    fn exception_options_matches() {
+        let segment = |negate: bool, names: &[&str]| ExceptionPathSegment {
+            negate: Some(negate),
+            names: names.iter().map(|name| name.to_string()).collect(),
+        };
+        let options = |path: Option<Vec<ExceptionPathSegment>>| ExceptionOptions {
+            path,
+            break_mode: ExceptionBreakMode::Always,
+        };
+
+        let panics = segment(false, &["Rust Panics"]);
+        let not_io = segment(true, &["std::io::Error", "std::fmt::Error"]);
+
+        let cases = vec![
+            (options(None), vec!["Rust Panics", "std::io::Error"], true),
+            (options(None), vec![], true),
+            (options(Some(vec![])), vec!["Signals"], true),
+            (
+                options(Some(vec![panics.clone()])),
+                vec!["Rust Panics"],
+                true,
+            ),
+            (
+                options(Some(vec![panics.clone()])),
+                vec!["Rust Panics", "std::io::Error"],
+                true,
+            ),
+            (options(Some(vec![panics.clone()])), vec!["Signals"], false),
+            (options(Some(vec![panics.clone()])), vec![], false),
+            (
+                options(Some(vec![panics.clone(), not_io.clone()])),
+                vec!["Rust Panics", "ParseIntError"],
+                true,
+            ),
+            (
+                options(Some(vec![panics.clone(), not_io.clone()])),
+                vec!["Rust Panics", "std::io::Error"],
+                false,
+            ),
+            (
+                options(Some(vec![panics.clone(), not_io.clone()])),
+                vec!["Rust Panics", "std::fmt::Error"],
+                false,
+            ),
+            (
+                options(Some(vec![panics.clone(), not_io.clone()])),
+                vec!["Rust Panics"],
+                false,
+            ),
+            (
+                options(Some(vec![panics, not_io.clone()])),
+                vec!["Signals", "ParseIntError"],
+                false,
+            ),
+            (options(Some(vec![not_io])), vec!["Signals"], true),
+            (
+                options(Some(vec![segment(false, &["Signals", "Rust Panics"])])),
+                vec!["Signals"],
+                true,
+            ),
+        ];
+
+        for (options, path, expected) in cases {
+            assert_eq!(
+                options.matches(&path),
+                expected,
+                "{:?} matching {:?}",
+                options.path,
+                path
+            );
+        }
+    }
+}
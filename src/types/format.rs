@@ -0,0 +1,192 @@
+use serde::{Deserialize, Serialize};
+
+/// The granularity of one ‘step’ in the stepping requests ‘next’, ‘stepIn’, ‘stepOut’, and ‘stepBack’.
+///
+/// When the granularity is omitted, `Statement` is assumed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SteppingGranularity {
+    /// The step should allow the program to run until the current statement has finished executing.
+    /// The meaning of a statement is determined by the adapter and it may be considered equivalent to a line.
+    /// For example ‘for(int i = 0; i < 10; i++) could be considered to have 3 statements ‘int i = 0’, ‘i < 10’, and ‘i++’.
+    #[default]
+    Statement,
+    /// The step should allow the program to run until the current source line has executed.
+    Line,
+    /// The step should allow one instruction to execute (e.g. one x86 instruction).
+    Instruction,
+    /// A granularity which is not part of the specification.
+    #[serde(untagged)]
+    Other(String),
+}
+
+/// Provides formatting information for a value.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueFormat {
+    /**
+     * Display the value in hex.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hex: Option<bool>,
+}
+
+/// Provides formatting information for a stack frame.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackFrameFormat {
+    /**
+     * Display the value in hex.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hex: Option<bool>,
+
+    /**
+     * Displays parameters for the stack frame.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<bool>,
+
+    /**
+     * Displays the types of parameters for the stack frame.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameter_types: Option<bool>,
+
+    /**
+     * Displays the names of parameters for the stack frame.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameter_names: Option<bool>,
+
+    /**
+     * Displays the values of parameters for the stack frame.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameter_values: Option<bool>,
+
+    /**
+     * Displays the line number of the stack frame.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<bool>,
+
+    /**
+     * Displays the module of the stack frame.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module: Option<bool>,
+
+    /**
+     * Includes all stack frames, including those the debug adapter might
+     * otherwise hide.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_all: Option<bool>,
+}
+
+impl StackFrameFormat {
+    /// Return true if the client did not ask for any formatting option.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stepping_granularity_round_trip() {
+        let granularities = vec![
+            SteppingGranularity::Statement,
+            SteppingGranularity::Line,
+            SteppingGranularity::Instruction,
+        ];
+
+        let value = serde_json::to_value(&granularities).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!(["statement", "line", "instruction"])
+        );
+
+        let read_back: Vec<SteppingGranularity> = serde_json::from_value(value).unwrap();
+        assert_eq!(read_back, granularities);
+    }
+
+    #[test]
+    fn value_format_round_trip() {
+        let format = ValueFormat { hex: Some(true) };
+
+        let value = serde_json::to_value(&format).unwrap();
+        assert_eq!(value, serde_json::json!({ "hex": true }));
+        assert_eq!(
+            serde_json::from_value::<ValueFormat>(value).unwrap(),
+            format
+        );
+    }
+
+    #[test]
+    fn stack_frame_format_round_trip() {
+        let format = StackFrameFormat {
+            hex: Some(false),
+            parameter_types: Some(true),
+            include_all: Some(true),
+            ..StackFrameFormat::default()
+        };
+
+        let value = serde_json::to_value(&format).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({ "hex": false, "parameterTypes": true, "includeAll": true })
+        );
+        assert_eq!(
+            serde_json::from_value::<StackFrameFormat>(value).unwrap(),
+            format
+        );
+    }
+
+    #[test]
+    fn stack_frame_format_empty() {
+        let format: StackFrameFormat = serde_json::from_str("{}").unwrap();
+
+        assert_eq!(format, StackFrameFormat::default());
+        assert_eq!(format.parameters, None);
+        assert_eq!(format.include_all, None);
+        assert!(format.is_default());
+
+        let format = StackFrameFormat {
+            line: Some(true),
+            ..StackFrameFormat::default()
+        };
+        assert!(!format.is_default());
+    }
+
+    #[test]
+    fn stepping_granularity_default() {
+        #[derive(Deserialize)]
+        struct Arguments {
+            #[serde(default)]
+            granularity: SteppingGranularity,
+        }
+
+        let arguments: Arguments = serde_json::from_str("{}").unwrap();
+        assert_eq!(arguments.granularity, SteppingGranularity::Statement);
+
+        let arguments: Arguments = serde_json::from_str(r#"{ "granularity": "line" }"#).unwrap();
+        assert_eq!(arguments.granularity, SteppingGranularity::Line);
+
+        // a unknown granularity is kept, rather than failing the whole request
+        let arguments: Arguments =
+            serde_json::from_str(r#"{ "granularity": "expression" }"#).unwrap();
+        assert_eq!(
+            arguments.granularity,
+            SteppingGranularity::Other("expression".to_string())
+        );
+        assert_eq!(
+            serde_json::to_value(&arguments.granularity).unwrap(),
+            serde_json::json!("expression")
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Source;
+
+/// Represents a single disassembled instruction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisassembledInstruction {
+    /**
+     * The address of the instruction. Treated as a hex value if prefixed with
+     * '0x', or as a decimal value otherwise.
+     */
+    pub address: String,
+
+    /**
+     * Optional raw bytes representing the instruction and its operands, in an
+     * implementation-defined format.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instruction_bytes: Option<String>,
+
+    /**
+     * Text representing the instruction and its operands, in an
+     * implementation-defined format.
+     */
+    pub instruction: String,
+
+    /**
+     * Name of the symbol that corresponds with the location of this instruction,
+     * if any.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+
+    /**
+     * Source location that corresponds to this instruction, if any.
+     * Should always be set (if available) on the first instruction returned,
+     * but can be omitted afterwards if this instruction maps to the same source
+     * file as the previous instruction.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Source>,
+
+    /**
+     * The line within the source location that corresponds to this instruction,
+     * if any.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+
+    /**
+     * The column within the line that corresponds to this instruction, if any.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+
+    /**
+     * The end line of the range that corresponds to this instruction, if any.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+
+    /**
+     * The end column of the range that corresponds to this instruction, if any.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
+}
+
+impl DisassembledInstruction {
+    pub fn new(address: impl Into<String>, instruction: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            instruction_bytes: None,
+            instruction: instruction.into(),
+            symbol: None,
+            location: None,
+            line: None,
+            column: None,
+            end_line: None,
+            end_column: None,
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// Logical areas that can be invalidated by the ‘invalidated’ event.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InvalidatedAreas {
+    /// All previously fetched data has become invalid and needs to be refetched.
+    All,
+    /// Previously fetched stack related data has become invalid and needs to be refetched.
+    Stacks,
+    /// Previously fetched thread related data has become invalid and needs to be refetched.
+    Threads,
+    /// Previously fetched variable data has become invalid and needs to be refetched.
+    Variables,
+    /// A area which is not part of the specification.
+    #[serde(untagged)]
+    Other(String),
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn invalidated_areas_round_trip() {
+        let areas = vec![
+            InvalidatedAreas::All,
+            InvalidatedAreas::Stacks,
+            InvalidatedAreas::Threads,
+            InvalidatedAreas::Variables,
+            InvalidatedAreas::Other("registers".to_string()),
+        ];
+
+        let value = serde_json::to_value(&areas).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!(["all", "stacks", "threads", "variables", "registers"])
+        );
+
+        let read_back: Vec<InvalidatedAreas> = serde_json::from_value(value).unwrap();
+        assert_eq!(read_back, areas);
+    }
+}
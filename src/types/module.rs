@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+
+/// A Module object represents a row in the modules view.
+///
+/// Two attributes are mandatory: an id identifies a module in the modules view and is used in a
+/// ModuleEvent for identifying a module for adding, updating or deleting.
+///
+/// The name is used to minimally render the module in the UI.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Module {
+    /**
+     * Unique identifier for the module.
+     */
+    pub id: ModuleId,
+
+    /**
+     * A name of the module.
+     */
+    pub name: String,
+
+    /**
+     * optional but recommended attributes.
+     * always try to use these first before introducing additional attributes.
+     *
+     * Logical full path to the module. The exact definition is implementation
+     * defined, but usually this would be a full path to the on-disk file for the
+     * module.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /**
+     * True if the module is optimized.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_optimized: Option<bool>,
+
+    /**
+     * True if the module is considered 'user code' by a debugger that supports
+     * 'Just My Code'.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_user_code: Option<bool>,
+
+    /**
+     * Version of Module.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /**
+     * User understandable description of if symbols were found for the module
+     * (ex: 'Symbols Loaded', 'Symbols not found', etc.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol_status: Option<String>,
+
+    /**
+     * Logical full path to the symbol file. The exact definition is
+     * implementation defined.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol_file_path: Option<String>,
+
+    /**
+     * Module created or modified.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_time_stamp: Option<String>,
+
+    /**
+     * Address range covered by this module.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address_range: Option<String>,
+}
+
+impl Module {
+    /// Create a module with only its mandatory attributes.
+    pub fn new(id: impl Into<ModuleId>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            path: None,
+            is_optimized: None,
+            is_user_code: None,
+            version: None,
+            symbol_status: None,
+            symbol_file_path: None,
+            date_time_stamp: None,
+            address_range: None,
+        }
+    }
+}
+
+/// The id of a module, which the protocol allow to be either a number or a string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ModuleId {
+    Number(usize),
+    String(String),
+}
+
+impl From<usize> for ModuleId {
+    fn from(id: usize) -> Self {
+        ModuleId::Number(id)
+    }
+}
+
+impl From<String> for ModuleId {
+    fn from(id: String) -> Self {
+        ModuleId::String(id)
+    }
+}
+
+impl From<&str> for ModuleId {
+    fn from(id: &str) -> Self {
+        ModuleId::String(id.to_string())
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn module_round_trip_integer_id() {
+        let mut module = Module::new(3, "libc.so.6");
+        module.path = Some("/usr/lib/libc.so.6".to_string());
+        module.is_optimized = Some(true);
+
+        let value = serde_json::to_value(&module).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "id": 3,
+                "name": "libc.so.6",
+                "path": "/usr/lib/libc.so.6",
+                "isOptimized": true
+            })
+        );
+
+        let read_back: Module = serde_json::from_value(value).unwrap();
+        assert_eq!(read_back, module);
+        assert_eq!(read_back.id, ModuleId::Number(3));
+    }
+
+    #[test]
+    fn module_string_id() {
+        let module: Module = serde_json::from_str(r#"{ "id": "main", "name": "main" }"#).unwrap();
+
+        assert_eq!(module.id, ModuleId::String("main".to_string()));
+    }
+}
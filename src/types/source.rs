@@ -0,0 +1,240 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Checksum;
+
+/// A Source is a descriptor for source code.
+///
+/// It is returned from the debug adapter as part of a StackFrame and it is used by clients when
+/// specifying breakpoints.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Source {
+    /**
+     * The short name of the source. Every source returned from the debug adapter
+     * has a name.
+     * When sending a source to the debug adapter this name is optional.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /**
+     * The path of the source to be shown in the UI.
+     * It is only used to locate and load the content of the source if no
+     * sourceReference is specified (or its value is 0).
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /**
+     * If sourceReference > 0 the contents of the source must be retrieved through
+     * the SourceRequest (even if a path is specified).
+     * A sourceReference is only valid for a session, so it must not be used to
+     * persist a source.
+     * The value should be less than or equal to 2147483647 (2^31-1).
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_reference: Option<usize>,
+
+    /**
+     * An optional hint for how to present the source in the UI.
+     * A value of 'deemphasize' can be used to indicate that the source is not
+     * available or that it is skipped on stepping.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presentation_hint: Option<SourcePresentationHint>,
+
+    /**
+     * The (optional) origin of this source: possible values 'internal module',
+     * 'inlined content from source map', etc.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+
+    /**
+     * An optional list of sources that are related to this source. These may be
+     * the source that generated this source.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sources: Option<Vec<Source>>,
+
+    /**
+     * Optional data that a debug adapter might want to loop through the client.
+     * The client should leave the data intact and persist it across sessions. The
+     * client should not interpret the data.
+     *
+     * The data is kept as a json value: its keys keep their order, but its numbers are
+     * normalized, `1e3` being written back as `1000.0`.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adapter_data: Option<serde_json::Value>,
+
+    /**
+     * The checksums associated with this file.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksums: Option<Vec<Checksum>>,
+}
+
+impl Source {
+    /// Whether `self` and `other` designate the same source: they have the same `source_reference`,
+    /// or, if they have none, the same `path`.
+    pub(crate) fn is_same(&self, other: &Source) -> bool {
+        match self.source_reference {
+            Some(_) => other.source_reference == self.source_reference,
+            None => other.source_reference.is_none() && other.path == self.path,
+        }
+    }
+
+    /// Check `content` against every checksum of the source, returning true if they all match,
+    /// or if the source has no checksum.
+    ///
+    /// A `timestamp` checksum cannot be computed from a content, so it is rejected with
+    /// `Error::InvalidMessage`, wherever it is in the list of checksums.
+    #[cfg(feature = "checksum")]
+    pub fn verify_checksum(&self, content: &[u8]) -> Result<bool, crate::Error> {
+        let checksums = self.checksums.iter().flatten();
+        // rejected before any digest is compared, so the result does not depend on the order
+        if checksums
+            .clone()
+            .any(|checksum| checksum.algorithm == crate::ChecksumAlgorithm::Timestamp)
+        {
+            return Err(crate::Error::InvalidMessage);
+        }
+
+        for expected in checksums {
+            let actual = Checksum::of_bytes(content, expected.algorithm)?;
+            // some clients write the hexadecimal digits in uppercase
+            if !actual.checksum.eq_ignore_ascii_case(&expected.checksum) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// A hint for how to present a source in the UI.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SourcePresentationHint {
+    Normal,
+    Emphasize,
+    Deemphasize,
+    /// A hint which is not part of the specification.
+    #[serde(untagged)]
+    Other(String),
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+    use crate::ChecksumAlgorithm;
+
+    #[test]
+    fn source_round_trip() {
+        let text = r#"{"name":"main.rs","path":"/src/main.rs","presentationHint":"deemphasize","sources":[{"name":"lib.rs","sourceReference":3}],"adapterData":{"z":[1,2.5,null],"a":{"nested":"héllo"}},"checksums":[{"algorithm":"SHA256","checksum":"abcd"}]}"#;
+
+        let source: Source = serde_json::from_str(text).unwrap();
+
+        assert_eq!(
+            source.presentation_hint,
+            Some(SourcePresentationHint::Deemphasize)
+        );
+        assert_eq!(
+            source.sources.as_ref().unwrap()[0].source_reference,
+            Some(3)
+        );
+        assert_eq!(
+            source.checksums.as_ref().unwrap()[0].algorithm,
+            ChecksumAlgorithm::Sha256
+        );
+        // the adapter data is opaque to the client, its key order must survive
+        assert_eq!(serde_json::to_string(&source).unwrap(), text);
+    }
+
+    #[test]
+    fn source_adapter_data_numbers() {
+        let text = r#"{"adapterData":{"size":1e3,"ratio":1.50,"id":7}}"#;
+
+        let source: Source = serde_json::from_str(text).unwrap();
+
+        // the numbers keep their value, not their formatting
+        assert_eq!(
+            serde_json::to_string(&source).unwrap(),
+            r#"{"adapterData":{"size":1000.0,"ratio":1.5,"id":7}}"#
+        );
+    }
+
+    #[test]
+    fn source_presentation_hint() {
+        for (hint, text) in [
+            (SourcePresentationHint::Normal, "\"normal\""),
+            (SourcePresentationHint::Emphasize, "\"emphasize\""),
+            (SourcePresentationHint::Deemphasize, "\"deemphasize\""),
+            (
+                SourcePresentationHint::Other("faded".to_string()),
+                "\"faded\"",
+            ),
+        ]
+        .iter()
+        {
+            assert_eq!(serde_json::to_string(hint).unwrap(), *text);
+            assert_eq!(
+                serde_json::from_str::<SourcePresentationHint>(text).unwrap(),
+                *hint
+            );
+        }
+    }
+
+    #[test]
+    fn source_deeply_nested() {
+        let depth = 100_000;
+        let text = format!("{}{}", r#"{"sources":["#.repeat(depth), "]}".repeat(depth));
+
+        // the nesting limit of the parser is reported as an error, rather than overflowing the stack
+        assert!(serde_json::from_str::<Source>(&text).is_err());
+    }
+
+    #[cfg(feature = "checksum")]
+    #[test]
+    fn source_verify_checksum() {
+        let checksum = |algorithm, checksum: &str| Checksum {
+            algorithm,
+            checksum: checksum.to_string(),
+        };
+        let mut source = Source::default();
+        assert!(source.verify_checksum(b"abc").unwrap());
+
+        source.checksums = Some(vec![
+            checksum(ChecksumAlgorithm::Md5, "900150983cd24fb0d6963f7d28e17f72"),
+            checksum(
+                ChecksumAlgorithm::Sha256,
+                "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD",
+            ),
+        ]);
+        assert!(source.verify_checksum(b"abc").unwrap());
+        assert!(!source.verify_checksum(b"abd").unwrap());
+
+        source.checksums = Some(vec![checksum(ChecksumAlgorithm::Timestamp, "1600000000")]);
+        match source.verify_checksum(b"abc") {
+            Err(crate::Error::InvalidMessage) => (),
+            _ => panic!(),
+        }
+
+        // a mismatch does not hide the timestamp, whatever their order
+        let mismatch = checksum(ChecksumAlgorithm::Md5, "00000000000000000000000000000000");
+        let timestamp = checksum(ChecksumAlgorithm::Timestamp, "1600000000");
+        for checksums in [
+            vec![mismatch.clone(), timestamp.clone()],
+            vec![timestamp, mismatch],
+        ]
+        .iter()
+        {
+            source.checksums = Some(checksums.clone());
+            match source.verify_checksum(b"abc") {
+                Err(crate::Error::InvalidMessage) => (),
+                _ => panic!("{:?} should be rejected", checksums),
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+/// A GotoTarget describes a code location that can be used as a target in the ‘goto’ request.
+///
+/// The possible goto targets can be determined via the ‘gotoTargets’ request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GotoTarget {
+    /**
+     * Unique identifier for a goto target. This is used in the goto request.
+     */
+    pub id: usize,
+
+    /**
+     * The name of the goto target (shown in the UI).
+     */
+    pub label: String,
+
+    /**
+     * The line of the goto target.
+     */
+    pub line: usize,
+
+    /**
+     * An optional column of the goto target.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+
+    /**
+     * An optional end line of the range covered by the goto target.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+
+    /**
+     * An optional end column of the range covered by the goto target.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
+
+    /**
+     * Optional memory reference for the instruction pointer value represented
+     * by this target.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instruction_pointer_reference: Option<String>,
+}
+
+impl GotoTarget {
+    pub fn new(id: usize, label: impl Into<String>, line: usize) -> Self {
+        Self {
+            id,
+            label: label.into(),
+            line,
+            column: None,
+            end_line: None,
+            end_column: None,
+            instruction_pointer_reference: None,
+        }
+    }
+}
+
+/// A StepInTarget can be used in the ‘stepIn’ request and determines into which single target the stepIn request should step.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepInTarget {
+    /**
+     * Unique identifier for a stepIn target.
+     */
+    pub id: usize,
+
+    /**
+     * The name of the stepIn target (shown in the UI).
+     */
+    pub label: String,
+}
+
+impl StepInTarget {
+    pub fn new(id: usize, label: impl Into<String>) -> Self {
+        Self {
+            id,
+            label: label.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn goto_target_round_trip() {
+        let target = GotoTarget {
+            column: Some(5),
+            instruction_pointer_reference: Some("0x401a2c".to_string()),
+            ..GotoTarget::new(1, "main.rs:12", 12)
+        };
+
+        let value = serde_json::to_value(&target).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "id": 1,
+                "label": "main.rs:12",
+                "line": 12,
+                "column": 5,
+                "instructionPointerReference": "0x401a2c"
+            })
+        );
+        assert_eq!(serde_json::from_value::<GotoTarget>(value).unwrap(), target);
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// A Thread
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Thread {
+    /**
+     * Unique identifier for the thread.
+     */
+    pub id: usize,
+
+    /**
+     * A name of the thread.
+     */
+    pub name: String,
+}
+
+impl Thread {
+    pub fn new(id: usize, name: impl Into<String>) -> Self {
+        Self {
+            id,
+            name: name.into(),
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{VariablesArguments, VariablesArgumentsFilter};
+
+/// A Variable is a name/value pair.
+///
+/// Optionally a variable can have a ‘type’ that is shown if space permits or when hovering over
+/// the variable’s name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Variable {
+    /**
+     * The variable's name.
+     */
+    pub name: String,
+
+    /**
+     * The variable's value. This can be a multi-line text, e.g. for a function
+     * the body of a function.
+     */
+    pub value: String,
+
+    /**
+     * The type of the variable's value. Typically shown in the UI when hovering
+     * over the value.
+     * This attribute should only be returned by a debug adapter if the client
+     * has passed the value true for the 'supportsVariableType' capability of the
+     * 'initialize' request.
+     */
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub variable_type: Option<String>,
+
+    /**
+     * Properties of a variable that can be used to determine how to render the
+     * variable in the UI.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presentation_hint: Option<VariablePresentationHint>,
+
+    /**
+     * Optional evaluatable name of this variable which can be passed to the
+     * 'EvaluateRequest' to fetch the variable's value.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evaluate_name: Option<String>,
+
+    /**
+     * If variablesReference is > 0, the variable is structured and its children
+     * can be retrieved by passing variablesReference to the VariablesRequest.
+     */
+    pub variables_reference: usize,
+
+    /**
+     * The number of named child variables.
+     * The client can use this optional information to present the children in a
+     * paged UI and fetch them in chunks.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub named_variables: Option<usize>,
+
+    /**
+     * The number of indexed child variables.
+     * The client can use this optional information to present the children in a
+     * paged UI and fetch them in chunks.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexed_variables: Option<usize>,
+
+    /**
+     * Optional memory reference for the variable if the variable represents
+     * executable code, such as a function pointer.
+     * This attribute is only required if the client has passed the value true
+     * for the 'supportsMemoryReferences' capability of the 'initialize' request.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_reference: Option<String>,
+}
+
+impl Variable {
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+            variable_type: None,
+            presentation_hint: None,
+            evaluate_name: None,
+            variables_reference: 0,
+            named_variables: None,
+            indexed_variables: None,
+            memory_reference: None,
+        }
+    }
+
+    /// Return the index of a indexed variable, such as a element of a array.
+    ///
+    /// Indexed variables are named after their index, either bare (`3`) or in brackets (`[3]`).
+    pub fn index(&self) -> Option<usize> {
+        let name = self.name.trim();
+        let index = match name.strip_prefix('[') {
+            Some(name) => name.strip_suffix(']')?,
+            None => name,
+        };
+        index.parse().ok()
+    }
+
+    /// Return true for a indexed variable, false for a named one.
+    pub fn is_indexed(&self) -> bool {
+        self.index().is_some()
+    }
+}
+
+/// Optional properties of a variable that can be used to determine how to render the variable
+/// in the UI.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariablePresentationHint {
+    /**
+     * The kind of variable. Before introducing additional values, try to use the
+     * listed values.
+     * Values: 'property', 'method', 'class', 'data', 'event', 'baseClass',
+     * 'innerClass', 'interface', 'mostDerivedClass', 'virtual', 'dataBreakpoint',
+     * etc.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+
+    /**
+     * Set of attributes represented as an array of strings. Before introducing
+     * additional values, try to use the listed values.
+     * Values: 'static', 'constant', 'readOnly', 'rawString', 'hasObjectId',
+     * 'canHaveObjectId', 'hasSideEffects', etc.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attributes: Option<Vec<String>>,
+
+    /**
+     * Visibility of variable. Before introducing additional values, try to use
+     * the listed values.
+     * Values: 'public', 'private', 'protected', 'internal', 'final', etc.
+     */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+}
+
+/// Select the children of a variable answering a ‘variables’ request.
+pub struct VariableSorter;
+
+impl VariableSorter {
+    /// Return the variables of `vars` requested by `args`.
+    ///
+    /// The variables are filtered according to `filter`, then the named variables are returned
+    /// in their original order, followed by the indexed variables sorted by index. Finally the
+    /// `start`/`count` window is applied, a missing or zero `count` returning every remaining
+    /// variable. A filter unknown to the specification is ignored.
+    pub fn apply(args: &VariablesArguments, vars: Vec<Variable>) -> Vec<Variable> {
+        let (mut indexed, named): (Vec<_>, Vec<_>) =
+            vars.into_iter().partition(Variable::is_indexed);
+        // the sort is stable, so variables with the same index keep their order
+        indexed.sort_by_key(Variable::index);
+
+        let variables = match args.filter {
+            Some(VariablesArgumentsFilter::Named) => named,
+            Some(VariablesArgumentsFilter::Indexed) => indexed,
+            _ => named.into_iter().chain(indexed).collect(),
+        };
+
+        let window = variables.into_iter().skip(args.start.unwrap_or(0));
+        match args.count {
+            None | Some(0) => window.collect(),
+            Some(count) => window.take(count).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+
+    fn variables() -> Vec<Variable> {
+        vec![
+            Variable::new("[2]", "'c'"),
+            Variable::new("len", "3"),
+            Variable::new("[0]", "'a'"),
+            Variable::new("capacity", "8"),
+            Variable::new("[1]", "'b'"),
+        ]
+    }
+
+    fn names(variables: Vec<Variable>) -> Vec<String> {
+        variables
+            .into_iter()
+            .map(|variable| variable.name)
+            .collect()
+    }
+
+    fn arguments(
+        filter: Option<VariablesArgumentsFilter>,
+        start: Option<usize>,
+        count: Option<usize>,
+    ) -> VariablesArguments {
+        VariablesArguments {
+            filter,
+            start,
+            count,
+            ..VariablesArguments::new(1)
+        }
+    }
+
+    #[test]
+    fn variable_index() {
+        assert_eq!(Variable::new("[12]", "").index(), Some(12));
+        assert_eq!(Variable::new("3", "").index(), Some(3));
+        assert_eq!(Variable::new("[x]", "").index(), None);
+        assert_eq!(Variable::new("[3", "").index(), None);
+        assert!(!Variable::new("len", "").is_indexed());
+    }
+
+    #[test]
+    fn sort_variables() {
+        let all = VariableSorter::apply(&arguments(None, None, None), variables());
+        assert_eq!(names(all), ["len", "capacity", "[0]", "[1]", "[2]"]);
+
+        let named = arguments(Some(VariablesArgumentsFilter::Named), None, None);
+        assert_eq!(
+            names(VariableSorter::apply(&named, variables())),
+            ["len", "capacity"]
+        );
+
+        let indexed = arguments(Some(VariablesArgumentsFilter::Indexed), Some(1), Some(1));
+        assert_eq!(names(VariableSorter::apply(&indexed, variables())), ["[1]"]);
+
+        let unknown = arguments(
+            Some(VariablesArgumentsFilter::Other("x".into())),
+            None,
+            None,
+        );
+        assert_eq!(VariableSorter::apply(&unknown, variables()).len(), 5);
+    }
+
+    #[test]
+    fn sort_variables_window_edges() {
+        // a zero count return every variable after start
+        let zero = arguments(Some(VariablesArgumentsFilter::Indexed), Some(1), Some(0));
+        assert_eq!(
+            names(VariableSorter::apply(&zero, variables())),
+            ["[1]", "[2]"]
+        );
+
+        // a count past the end is cut short
+        let long = arguments(None, Some(3), Some(10));
+        assert_eq!(
+            names(VariableSorter::apply(&long, variables())),
+            ["[1]", "[2]"]
+        );
+
+        let past_end = arguments(None, Some(5), None);
+        assert!(VariableSorter::apply(&past_end, variables()).is_empty());
+        let far_past_end = arguments(None, Some(100), Some(2));
+        assert!(VariableSorter::apply(&far_past_end, variables()).is_empty());
+    }
+}
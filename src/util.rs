@@ -0,0 +1,136 @@
+//! Small helpers shared by the rest of the crate.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use base64::engine::general_purpose::{GeneralPurpose, STANDARD, URL_SAFE};
+use base64::Engine;
+
+use crate::Error;
+
+/// Generate the sequence numbers of the messages sent in a session.
+///
+/// The counter can be shared between threads, and never return the same number twice.
+#[derive(Debug)]
+pub struct SeqCounter {
+    next: AtomicUsize,
+}
+
+impl SeqCounter {
+    /// Create a counter whose first sequence number is `first`.
+    pub fn new(first: usize) -> Self {
+        Self {
+            next: AtomicUsize::new(first),
+        }
+    }
+
+    /// Return the next sequence number.
+    pub fn next(&self) -> usize {
+        self.next.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+impl Default for SeqCounter {
+    /// Sequence numbers start at 1.
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+/// Apply the signed `offset` of a memory or disassemble request to the address `base`.
+///
+/// Return `None` if the resulting address would be below 0 or above `u64::MAX`.
+pub fn apply_offset(base: u64, offset: i64) -> Option<u64> {
+    if offset < 0 {
+        base.checked_sub(offset.unsigned_abs())
+    } else {
+        base.checked_add(offset as u64)
+    }
+}
+
+/// The base64 alphabet of the memory contents exchanged by 'readMemory' and 'writeMemory'.
+///
+/// The specification uses the standard alphabet, which the plain accessors default to. The
+/// `_with` variants take the engine as a type parameter, e.g. `data_bytes_with::<UrlSafeBase64>()`
+/// for a client known to use the url safe alphabet.
+pub trait Base64Engine {
+    const ENGINE: GeneralPurpose;
+}
+
+/// The standard base64 alphabet, with padding.
+#[derive(Debug, Clone, Copy)]
+pub struct StandardBase64;
+
+impl Base64Engine for StandardBase64 {
+    const ENGINE: GeneralPurpose = STANDARD;
+}
+
+/// The url safe base64 alphabet, with padding.
+#[derive(Debug, Clone, Copy)]
+pub struct UrlSafeBase64;
+
+impl Base64Engine for UrlSafeBase64 {
+    const ENGINE: GeneralPurpose = URL_SAFE;
+}
+
+pub(crate) fn encode_base64<E: Base64Engine>(bytes: &[u8]) -> String {
+    E::ENGINE.encode(bytes)
+}
+
+/// Decode `data`, reporting malformed base64 as a invalid message.
+pub(crate) fn decode_base64<E: Base64Engine>(data: &str) -> Result<Vec<u8>, Error> {
+    E::ENGINE.decode(data).or(Err(Error::InvalidMessage))
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn apply_offset_bounds() {
+        assert_eq!(apply_offset(0x1000, 16), Some(0x1010));
+        assert_eq!(apply_offset(0x1000, -16), Some(0x0ff0));
+        assert_eq!(apply_offset(0x10, -16), Some(0));
+        assert_eq!(apply_offset(0x10, -17), None);
+        assert_eq!(apply_offset(u64::MAX, 1), None);
+        assert_eq!(apply_offset(u64::MAX, i64::MIN), Some(u64::MAX - (1 << 63)));
+    }
+
+    #[test]
+    fn base64_engines() {
+        let bytes = [0xfb, 0xff, 0x00];
+        assert_eq!(encode_base64::<StandardBase64>(&bytes), "+/8A");
+        assert_eq!(encode_base64::<UrlSafeBase64>(&bytes), "-_8A");
+
+        assert_eq!(decode_base64::<StandardBase64>("+/8A").unwrap(), bytes);
+        assert_eq!(decode_base64::<UrlSafeBase64>("-_8A").unwrap(), bytes);
+        assert!(decode_base64::<StandardBase64>("-_8A").is_err());
+        assert!(decode_base64::<StandardBase64>("not base64!").is_err());
+    }
+
+    #[test]
+    fn seq_counter_concurrent() {
+        let counter = Arc::new(SeqCounter::default());
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let counter = counter.clone();
+                thread::spawn(move || (0..1000).map(|_| counter.next()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let mut seen = HashSet::new();
+        for handle in handles {
+            for seq in handle.join().unwrap() {
+                assert!(seen.insert(seq), "{} was returned twice", seq);
+            }
+        }
+
+        assert_eq!(seen.len(), 8000);
+        assert_eq!(seen.iter().min(), Some(&1));
+        assert_eq!(seen.iter().max(), Some(&8000));
+    }
+}
@@ -0,0 +1,108 @@
+//! A client talking to a mock adapter, through a in-memory connection.
+
+use std::convert::TryFrom;
+use std::io;
+
+use futures::{SinkExt, StreamExt};
+use headcrab_dap::*;
+use tokio::io::{AsyncWriteExt, BufReader};
+use tokio_util::codec::Framed;
+
+#[tokio::test]
+async fn initialize_session() {
+    let (client, adapter) = tokio::io::duplex(64);
+    let (input, output) = tokio::io::split(client);
+    let client = DapClient::new(BufReader::new(input), output);
+
+    // the mock adapter answer `initialize`, followed by the `initialized` event,
+    // cancel `slow`, and reject every other request
+    let mut adapter = Framed::new(adapter, DapCodec::new());
+    let mock = tokio::spawn(async move {
+        let seq = SeqCounter::default();
+        while let Some(message) = adapter.next().await {
+            let request = Request::try_from(message.unwrap()).unwrap();
+
+            if let RequestKind::Initialize(initialize) = request.request_kind() {
+                assert_eq!(initialize.arguments().adapter_id, "mock");
+                let capabilities = Capabilities::builder()
+                    .supports_configuration_done_request(true)
+                    .build();
+                let response = Response::for_request(seq.next(), &request, capabilities).unwrap();
+                adapter
+                    .send(Message::try_from(response).unwrap())
+                    .await
+                    .unwrap();
+
+                let initialized = EventKind::Initialized.into_message(seq.next()).unwrap();
+                adapter.send(initialized).await.unwrap();
+            } else if request.command() == "slow" {
+                let cancelled = Error::Cancelled {
+                    request_seq: request.seq(),
+                };
+                let response =
+                    Response::error_for_request(seq.next(), &request, cancelled.to_error_message())
+                        .unwrap();
+                adapter
+                    .send(Message::try_from(response).unwrap())
+                    .await
+                    .unwrap();
+            } else {
+                let error = ErrorMessage::new(1, "unsupported request");
+                let response = Response::error_for_request(seq.next(), &request, error).unwrap();
+                adapter
+                    .send(Message::try_from(response).unwrap())
+                    .await
+                    .unwrap();
+            }
+        }
+    });
+
+    let capabilities: Capabilities = client
+        .send_request(InitializeRequestArguments::new("mock"))
+        .await
+        .unwrap();
+    assert_eq!(capabilities.supports_configuration_done_request, Some(true));
+
+    let initialized = Event::try_from(client.next_message().await.unwrap()).unwrap();
+    assert_eq!(initialized.event_kind().unwrap(), EventKind::Initialized);
+
+    let launch: LaunchArguments =
+        serde_json::from_value(serde_json::json!({ "program": "a.out" })).unwrap();
+    match client.send_request::<_, serde_json::Value>(launch).await {
+        Err(Error::RequestFailed { message }) => assert_eq!(message, "unsupported request"),
+        other => panic!("{:?}", other),
+    }
+
+    match client.send_custom_request("slow", ()).await {
+        Err(Error::Cancelled { request_seq }) => assert_eq!(request_seq, 3),
+        other => panic!("{:?}", other),
+    }
+
+    // once the adapter is gone, the requests can not be answered anymore
+    mock.abort();
+    assert!(mock.await.unwrap_err().is_cancelled());
+    match client.send_custom_request("threads", ()).await {
+        Err(Error::Io(err)) => assert!(matches!(
+            err.kind(),
+            io::ErrorKind::UnexpectedEof | io::ErrorKind::BrokenPipe
+        )),
+        other => panic!("{:?}", other),
+    }
+    assert!(client.next_message().await.is_none());
+}
+
+#[tokio::test]
+async fn message_too_large_end_session() {
+    let (client, mut adapter) = tokio::io::duplex(64);
+    let (input, output) = tokio::io::split(client);
+    let client = DapClient::with_max_message_size(BufReader::new(input), output, 10);
+
+    let initialized = EventKind::Initialized.into_message(1).unwrap();
+    adapter
+        .write_all(&initialized.to_bytes().unwrap())
+        .await
+        .unwrap();
+
+    // the event is larger than the limit, so the session end without yielding it
+    assert!(client.next_message().await.is_none());
+}